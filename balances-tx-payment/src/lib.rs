@@ -16,12 +16,14 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::traits::fungibles;
+use frame_support::traits::{fungibles, fungibles::Balanced};
 use pallet_asset_tx_payment::HandleCredit;
 use pallet_transaction_payment::OnChargeTransaction;
 
 pub mod balance_conversion;
 #[cfg(test)]
+mod mock;
+#[cfg(test)]
 mod tests;
 
 pub use balance_conversion::*;
@@ -53,3 +55,22 @@ where
 		// of fungibles an decrease total issuance.
 	}
 }
+
+/// Transfers the fees paid in a community currency to the treasury of that community.
+///
+/// Fees which cannot be deposited to the treasury are burned, like with `BurnCredit`.
+pub struct TransferCreditToCommunityTreasury;
+impl<T> HandleCredit<<T as frame_system::Config>::AccountId, pallet_encointer_balances::Pallet<T>>
+	for TransferCreditToCommunityTreasury
+where
+	T: frame_system::Config + pallet_encointer_balances::Config,
+{
+	fn handle_credit(
+		credit: fungibles::Credit<AccountIdOf<T>, pallet_encointer_balances::Pallet<T>>,
+	) {
+		let treasury =
+			pallet_encointer_balances::Pallet::<T>::get_community_treasury_account(credit.asset());
+		// dropping the credit, if it can't be deposited, decreases the total issuance
+		let _ = pallet_encointer_balances::Pallet::<T>::resolve(&treasury, credit);
+	}
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2019 Alain Brenzikofer
+// This file is part of Encointer
+//
+// Encointer is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Encointer is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Encointer.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Mock runtime paying transaction fees in community currency
+
+use crate::{BalanceToCommunityBalance, TransferCreditToCommunityTreasury};
+use encointer_primitives::balances::BalanceType;
+use frame_support::{
+	traits::{ConstU64, ConstU8},
+	weights::{ConstantMultiplier, IdentityFee},
+};
+use pallet_asset_tx_payment::FungiblesAdapter;
+use pallet_transaction_payment::CurrencyAdapter;
+use sp_runtime::BuildStorage;
+use test_utils::*;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+
+frame_support::construct_runtime!(
+	pub enum TestRuntime
+	{
+		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		TransactionPayment: pallet_transaction_payment::{Pallet, Storage, Event<T>},
+		AssetTxPayment: pallet_asset_tx_payment::{Pallet, Event<T>},
+		EncointerScheduler: encointer_scheduler::{Pallet, Call, Storage, Config<T>, Event},
+		EncointerBalances: encointer_balances::{Pallet, Call, Storage, Event<T>, Config<T>},
+		EncointerCeremonies: encointer_ceremonies::{Pallet, Call, Storage, Config<T>, Event<T>},
+		EncointerCommunities: encointer_communities::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+impl pallet_transaction_payment::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
+	type OperationalFeeMultiplier = ConstU8<5>;
+	type WeightToFee = IdentityFee<Balance>;
+	type LengthToFee = ConstantMultiplier<Balance, ConstU64<1>>;
+	type FeeMultiplierUpdate = ();
+}
+
+impl pallet_asset_tx_payment::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type Fungibles = EncointerBalances;
+	type OnChargeAssetTransaction =
+		FungiblesAdapter<BalanceToCommunityBalance<TestRuntime>, TransferCreditToCommunityTreasury>;
+}
+
+// boilerplate
+impl_frame_system!(TestRuntime);
+impl_balances!(TestRuntime, System);
+impl_timestamp!(TestRuntime, EncointerScheduler);
+impl_encointer_scheduler!(TestRuntime, EncointerCeremonies);
+impl_encointer_balances!(TestRuntime);
+impl_encointer_communities!(TestRuntime);
+impl_encointer_ceremonies!(TestRuntime);
+
+// genesis values
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<TestRuntime>::default().build_storage().unwrap();
+
+	encointer_balances::GenesisConfig::<TestRuntime> {
+		fee_conversion_factor: 100_000,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	encointer_ceremonies::GenesisConfig::<TestRuntime> {
+		ceremony_reward: BalanceType::from_num(1),
+		location_tolerance: LOCATION_TOLERANCE, // [m]
+		time_tolerance: TIME_TOLERANCE,         // [ms]
+		inactivity_timeout: 12,
+		endorsement_tickets_per_bootstrapper: 50,
+		endorsement_tickets_per_reputable: 2,
+		reputation_lifetime: 6,
+		meetup_time_offset: 0,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	encointer_communities::GenesisConfig::<TestRuntime> {
+		min_solar_trip_time_s: 1,
+		max_speed_mps: 83,
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	t.into()
+}
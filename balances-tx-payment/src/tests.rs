@@ -1,5 +1,16 @@
-use crate::{apply_fee_conversion_factor, ONE_MICRO_KSM};
+use crate::{
+	apply_fee_conversion_factor,
+	mock::{new_test_ext, EncointerBalances, RuntimeCall, RuntimeOrigin, TestRuntime},
+	ONE_MICRO_KSM,
+};
+use approx::assert_abs_diff_eq;
+use codec::Encode;
+use encointer_primitives::balances::BalanceType;
+use frame_support::{assert_ok, dispatch::GetDispatchInfo};
+use pallet_asset_tx_payment::ChargeAssetTxPayment;
 use rstest::*;
+use sp_runtime::traits::{Dispatchable, SignedExtension};
+use test_utils::{encointer_balances, helpers::register_test_community, AccountId, AccountKeyring};
 
 /// one unit of community currency is a fixpoint with 64 fractional bits
 const ONE_CC: u128 = 1 << 64;
@@ -21,3 +32,42 @@ fn balance_to_community_balance_works(
 	let balance = apply_fee_conversion_factor(ksm_balance, ceremony_reward, conversion_factor);
 	assert_eq!(balance, expected_community_balance);
 }
+
+#[test]
+fn transaction_fees_in_community_currency_are_credited_to_the_treasury() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		assert_ok!(EncointerBalances::issue(cid, &alice, BalanceType::from_num(100)));
+
+		let call = RuntimeCall::EncointerBalances(encointer_balances::Call::transfer {
+			dest: AccountKeyring::Bob.into(),
+			community_id: cid,
+			amount: BalanceType::from_num(1),
+		});
+		let info = call.get_dispatch_info();
+		let len = call.encoded_size();
+
+		let pre = ChargeAssetTxPayment::<TestRuntime>::from(0, Some(cid))
+			.pre_dispatch(&alice, &call, &info, len)
+			.unwrap();
+		let post_info = call.dispatch(RuntimeOrigin::signed(alice.clone())).unwrap();
+		assert_ok!(ChargeAssetTxPayment::<TestRuntime>::post_dispatch(
+			Some(pre),
+			&info,
+			&post_info,
+			len,
+			&Ok(())
+		));
+
+		let fee = BalanceType::from_num(99) - EncointerBalances::balance(cid, &alice);
+		assert!(fee > BalanceType::from_num(0));
+		// the fee is credited with the precision of the fungibles representation
+		assert_abs_diff_eq!(
+			EncointerBalances::balance(cid, &treasury).to_num::<f64>(),
+			fee.to_num::<f64>(),
+			epsilon = 1.0e-12
+		);
+	});
+}
@@ -17,19 +17,24 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use crate::weights::WeightInfo;
+use codec::{Decode, Encode};
 use core::marker::PhantomData;
 use encointer_primitives::{
 	balances::{BalanceEntry, BalanceType, Demurrage, FeeConversionFactorType},
 	communities::{validate_demurrage, CommunityIdentifier, RangeError},
 };
 use frame_support::{
-	dispatch::DispatchResult,
+	dispatch::{DispatchResult, DispatchResultWithPostInfo},
 	ensure,
 	traits::{tokens::fungibles, Get},
+	PalletId,
 };
 use frame_system::{self as frame_system, ensure_signed, pallet_prelude::BlockNumberFor};
 use log::{debug, info};
-use sp_runtime::DispatchError;
+use sp_runtime::{
+	traits::{Hash, TrailingZeroInput},
+	DispatchError,
+};
 use sp_std::convert::TryInto;
 
 // Logger target
@@ -81,6 +86,10 @@ pub mod pallet {
 		type WeightInfo: WeightInfo;
 
 		type CeremonyMaster: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Used to derive the treasury account of each community
+		#[pallet::constant]
+		type CommunityTreasuryPalletId: Get<PalletId>;
 	}
 
 	#[pallet::call]
@@ -106,10 +115,7 @@ pub mod pallet {
 			fee_conversion_factor: FeeConversionFactorType,
 		) -> DispatchResultWithPostInfo {
 			T::CeremonyMaster::ensure_origin(origin)?;
-			<FeeConversionFactor<T>>::put(fee_conversion_factor);
-			info!(target: LOG, "set fee conversion factor to {}", fee_conversion_factor);
-			Self::deposit_event(Event::FeeConversionFactorUpdated(fee_conversion_factor));
-			Ok(().into())
+			Self::do_set_fee_conversion_factor(fee_conversion_factor)
 		}
 
 		#[pallet::call_index(2)]
@@ -226,6 +232,20 @@ impl<T: Config> Pallet<T> {
 		Self::apply_demurrage(entry, Self::demurrage(&community_id))
 	}
 
+	/// The account holding the treasury of a community, derived from its identifier.
+	///
+	/// Nobody knows its private key, so it can only be spent from by democracy.
+	pub fn get_community_treasury_account(community_id: CommunityIdentifier) -> T::AccountId {
+		let treasury_identifier = [
+			<T as Config>::CommunityTreasuryPalletId::get().0.as_slice(),
+			community_id.encode().as_slice(),
+		]
+		.concat();
+		let treasury_id_hash = T::Hashing::hash_of(&treasury_identifier);
+		T::AccountId::decode(&mut TrailingZeroInput::new(treasury_id_hash.as_ref()))
+			.expect("infinite input; no invalid input for decode; qed")
+	}
+
 	pub fn total_issuance(community_id: CommunityIdentifier) -> BalanceType {
 		Self::total_issuance_entry_updated(community_id).principal
 	}
@@ -368,6 +388,15 @@ impl<T: Config> Pallet<T> {
 		<DemurragePerBlock<T>>::try_get(cid).unwrap_or_else(|_| T::DefaultDemurrage::get())
 	}
 
+	pub fn do_set_fee_conversion_factor(
+		fee_conversion_factor: FeeConversionFactorType,
+	) -> DispatchResultWithPostInfo {
+		<FeeConversionFactor<T>>::put(fee_conversion_factor);
+		info!(target: LOG, "set fee conversion factor to {}", fee_conversion_factor);
+		Self::deposit_event(Event::FeeConversionFactorUpdated(fee_conversion_factor));
+		Ok(().into())
+	}
+
 	pub fn set_demurrage(
 		cid: &CommunityIdentifier,
 		demurrage: Demurrage,
@@ -46,6 +46,7 @@ impl dut::Config for TestRuntime {
 	type ExistentialDeposit = ExistentialDeposit;
 	type WeightInfo = ();
 	type CeremonyMaster = EnsureAlice;
+	type CommunityTreasuryPalletId = CommunityTreasuryPalletId;
 }
 
 // boilerplate
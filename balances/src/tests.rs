@@ -71,6 +71,18 @@ fn burn_should_work() {
 	});
 }
 
+#[test]
+fn community_treasury_account_is_derived_from_cid() {
+	new_test_ext().execute_with(|| {
+		let cid = CommunityIdentifier::default();
+		let cid2 = CommunityIdentifier::from_str("111112Fvv9e").unwrap();
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		assert_eq!(treasury, EncointerBalances::get_community_treasury_account(cid));
+		assert_ne!(treasury, EncointerBalances::get_community_treasury_account(cid2));
+		assert_ne!(treasury, AccountId32::new([0u8; 32]));
+	});
+}
+
 #[test]
 fn transfer_should_work() {
 	new_test_ext().execute_with(|| {
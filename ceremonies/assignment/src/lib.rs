@@ -29,6 +29,35 @@ pub fn assignment_fn(
 		.and_then(|div| checked_modulo::<u64>(div, assignment_count))
 }
 
+/// Groups participants by their preferred region before they are assigned to meetups.
+///
+/// Returns the indices of the participants of each of `regions`, keeping their original order.
+/// Participants without preference, or preferring a region which is not among `regions`, are
+/// distributed evenly over all regions.
+pub fn group_by_region<Region: PartialEq>(
+	preferences: &[Option<Region>],
+	regions: &[Region],
+) -> Vec<Vec<usize>> {
+	let mut groups: Vec<Vec<usize>> = regions.iter().map(|_| vec![]).collect();
+	if regions.is_empty() {
+		return groups
+	}
+	let mut next_unspecified = 0;
+	for (i, preference) in preferences.iter().enumerate() {
+		let region_index =
+			match preference.as_ref().and_then(|p| regions.iter().position(|r| r == p)) {
+				Some(region_index) => region_index,
+				None => {
+					let region_index = next_unspecified;
+					next_unspecified = (next_unspecified + 1) % regions.len();
+					region_index
+				},
+			};
+		groups[region_index].push(i);
+	}
+	groups
+}
+
 /// Generates randomized `[AssignmentParams]` for `num_participants` to be distributed across
 /// `num_meetups`.
 pub fn generate_assignment_function_params<Hashing: Hash>(
@@ -166,6 +195,33 @@ pub fn get_meetup_location_index(
 	assignment_fn(meetup_index, location_assignment_params, locations.len() as u64)
 }
 
+/// Performs the inverse function of `get_meetup_location_index`.
+///
+/// Returns the meetup up to `meetup_count` which takes place at the location with
+/// `location_index`. The location assignment params map the meetups bijectively onto the
+/// locations, so there is at most one.
+pub fn get_location_meetup_index(
+	location_index: u64,
+	locations: &[Location],
+	location_assignment_params: AssignmentParams,
+	meetup_count: MeetupIndexType,
+) -> Option<MeetupIndexType> {
+	let m = location_assignment_params.m as i64;
+	let s1_inverse = checked_mod_inv(location_assignment_params.s1 as i64, m)?;
+	let index = (location_index as i64)
+		.checked_sub(location_assignment_params.s2 as i64)?
+		.checked_rem_euclid(m)?
+		.checked_mul(s1_inverse)?
+		.checked_rem_euclid(m)? as u64;
+	// meetup indices start at 1, so `m` takes the place of 0
+	let meetup_index = if index == 0 { m as u64 } else { index };
+
+	(meetup_index <= meetup_count &&
+		get_meetup_location_index(meetup_index, locations, location_assignment_params) ==
+			Some(location_index))
+	.then_some(meetup_index)
+}
+
 pub fn meetup_location(
 	meetup_index: MeetupIndexType,
 	locations: Vec<Location>,
@@ -221,11 +277,38 @@ mod tests {
 		) // Mon Dec 19 2022 17:17:00 UTC
 	}
 
+	#[test]
+	fn group_by_region_works() {
+		let preferences = [Some(1), None, Some(2), Some(3), None, Some(1), None];
+		assert_eq!(group_by_region(&preferences, &[1, 2]), vec![vec![0, 1, 4, 5], vec![2, 3, 6]]);
+		assert_eq!(group_by_region(&preferences, &[]), Vec::<Vec<usize>>::new());
+	}
+
 	#[test]
 	fn assignment_fn_works() {
 		assert_eq!(assignment_fn(6, AssignmentParams { m: 4, s1: 5, s2: 3 }, 5).unwrap(), 1)
 	}
 
+	#[test]
+	fn get_location_meetup_index_works() {
+		let locations = vec![Location::default(); 7];
+		let params = AssignmentParams { m: 7, s1: 3, s2: 5 };
+		for meetup_index in 1..=5 {
+			let location_index =
+				get_meetup_location_index(meetup_index, &locations, params).unwrap();
+			assert_eq!(
+				get_location_meetup_index(location_index, &locations, params, 5),
+				Some(meetup_index)
+			);
+		}
+		// the locations of meetups 6 and 7 are not in use with 5 meetups
+		for meetup_index in 6..=7 {
+			let location_index =
+				get_meetup_location_index(meetup_index, &locations, params).unwrap();
+			assert_eq!(get_location_meetup_index(location_index, &locations, params, 5), None);
+		}
+	}
+
 	#[test]
 	fn validate_equal_mapping_works() {
 		assert_eq!(
@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 use codec::{Decode, Encode, MaxEncodedLen};
+use encointer_primitives::ceremonies::VoteMajority;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_std::{vec, vec::Vec};
@@ -20,7 +21,8 @@ pub fn get_participant_judgements(
 	participants: &Participants,
 	participant_votes: &Vec<u32>,
 	participant_attestations: &Attestations,
-	attestation_threshold_fn: fn(usize) -> usize,
+	attestation_threshold_fn: impl Fn(usize) -> usize,
+	vote_majority: VoteMajority,
 ) -> Result<ParticipantJudgements, MeetupValidationError> {
 	let mut participant_judgements = ParticipantJudgements {
 		legit: participants.clone(),
@@ -33,7 +35,7 @@ pub fn get_participant_judgements(
 	)?);
 
 	let (n_confirmed, _num_votes, vote_is_unanimous) =
-		find_majority_vote(&participant_judgements.legit, participant_votes)?;
+		find_majority_vote(&participant_judgements.legit, participant_votes, vote_majority)?;
 
 	participant_judgements.exclude_participants(get_excluded_participants_wrong_vote(
 		&participant_judgements.legit,
@@ -156,7 +158,7 @@ fn get_excluded_participants_wrong_vote(
 fn get_excluded_participants_num_attestations(
 	participants: &Participants,
 	participant_attestations: Attestations,
-	threshold_fn: fn(usize) -> usize,
+	threshold_fn: impl Fn(usize) -> usize,
 ) -> Result<Vec<(usize, ExclusionReason)>, MeetupValidationError> {
 	let mut relevant_attestations = filter_attestations(participants, participant_attestations);
 
@@ -229,6 +231,7 @@ fn get_excluded_participants_num_attestations(
 fn find_majority_vote(
 	participants: &Participants,
 	participant_votes: &Vec<u32>,
+	vote_majority: VoteMajority,
 ) -> Result<(u32, u32, bool), MeetupValidationError> {
 	let mut n_vote_candidates: Vec<(u32, u32)> = vec![];
 	for i in participants {
@@ -249,6 +252,14 @@ fn find_majority_vote(
 	}
 	let (n_confirmed, vote_count) = n_vote_candidates.get_or_err(0)?;
 	let vote_is_unanimous = n_vote_candidates.len() == 1;
+	let vote_is_dependable = match vote_majority {
+		VoteMajority::Plurality => true,
+		VoteMajority::AbsoluteMajority => *vote_count as usize * 2 > participants.len(),
+		VoteMajority::Unanimity => vote_is_unanimous,
+	};
+	if !vote_is_dependable {
+		return Err(MeetupValidationError::NoDependableVote)
+	}
 	Ok((*n_confirmed, *vote_count, vote_is_unanimous))
 }
 
@@ -45,6 +45,7 @@ fn n_attendees_do_not_attest_anyone(meetup_size: usize, num_attendees: usize, n:
 			&participant_votes,
 			&participant_attestations,
 			|n| n - 1,
+			VoteMajority::Plurality,
 		)
 		.unwrap()
 		.legit,
@@ -108,6 +109,7 @@ fn attendee_is_not_attested_by_n_others(meetup_size: usize, num_attendees: usize
 			&participant_votes,
 			&participant_attestations,
 			|n| n - 1,
+			VoteMajority::Plurality,
 		)
 		.unwrap()
 		.legit,
@@ -189,6 +191,7 @@ fn adversary_holds_n_assignee_keys_and_self_attests(
 			&participant_votes,
 			&participant_attestations,
 			|n| n - 1,
+			VoteMajority::Plurality,
 		)
 		.unwrap()
 		.legit,
@@ -264,6 +267,7 @@ fn n_attendees_vote_plus_one_and_attest_absent_assignee(
 			&participant_votes,
 			&participant_attestations,
 			|n| n - 1,
+			VoteMajority::Plurality,
 		)
 		.unwrap()
 		.legit,
@@ -313,6 +317,7 @@ fn adversary_holds_n_assignee_keys_and_self_attests_and_votes_like_honest_partic
 		&participant_votes,
 		&participant_attestations,
 		|i: usize| max(if i > 5 { i.saturating_sub(2) } else { i.saturating_sub(1) }, 1),
+		VoteMajority::Plurality,
 	)
 	.unwrap()
 	.legit;
@@ -366,6 +371,7 @@ fn n_honest_participants_dont_have_internet_and_n_attackers_try_to_provoke_early
 		&participant_votes,
 		&participant_attestations,
 		|i: usize| max(if i > 5 { i.saturating_sub(2) } else { i.saturating_sub(1) }, 1),
+		VoteMajority::Plurality,
 	)
 	.unwrap()
 	.early_rewards_possible;
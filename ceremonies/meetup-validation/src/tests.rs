@@ -99,14 +99,50 @@ fn get_excluded_participants_num_attestations_works() {
 fn find_majority_vote_works() {
 	let participants: Participants = vec![0, 1, 2, 3, 4];
 	let participant_votes: Vec<u32> = vec![1, 1, 2, 3, 1];
-	assert_eq!(find_majority_vote(&participants, &participant_votes), Ok((1u32, 3u32, false)));
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::Plurality),
+		Ok((1u32, 3u32, false))
+	);
 }
 
 #[test]
 fn find_majority_vote_works_with_unanimous_vote() {
 	let participants: Participants = vec![0, 1, 2, 3, 4];
 	let participant_votes: Vec<u32> = vec![1, 1, 1, 1, 1];
-	assert_eq!(find_majority_vote(&participants, &participant_votes), Ok((1u32, 5u32, true)));
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::Plurality),
+		Ok((1u32, 5u32, true))
+	);
+}
+
+#[test]
+fn find_majority_vote_with_absolute_majority_works() {
+	let participants: Participants = vec![0, 1, 2, 3, 4, 5];
+	let participant_votes: Vec<u32> = vec![1, 1, 2, 3, 1, 1];
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::AbsoluteMajority),
+		Ok((1u32, 4u32, false))
+	);
+	let participant_votes: Vec<u32> = vec![1, 1, 2, 3, 1, 2];
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::AbsoluteMajority),
+		Err(MeetupValidationError::NoDependableVote)
+	);
+}
+
+#[test]
+fn find_majority_vote_with_unanimity_works() {
+	let participants: Participants = vec![0, 1, 2, 3, 4];
+	let participant_votes: Vec<u32> = vec![1, 1, 1, 1, 2];
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::Unanimity),
+		Err(MeetupValidationError::NoDependableVote)
+	);
+	let participant_votes: Vec<u32> = vec![1, 1, 1, 1, 1];
+	assert_eq!(
+		find_majority_vote(&participants, &participant_votes, VoteMajority::Unanimity),
+		Ok((1u32, 5u32, true))
+	);
 }
 
 #[test]
@@ -208,6 +244,7 @@ fn validate_participant_judgements(
 			&participant_votes,
 			&participant_attestations,
 			attestation_threshold_fn,
+			VoteMajority::Plurality,
 		)
 		.unwrap(),
 		participant_judgements
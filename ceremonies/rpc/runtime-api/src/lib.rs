@@ -22,7 +22,10 @@
 use sp_std::vec::Vec;
 
 use encointer_primitives::{
-	ceremonies::{AggregatedAccountData, CeremonyIndexType, CeremonyInfo, CommunityReputation},
+	ceremonies::{
+		AggregatedAccountData, AssignmentForecast, CeremonyIndexType, CeremonyInfo, CeremonyStats,
+		CommunityReputation, ReputationScore,
+	},
 	communities::CommunityIdentifier,
 };
 use sp_api::{Decode, Encode};
@@ -34,7 +37,10 @@ sp_api::decl_runtime_apis! {
 
 	{
 		fn get_reputations(account: &AccountId) -> Vec<(CeremonyIndexType, CommunityReputation)>;
+		fn get_reputation_score(cid: CommunityIdentifier, account: &AccountId) -> ReputationScore;
 		fn get_aggregated_account_data(cid:CommunityIdentifier, account: &AccountId) -> AggregatedAccountData<AccountId, Moment>;
 		fn get_ceremony_info() -> CeremonyInfo;
+		fn get_ceremony_stats(cid: CommunityIdentifier, cindex: CeremonyIndexType) -> CeremonyStats;
+		fn get_assignment_forecast(cid: CommunityIdentifier) -> Option<AssignmentForecast>;
 	}
 }
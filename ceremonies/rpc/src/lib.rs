@@ -28,8 +28,9 @@ use std::sync::Arc;
 use encointer_ceremonies_rpc_runtime_api::CeremoniesApi as CeremoniesRuntimeApi;
 use encointer_primitives::{
 	ceremonies::{
-		reputation_cache_dirty_key, reputation_cache_key, AggregatedAccountData, CeremonyInfo,
-		CommunityReputation, ReputationCacheValue,
+		reputation_cache_dirty_key, reputation_cache_key, AggregatedAccountData,
+		AssignmentForecast, CeremonyInfo, CeremonyStats, CommunityReputation, ReputationCacheValue,
+		ReputationScore,
 	},
 	communities::CommunityIdentifier,
 	scheduler::CeremonyIndexType,
@@ -48,6 +49,30 @@ where
 		at: Option<BlockHash>,
 	) -> RpcResult<Vec<(CeremonyIndexType, CommunityReputation)>>;
 
+	#[method(name = "encointer_getReputationScore")]
+	fn get_reputation_score(
+		&self,
+		cid: CommunityIdentifier,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<ReputationScore>;
+
+	#[method(name = "encointer_getCeremonyStats")]
+	fn get_ceremony_stats(
+		&self,
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		at: Option<BlockHash>,
+	) -> RpcResult<CeremonyStats>;
+
+	// returns `null` outside of the REGISTERING phase
+	#[method(name = "encointer_getAssignmentForecast")]
+	fn get_assignment_forecast(
+		&self,
+		cid: CommunityIdentifier,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<AssignmentForecast>>;
+
 	// For rpc calls that need a while block should help the rpc server to
 	// spawn it with `tokio.spawn_blocking` to keep the rpc server responsive
 	// for calls that take longer. (not 100% sure if I understand correctly.)
@@ -180,6 +205,41 @@ where
 		Ok(self.refresh_reputation_cache(account, ceremony_info, at)?.reputation)
 	}
 
+	fn get_reputation_score(
+		&self,
+		cid: CommunityIdentifier,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<ReputationScore> {
+		let api = self.client.runtime_api();
+		Ok(api
+			.get_reputation_score(self.resolve_at(at), cid, &account)
+			.map_err(|e| Error::Runtime(e.into()))?)
+	}
+
+	fn get_ceremony_stats(
+		&self,
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<CeremonyStats> {
+		let api = self.client.runtime_api();
+		Ok(api
+			.get_ceremony_stats(self.resolve_at(at), cid, cindex)
+			.map_err(|e| Error::Runtime(e.into()))?)
+	}
+
+	fn get_assignment_forecast(
+		&self,
+		cid: CommunityIdentifier,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AssignmentForecast>> {
+		let api = self.client.runtime_api();
+		Ok(api
+			.get_assignment_forecast(self.resolve_at(at), cid)
+			.map_err(|e| Error::Runtime(e.into()))?)
+	}
+
 	fn get_aggregated_account_data(
 		&self,
 		cid: CommunityIdentifier,
@@ -1,10 +1,12 @@
 use crate::*;
-use encointer_primitives::communities::{CommunityIdentifier, CommunityMetadata, Degree, Location};
+use encointer_primitives::communities::{
+	CommunityIdentifier, CommunityMetadata, CommunityRules, Degree, GeoHash, Location,
+};
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
 use sp_core::{crypto::ByteArray, sr25519};
-use sp_runtime::RuntimeAppPublic;
+use sp_runtime::{Perbill, RuntimeAppPublic};
 
 /// Our own little test-crypto module because we can't use the `sp-core::sr25519` signing methods
 /// in the runtime.
@@ -43,7 +45,10 @@ fn test_location() -> Location {
 }
 
 fn create_community<T: Config>() -> CommunityIdentifier {
-	let location = test_location();
+	create_community_at::<T>(test_location())
+}
+
+fn create_community_at<T: Config>(location: Location) -> CommunityIdentifier {
 	let bs = bootstrappers::<T>();
 
 	encointer_communities::Pallet::<T>::new_community(
@@ -195,6 +200,28 @@ benchmarks! {
 		);
 	}
 
+	register_participant_in_region {
+		let cid = create_community::<T>();
+
+		let zoran = generate_pair();
+		let zoran_account= account_id::<T>(&zoran);
+		let proof = fake_last_attendance_and_get_proof::<T>(&zoran, cid);
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+
+		let location = test_location();
+		let region = GeoHash::try_from_params(location.lat, location.lon).unwrap();
+
+		assert_eq!(ReputableCount::<T>::get((cid, cindex)), 0);
+	}: _(RawOrigin::Signed(zoran_account.clone()), cid, Some(proof), region.clone())
+	verify {
+		assert_eq!(ReputableCount::<T>::get((cid, cindex)), 1);
+		assert_eq!(PreferredRegions::<T>::get((cid, cindex), &zoran_account), Some(region));
+		assert_eq!(
+			Pallet::<T>::participant_reputation((cid, cindex - 1), zoran_account),
+			Reputation::VerifiedLinked
+		);
+	}
+
 	upgrade_registration {
 		let cid = create_community::<T>();
 
@@ -303,6 +330,44 @@ benchmarks! {
 		assert_eq!(<EndorseesCount<T>>::get((cid, cindex)), 1);
 	}
 
+	endorse_and_register_batch {
+		// the component ranges are halved, such that both fit into a batch
+		let n in 0 .. T::MaxEndorsementBatchSize::get() / 2;
+		let f in 0 .. T::MaxEndorsementBatchSize::get() / 2;
+		let cid = create_community::<T>();
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+
+		// we let the newbies be endorsed by a reputable as this is the worst case scenario
+		let zoran = account_id::<T>(&generate_pair());
+		Pallet::<T>::fake_reputation((cid, cindex - 1), &zoran, Reputation::VerifiedUnlinked);
+
+		// issue some income such that newbies are allowed to register
+		assert_ok!(encointer_balances::Pallet::<T>::issue(
+			cid,
+			&zoran,
+			NominalIncome::from_num(1)
+		));
+
+		// the last `f` consents fail after their signature has been verified, as the tickets of
+		// the endorser are used up
+		EndorsementTicketsPerReputable::<T>::put(n as EndorsementTicketsType);
+		let consents: Vec<RegistrationConsentOf<T>> = (0..n + f).map(|_| {
+			let newbie = generate_pair();
+			let mut consent = RegistrationConsent {
+				newbie: account_id::<T>(&newbie),
+				endorser: zoran.clone(),
+				ceremony_index: cindex,
+				community_identifier: cid,
+				newbie_signature: T::Signature::from(sr25519::Signature::from_raw([0u8; 64])),
+			};
+			consent.newbie_signature = T::Signature::from(sign(&newbie, &consent.payload_encoded()));
+			consent
+		}).collect();
+	}: _(RawOrigin::Signed(zoran), cid, BoundedVec::try_from(consents).unwrap())
+	verify {
+		assert_eq!(<EndorseesCount<T>>::get((cid, cindex)), n as u64);
+	}
+
 	claim_rewards {
 		frame_system::Pallet::<T>::set_block_number(frame_system::Pallet::<T>::block_number() + 1u32.into()); // this is needed to assert events
 		let cid = create_community::<T>();
@@ -316,12 +381,14 @@ benchmarks! {
 		let time = crate::Pallet::<T>::get_meetup_time(loc).expect("Could not get meetup time");
 		let mindex = 1;
 
-		// attest_attendees
-		for attestor in users.iter() {
+		// attest_attendees. the last user is attested by too few participants to be rewarded, so
+		// all the others who attested them are penalized
+		let absentee = users[9].clone();
+		for (i, attestor) in users.iter().enumerate() {
 			assert_ok!(Pallet::<T>::attest_attendees(
 				RawOrigin::Signed(attestor.clone()).into(),
 				cid, 10,
-				BoundedVec::try_from(users.clone().into_iter().filter(|u| u!= attestor).collect::<Vec<T::AccountId>>()).unwrap()
+				BoundedVec::try_from(users.clone().into_iter().filter(|u| u != attestor && (i < 7 || *u != absentee)).collect::<Vec<T::AccountId>>()).unwrap()
 			));
 		}
 
@@ -330,8 +397,9 @@ benchmarks! {
 
 	}: _(RawOrigin::Signed(users[0].clone()), cid, None)
 	verify {
-		assert_eq!(last_event::<T>(), Some(Event::RewardsIssued(cid, 1, 10).into()));
+		assert_eq!(last_event::<T>(), Some(Event::RewardsIssued(cid, 1, 9).into()));
 		assert!(IssuedRewards::<T>::contains_key((cid, cindex), mindex));
+		assert_eq!(Penalties::<T>::iter_prefix(cid).count(), 7);
 	}
 
 	set_inactivity_timeout {
@@ -388,6 +456,133 @@ benchmarks! {
 		assert_eq!(ReputableCount::<T>::get((cid, cindex)), 0);
 	}
 
+	attest_meetup {
+		let n in 2 .. T::MaxAttestations::get();
+		let cid = create_community::<T>();
+		let users = register_users::<T>(cid, 2, 8).into_iter().take(n as usize).collect::<Vec<_>>();
+
+		next_phase::<T>();
+		next_phase::<T>();
+
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+		let loc = test_location();
+		let time = crate::Pallet::<T>::get_meetup_time(loc).expect("Could not get meetup time");
+		let mindex = 1;
+
+		let claimants = users.iter().map(|u| account_id::<T>(u)).collect::<Vec<_>>();
+		let claims = BoundedVec::try_from(users.iter().map(|u| {
+			let mut claim = ClaimOfAttendance::new_unsigned(account_id::<T>(u), cindex, cid, mindex, loc, time, 10);
+			claim.claimant_signature = Some(T::Signature::from(sign(u, &claim.bundle_payload_encoded(&claimants))));
+			claim
+		}).collect::<Vec<_>>()).unwrap();
+		let submitter = account_id::<T>(&users[0]);
+	}: _(RawOrigin::Signed(submitter.clone()), cid, claims)
+	verify {
+		assert_eq!(AttestationCount::<T>::get((cid, cindex)), n as u64);
+		assert_eq!(MeetupParticipantCountVote::<T>::get((cid, cindex), &submitter), 10);
+	}
+
+	set_meetup_validation_policy {
+		let cid = create_community::<T>();
+		let policy = MeetupValidationPolicy {
+			min_attestation_ratio: Some(Perbill::from_percent(60)),
+			vote_majority: VoteMajority::AbsoluteMajority,
+			early_rewards_allowed: false,
+		};
+	}: _(RawOrigin::Root, cid, policy)
+	verify {
+		assert_eq!(MeetupValidationPolicies::<T>::get(cid), policy);
+	}
+
+	choose_meetup_time {
+		let cid = create_community::<T>();
+		assert_ok!(encointer_communities::Pallet::<T>::update_community_metadata(
+			RawOrigin::Root.into(),
+			cid,
+			CommunityMetadata { rules: CommunityRules::LoCoFlex, ..Default::default() }
+		));
+		let participant = account_id::<T>(&register_users::<T>(cid, 2, 7)[0]);
+
+		next_phase::<T>();
+
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+		let meetup_index = Pallet::<T>::get_meetup_index((cid, cindex), &participant).unwrap();
+		let (start, _) = Pallet::<T>::attesting_phase_window();
+	}: _(RawOrigin::Signed(participant), cid, start)
+	verify {
+		assert_eq!(MeetupTimeSlot::<T>::get((cid, cindex), meetup_index), Some(start));
+	}
+
+	add_partner_community {
+		let cid = create_community::<T>();
+		let partner = create_community_at::<T>(Location {
+			lat: Degree::from_num(10i32),
+			lon: Degree::from_num(10i32),
+		});
+		// all but one partner slots are taken
+		for i in 1..T::MaxPartnerCommunities::get() {
+			let other = CommunityIdentifier::new(test_location(), vec![i]).unwrap();
+			PartnerCommunities::<T>::insert(cid, other, ());
+		}
+	}: _(RawOrigin::Root, cid, partner)
+	verify {
+		assert!(Pallet::<T>::is_trusted_community(&cid, &partner));
+	}
+
+	remove_partner_community {
+		let cid = create_community::<T>();
+		let partner = create_community_at::<T>(Location {
+			lat: Degree::from_num(10i32),
+			lon: Degree::from_num(10i32),
+		});
+		assert_ok!(Pallet::<T>::do_add_partner_community(cid, partner));
+	}: _(RawOrigin::Root, cid, partner)
+	verify {
+		assert!(!Pallet::<T>::is_trusted_community(&cid, &partner));
+	}
+
+	set_waitlist_auto_registration {
+		let cid = create_community::<T>();
+		let participant: T::AccountId = account("participant", 1, 1);
+	}: _(RawOrigin::Signed(participant.clone()), cid, true)
+	verify {
+		assert!(WaitlistAutoRegistration::<T>::get(cid, &participant));
+	}
+
+	cancel_meetup {
+		// reputables are the worst case, as their linked reputation is restored
+		let n in 3 .. T::MeetupSizeTarget::get() as u32;
+		let cid = create_community::<T>();
+		register_users::<T>(cid, 0, n);
+
+		next_phase::<T>();
+		next_phase::<T>();
+
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+		assert_eq!(Pallet::<T>::get_meetup_participants((cid, cindex), 1).unwrap().len(), n as usize);
+	}: _(RawOrigin::Root, cid, cindex, 1)
+	verify {
+		assert!(CancelledMeetups::<T>::contains_key((cid, cindex), 1));
+	}
+
+	reschedule_meetup {
+		let cid = create_community::<T>();
+		let location = Location { lat: Degree::from_num(2i32), lon: Degree::from_num(2i32) };
+		assert_ok!(encointer_communities::Pallet::<T>::add_location(
+			RawOrigin::Root.into(),
+			cid,
+			location
+		));
+		register_users::<T>(cid, 2, 8);
+
+		next_phase::<T>();
+		next_phase::<T>();
+
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+	}: _(RawOrigin::Root, cid, cindex, 1, location)
+	verify {
+		assert_eq!(Pallet::<T>::get_meetup_location((cid, cindex), 1), Some(location));
+	}
 }
 
 impl_benchmark_test_suite!(Pallet, crate::benchmarking::new_test_ext(), crate::mock::TestRuntime);
@@ -27,7 +27,8 @@
 
 use codec::{Decode, Encode};
 use encointer_ceremonies_assignment::{
-	assignment_fn_inverse, generate_assignment_function_params, get_meetup_location_index,
+	assignment_fn_inverse, generate_assignment_function_params, get_location_meetup_index,
+	get_meetup_location_index, group_by_region,
 	math::{checked_ceil_division, find_prime_below, find_random_coprime_below},
 	meetup_index, meetup_location, meetup_time,
 };
@@ -35,30 +36,54 @@ use encointer_meetup_validation::*;
 use encointer_primitives::{
 	balances::BalanceType,
 	ceremonies::*,
-	communities::{CommunityIdentifier, Location, NominalIncome},
+	communities::{CommunityIdentifier, CommunityRules, GeoHash, Location, NominalIncome},
 	scheduler::{CeremonyIndexType, CeremonyPhaseType},
+	vouches::VouchesProvider,
 	RandomNumberGenerator,
 };
 use encointer_scheduler::OnCeremonyPhaseChange;
 use frame_support::{
-	dispatch::{DispatchResult, DispatchResultWithPostInfo, Pays},
+	dispatch::{DispatchResult, DispatchResultWithPostInfo, Pays, PostDispatchInfo},
 	ensure,
+	storage::with_storage_layer,
 	traits::{Get, Randomness},
+	weights::Weight,
 	BoundedVec,
 };
 use frame_system::ensure_signed;
 use log::{debug, error, info, trace, warn};
 use scale_info::TypeInfo;
 use sp_core::bounded::BoundedSlice;
-use sp_runtime::traits::{IdentifyAccount, Member, Verify};
-use sp_std::{
-	cmp::{max, min},
-	prelude::*,
-	vec,
+use sp_runtime::{
+	traits::{IdentifyAccount, Member, Saturating, Verify},
+	DispatchError, PerThing, Permill,
 };
+use sp_std::{cmp::min, collections::btree_set::BTreeSet, prelude::*, vec};
 // Logger target
 const LOG: &str = "encointer";
 
+pub type ClaimOfAttendanceOf<T> = ClaimOfAttendance<
+	<T as pallet::Config>::Signature,
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_timestamp::Config>::Moment,
+>;
+
+pub type RegistrationConsentOf<T> =
+	RegistrationConsent<<T as pallet::Config>::Signature, <T as frame_system::Config>::AccountId>;
+
+/// Minimum number of meetups the participants of a region have to be assigned to for the region
+/// to be considered in the meetup assignment.
+const MIN_MEETUPS_PER_REGION: u64 = 2;
+
+/// Participants of a ceremony grouped by the regions considered for the meetup assignment.
+struct RegionalGrouping {
+	regions: Vec<GeoHash>,
+	/// indices of the bootstrappers, reputables, endorsees and newbies of each region
+	groups: [Vec<Vec<usize>>; 4],
+	/// registered participants, number of locations and assignment allowance of each region
+	allowances: Vec<(AssignmentCount, u64, AssignmentCount)>,
+}
+
 pub use pallet::*;
 pub use weights::WeightInfo;
 
@@ -104,9 +129,49 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttestations: Get<u32>;
 
+		/// Share of each ceremony reward which is issued to the community treasury instead of
+		/// to the participant
+		#[pallet::constant]
+		type CommunityTreasuryShare: Get<Permill>;
+
+		/// Source of the vouches which replace attestations in communities running
+		/// `CommunityRules::BeeDance`
+		type Vouches: VouchesProvider<Self::AccountId, Self::Moment>;
+
+		/// Number of penalties within the reputation lifetime after which a participant's newbie
+		/// tickets are withheld and they can no longer register as bootstrapper or reputable
+		#[pallet::constant]
+		type PenaltyThreshold: Get<PenaltyCountType>;
+
+		/// Maximum number of newbies which can be endorsed and registered in one call
+		#[pallet::constant]
+		type MaxEndorsementBatchSize: Get<u32>;
+
+		/// Maximum number of meetups which can be evaluated in one `claim_rewards_batch` call
+		#[pallet::constant]
+		type MaxClaimBatch: Get<u32>;
+
+		/// Maximum number of unassigned participants which are waitlisted per community ceremony
+		#[pallet::constant]
+		type MaxWaitlistSize: Get<u32>;
+
+		/// Maximum number of partner communities a community can trust
+		#[pallet::constant]
+		type MaxPartnerCommunities: Get<u32>;
+
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used_weight = Self::issue_pending_rewards(remaining_weight);
+			used_weight.saturating_add(Self::register_waitlisted_participants(
+				remaining_weight.saturating_sub(used_weight),
+			))
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
@@ -117,69 +182,7 @@ pub mod pallet {
 			proof: Option<ProofOfAttendance<T::Signature, T::AccountId>>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			let current_phase = <encointer_scheduler::Pallet<T>>::current_phase();
-			ensure!(
-				CeremonyPhaseType::is_registering_or_attesting(&current_phase),
-				Error::<T>::RegisteringOrAttestationPhaseRequired
-			);
-
-			ensure!(
-				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
-				Error::<T>::InexistentCommunity
-			);
-
-			let mut cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
-
-			if current_phase == CeremonyPhaseType::Attesting {
-				cindex += 1
-			};
-
-			if Self::is_registered(cid, cindex, &sender) {
-				return Err(<Error<T>>::ParticipantAlreadyRegistered.into())
-			}
-
-			if let Some(p) = &proof {
-				// we accept proofs from other communities as well. no need to ensure cid
-				ensure!(sender == p.prover_public, Error::<T>::WrongProofSubject);
-				ensure!(p.ceremony_index < cindex, Error::<T>::ProofAcausal);
-				ensure!(
-					p.ceremony_index >= cindex.saturating_sub(Self::reputation_lifetime()),
-					Error::<T>::ProofOutdated
-				);
-				ensure!(
-					Self::participant_reputation(
-						(p.community_identifier, p.ceremony_index),
-						&p.attendee_public
-					) == Reputation::VerifiedUnlinked,
-					Error::<T>::AttendanceUnverifiedOrAlreadyUsed
-				);
-				if Self::verify_attendee_signature(p.clone()).is_err() {
-					return Err(<Error<T>>::BadProofOfAttendanceSignature.into())
-				};
-
-				// this reputation must now be burned so it can not be used again
-				<ParticipantReputation<T>>::insert(
-					(p.community_identifier, p.ceremony_index),
-					&p.attendee_public,
-					Reputation::VerifiedLinked,
-				);
-				// register participant as reputable
-				<ParticipantReputation<T>>::insert(
-					(cid, cindex),
-					&sender,
-					Reputation::UnverifiedReputable,
-				);
-			};
-
-			let participant_type = Self::register(cid, cindex, &sender, proof.is_some())?;
-
-			// invalidate reputation cache
-			sp_io::offchain_index::set(&reputation_cache_dirty_key(&sender), &true.encode());
-
-			debug!(target: LOG, "registered participant: {:?} as {:?}", sender, participant_type);
-			Self::deposit_event(Event::ParticipantRegistered(cid, participant_type, sender));
-
-			Ok(().into())
+			Self::do_register_participant(sender, cid, proof, None)
 		}
 
 		#[pallet::call_index(1)]
@@ -189,7 +192,7 @@ pub mod pallet {
 			cid: CommunityIdentifier,
 			proof: ProofOfAttendance<T::Signature, T::AccountId>,
 		) -> DispatchResultWithPostInfo {
-			let sender = ensure_signed(origin.clone())?;
+			let sender = ensure_signed(origin)?;
 			let current_phase = <encointer_scheduler::Pallet<T>>::current_phase();
 			ensure!(
 				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
@@ -210,8 +213,9 @@ pub mod pallet {
 			let participant_type = Self::get_participant_type((cid, cindex), &sender)
 				.ok_or(<Error<T>>::ParticipantIsNotRegistered)?;
 			if participant_type == ParticipantType::Newbie {
+				let preferred_region = Self::preferred_region((cid, cindex), &sender);
 				Self::remove_participant_from_registry(cid, cindex, &sender)?;
-				Self::register_participant(origin, cid, Some(proof))?;
+				Self::do_register_participant(sender, cid, Some(proof), preferred_region)?;
 			} else {
 				return Err(<Error<T>>::MustBeNewbieToUpgradeRegistration.into())
 			}
@@ -245,7 +249,26 @@ pub mod pallet {
 
 			let participant_type = Self::get_participant_type((cid, cindex), &sender)
 				.ok_or(<Error<T>>::ParticipantIsNotRegistered)?;
-			if participant_type == ParticipantType::Reputable {
+			if let Some((reputation_cc, attendee)) =
+				<LinkedReputations<T>>::take((cid, cindex), &sender)
+			{
+				// reputables and endorsees who registered with a proof of attendance
+				if Self::participant_reputation(reputation_cc, &attendee) ==
+					Reputation::VerifiedLinked
+				{
+					<ParticipantReputation<T>>::insert(
+						reputation_cc,
+						&attendee,
+						Reputation::VerifiedUnlinked,
+					);
+				}
+				<ParticipantReputation<T>>::remove((cid, cindex), &sender);
+
+				// invalidate reputation cache
+				sp_io::offchain_index::set(&reputation_cache_dirty_key(&sender), &true.encode());
+				sp_io::offchain_index::set(&reputation_cache_dirty_key(&attendee), &true.encode());
+			} else if participant_type == ParticipantType::Reputable {
+				// registered before `LinkedReputations` was introduced
 				let cc = maybe_reputation_community_ceremony
 					.ok_or(<Error<T>>::ReputationCommunityCeremonyRequired)?;
 				ensure!(
@@ -265,6 +288,7 @@ pub mod pallet {
 				sp_io::offchain_index::set(&reputation_cache_dirty_key(&sender), &true.encode());
 			}
 			Self::remove_participant_from_registry(cid, cindex, &sender)?;
+			<PreferredRegions<T>>::remove((cid, cindex), &sender);
 
 			Ok(().into())
 		}
@@ -286,6 +310,10 @@ pub mod pallet {
 				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
 				Error::<T>::InexistentCommunity
 			);
+			ensure!(
+				Self::community_rules(&cid) != CommunityRules::BeeDance,
+				Error::<T>::NotAllowedByCommunityRules
+			);
 
 			let (cindex, meetup_index, meetup_participants, _meetup_location, _meetup_time) =
 				Self::gather_meetup_data(&cid, &sender)?;
@@ -354,7 +382,7 @@ pub mod pallet {
 
 			if <NewbieIndex<T>>::contains_key((cid, cindex), &newbie) {
 				Self::remove_participant_from_registry(cid, cindex, &newbie)?;
-				Self::register(cid, cindex, &newbie, false)?;
+				Self::register(cid, cindex, &newbie, None)?;
 			}
 
 			debug!(target: LOG, "bootstrapper {:?} endorsed newbie: {:?}", sender, newbie);
@@ -363,6 +391,60 @@ pub mod pallet {
 			Ok(().into())
 		}
 
+		/// Endorse several newbies and register them as endorsees at once. Each newbie consents
+		/// by signing the payload of their `RegistrationConsent`. Newbies which can't be
+		/// registered are skipped and reported with `EndorseeRegistrationFailed`.
+		#[pallet::call_index(20)]
+		#[pallet::weight((<T as Config>::WeightInfo::endorse_and_register_batch(consents.len() as u32, 0), DispatchClass::Normal, Pays::Yes))]
+		pub fn endorse_and_register_batch(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			consents: BoundedVec<RegistrationConsentOf<T>, T::MaxEndorsementBatchSize>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let current_phase = <encointer_scheduler::Pallet<T>>::current_phase();
+			ensure!(
+				CeremonyPhaseType::is_registering_or_attesting(&current_phase),
+				Error::<T>::RegisteringOrAttestationPhaseRequired
+			);
+
+			ensure!(
+				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+				Error::<T>::InexistentCommunity
+			);
+
+			let mut cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+
+			if current_phase == CeremonyPhaseType::Attesting {
+				cindex += 1
+			};
+
+			let mut registered = 0u32;
+			let mut failed = 0u32;
+			for (index, consent) in consents.into_iter().enumerate() {
+				if let Err(error) = with_storage_layer(|| {
+					Self::do_endorse_and_register(cid, cindex, &sender, &consent)
+				}) {
+					failed += 1;
+					debug!(
+						target: LOG,
+						"failed to register endorsee {:?}: {:?}", consent.newbie, error
+					);
+					Self::deposit_event(Event::EndorseeRegistrationFailed {
+						cid,
+						index: index as u32,
+						newbie: consent.newbie,
+						error,
+					});
+				} else {
+					registered += 1;
+				}
+			}
+
+			Ok(Some(<T as Config>::WeightInfo::endorse_and_register_batch(registered, failed))
+				.into())
+		}
+
 		#[pallet::call_index(5)]
 		#[pallet::weight((<T as Config>::WeightInfo::claim_rewards(), DispatchClass::Normal, Pays::Yes))]
 		pub fn claim_rewards(
@@ -387,9 +469,6 @@ pub mod pallet {
 					.ok_or(<Error<T>>::ParticipantIsNotRegistered)?,
 			};
 
-			if <IssuedRewards<T>>::contains_key((cid, cindex), meetup_index) {
-				return Err(<Error<T>>::RewardsAlreadyIssued.into())
-			}
 			info!(
 				target: LOG,
 				"validating meetup {:?} for cid {:?} triggered by {:?}",
@@ -398,111 +477,67 @@ pub mod pallet {
 				participant
 			);
 
-			//gather all data
-			let meetup_participants = Self::get_meetup_participants((cid, cindex), meetup_index)?;
-			let (participant_votes, participant_attestations) =
-				Self::gather_meetup_validation_data(cid, cindex, meetup_participants.clone());
-
-			// initialize an array of local participant indices that are eligible for the reward
-			// indices will be deleted in the following based on various rules
-			let mut participants_eligible_for_rewards: Vec<usize> =
-				(0..meetup_participants.len()).collect();
-
-			let attestation_threshold_fn =
-				|i: usize| max(if i > 5 { i.saturating_sub(2) } else { i.saturating_sub(1) }, 1);
-			let participant_judgements = match get_participant_judgements(
-				&participants_eligible_for_rewards,
-				&participant_votes,
-				&participant_attestations,
-				attestation_threshold_fn,
-			) {
-				Ok(participant_judgements) => participant_judgements,
-				// handle errors
-				Err(err) => {
-					let (error, meetup_result) = match err {
-						MeetupValidationError::BallotEmpty => {
-							debug!(
-								target: LOG,
-								"ballot empty for meetup {:?}, cid: {:?}", meetup_index, cid
-							);
-							(
-								Err(<Error<T>>::VotesNotDependable.into()),
-								MeetupResult::VotesNotDependable,
-							)
-						},
-						MeetupValidationError::NoDependableVote => {
-							debug!(
-								target: LOG,
-								"ballot doesn't reach dependable majority for meetup {:?}, cid: {:?}",
-								meetup_index,
-								cid
-							);
-							(
-								Err(<Error<T>>::VotesNotDependable.into()),
-								MeetupResult::VotesNotDependable,
-							)
-						},
-						MeetupValidationError::IndexOutOfBounds => {
-							debug!(
-								target: LOG,
-								"index out of bounds for meetup {:?}, cid: {:?}", meetup_index, cid
-							);
-							(
-								Err(<Error<T>>::MeetupValidationIndexOutOfBounds.into()),
-								MeetupResult::MeetupValidationIndexOutOfBounds,
-							)
-						},
-					};
-					// only mark issuance as complete in registering phase
-					// because in attesting phase there could be a failing early payout attempt
-					if current_phase == CeremonyPhaseType::Registering {
-						info!(target: LOG, "marking issuance as completed for failed meetup.");
-
-						<IssuedRewards<T>>::insert((cid, cindex), meetup_index, meetup_result);
-						Self::deposit_event(Event::MeetupEvaluated(
-							cid,
-							meetup_index,
-							meetup_result,
-						));
-						return Ok(Pays::No.into())
-					} else {
-						return error
-					}
-				},
-			};
-			if current_phase == CeremonyPhaseType::Attesting &&
-				!participant_judgements.early_rewards_possible
-			{
-				debug!(
-					target: LOG,
-					"early rewards not possible for meetup {:?}, cid: {:?}", meetup_index, cid
-				);
-				return Err(<Error<T>>::EarlyRewardsNotPossible.into())
+			Self::do_claim_rewards(cid, cindex, meetup_index, current_phase)
+		}
+
+		/// Evaluate several meetups of a community at once and issue rewards to their legit
+		/// participants. Meetups which can't be evaluated are skipped.
+		///
+		/// Evaluating the meetups is free, like `claim_rewards`. If any meetup has been skipped, the
+		/// sender pays for the whole batch.
+		#[pallet::call_index(17)]
+		#[pallet::weight((<T as Config>::WeightInfo::claim_rewards().saturating_mul(meetup_indices.len() as u64), DispatchClass::Normal, Pays::Yes))]
+		pub fn claim_rewards_batch(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			meetup_indices: BoundedVec<MeetupIndexType, T::MaxClaimBatch>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let current_phase = <encointer_scheduler::Pallet<T>>::current_phase();
+			let mut cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+			match current_phase {
+				CeremonyPhaseType::Registering => cindex -= 1,
+				CeremonyPhaseType::Attesting => (),
+				CeremonyPhaseType::Assigning =>
+					return Err(<Error<T>>::WrongPhaseForClaimingRewards.into()),
 			}
-			participants_eligible_for_rewards = participant_judgements.legit;
-			// emit events
-			for p in participant_judgements.excluded {
-				let participant = meetup_participants
-					.get(p.index)
-					.ok_or(Error::<T>::MeetupValidationIndexOutOfBounds)?
-					.clone();
-				Self::deposit_event(Event::NoReward {
-					cid,
-					cindex,
-					meetup_index,
-					account: participant,
-					reason: p.reason,
-				});
+
+			info!(
+				target: LOG,
+				"validating meetups {:?} for cid {:?} triggered by {:?}",
+				meetup_indices,
+				&cid,
+				sender
+			);
+
+			let mut evaluated: u64 = 0;
+			let mut skipped: u64 = 0;
+			for meetup_index in meetup_indices {
+				match with_storage_layer(|| {
+					Self::do_claim_rewards(cid, cindex, meetup_index, current_phase)
+				}) {
+					Ok(_) => evaluated += 1,
+					Err(e) => {
+						skipped += 1;
+						debug!(
+							target: LOG,
+							"skipping meetup {:?} for cid {:?}: {:?}", meetup_index, cid, e.error
+						)
+					},
+				}
 			}
 
-			Self::issue_rewards(
-				cid,
-				cindex,
-				meetup_index,
-				meetup_participants,
-				participants_eligible_for_rewards,
-			)?;
-			Ok(Pays::No.into())
+			// a batch which only evaluates meetups is free, like `claim_rewards`. Skipping meetups
+			// could be used to spam the chain, so the sender pays for the whole batch then.
+			let pays_fee = if skipped == 0 { Pays::No } else { Pays::Yes };
+			Ok(PostDispatchInfo {
+				actual_weight: Some(
+					<T as Config>::WeightInfo::claim_rewards()
+						.saturating_mul(evaluated.saturating_add(skipped)),
+				),
+				pays_fee,
+			})
 		}
 
 		#[pallet::call_index(6)]
@@ -522,16 +557,7 @@ pub mod pallet {
 			endorsement_tickets_per_bootstrapper: EndorsementTicketsType,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			<EndorsementTicketsPerBootstrapper<T>>::put(endorsement_tickets_per_bootstrapper);
-			info!(
-				target: LOG,
-				"set endorsement tickets per bootstrapper to {}",
-				endorsement_tickets_per_bootstrapper
-			);
-			Self::deposit_event(Event::EndorsementTicketsPerBootstrapperUpdated(
-				endorsement_tickets_per_bootstrapper,
-			));
-			Ok(().into())
+			Self::do_set_endorsement_tickets_per_bootstrapper(endorsement_tickets_per_bootstrapper)
 		}
 
 		#[pallet::call_index(8)]
@@ -541,15 +567,7 @@ pub mod pallet {
 			endorsement_tickets_per_reputable: EndorsementTicketsType,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			<EndorsementTicketsPerReputable<T>>::put(endorsement_tickets_per_reputable);
-			info!(
-				target: LOG,
-				"set endorsement tickets per reputable to {}", endorsement_tickets_per_reputable
-			);
-			Self::deposit_event(Event::EndorsementTicketsPerReputableUpdated(
-				endorsement_tickets_per_reputable,
-			));
-			Ok(().into())
+			Self::do_set_endorsement_tickets_per_reputable(endorsement_tickets_per_reputable)
 		}
 
 		#[pallet::call_index(9)]
@@ -559,10 +577,7 @@ pub mod pallet {
 			reputation_lifetime: ReputationLifetimeType,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			<ReputationLifetime<T>>::put(reputation_lifetime);
-			info!(target: LOG, "set reputation lifetime to {}", reputation_lifetime);
-			Self::deposit_event(Event::ReputationLifetimeUpdated(reputation_lifetime));
-			Ok(().into())
+			Self::do_set_reputation_lifetime(reputation_lifetime)
 		}
 
 		#[pallet::call_index(10)]
@@ -572,19 +587,7 @@ pub mod pallet {
 			meetup_time_offset: MeetupTimeOffsetType,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			if <encointer_scheduler::Pallet<T>>::current_phase() != CeremonyPhaseType::Registering {
-				return Err(<Error<T>>::WrongPhaseForChangingMeetupTimeOffset.into())
-			}
-
-			// Meetup time offset needs to be in [-8h, 8h]
-			if meetup_time_offset.abs() > 8 * 3600 * 1000 {
-				return Err(<Error<T>>::InvalidMeetupTimeOffset.into())
-			}
-
-			<MeetupTimeOffset<T>>::put(meetup_time_offset);
-			info!(target: LOG, "set meetup time offset to {} ms", meetup_time_offset);
-			Self::deposit_event(Event::MeetupTimeOffsetUpdated(meetup_time_offset));
-			Ok(().into())
+			Self::do_set_meetup_time_offset(meetup_time_offset)
 		}
 
 		#[pallet::call_index(11)]
@@ -594,10 +597,7 @@ pub mod pallet {
 			time_tolerance: T::Moment,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			<TimeTolerance<T>>::put(time_tolerance);
-			info!(target: LOG, "set meetup time tolerance to {:?}", time_tolerance);
-			Self::deposit_event(Event::TimeToleranceUpdated(time_tolerance));
-			Ok(().into())
+			Self::do_set_time_tolerance(time_tolerance)
 		}
 
 		#[pallet::call_index(12)]
@@ -607,10 +607,7 @@ pub mod pallet {
 			location_tolerance: u32,
 		) -> DispatchResultWithPostInfo {
 			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
-			<LocationTolerance<T>>::put(location_tolerance);
-			info!(target: LOG, "set meetup location tolerance to {}", location_tolerance);
-			Self::deposit_event(Event::LocationToleranceUpdated(location_tolerance));
-			Ok(().into())
+			Self::do_set_location_tolerance(location_tolerance)
 		}
 		#[pallet::call_index(13)]
 		#[pallet::weight((<T as Config>::WeightInfo::purge_community_ceremony(), DispatchClass::Normal, Pays::Yes))]
@@ -624,6 +621,293 @@ pub mod pallet {
 
 			Ok(().into())
 		}
+
+		/// Submit the signed claims of attendance of all participants of the sender's meetup at once.
+		/// Every valid claimant attests all other valid claimants and votes for the number of
+		/// participants stated in their claim. The sender has to be one of the claimants. Bundles
+		/// submitted later add to the attestations registered before.
+		///
+		/// Each claim has to be signed for the bundle, see
+		/// `ClaimOfAttendance::bundle_payload_encoded`, such that no claim can be added to or
+		/// removed from a bundle after its claimants have signed it.
+		#[pallet::call_index(15)]
+		#[pallet::weight((<T as Config>::WeightInfo::attest_meetup(claims.len() as u32), DispatchClass::Normal, Pays::Yes))]
+		pub fn attest_meetup(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			claims: BoundedVec<ClaimOfAttendanceOf<T>, T::MaxAttestations>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				<encointer_scheduler::Pallet<T>>::current_phase() == CeremonyPhaseType::Attesting,
+				Error::<T>::AttestationPhaseRequired
+			);
+			ensure!(
+				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+				Error::<T>::InexistentCommunity
+			);
+			ensure!(
+				Self::community_rules(&cid) != CommunityRules::BeeDance,
+				Error::<T>::NotAllowedByCommunityRules
+			);
+
+			let (cindex, meetup_index, meetup_participants, meetup_location, meetup_time) =
+				Self::gather_meetup_data(&cid, &sender)?;
+
+			ensure!(claims.len() <= meetup_participants.len(), Error::<T>::TooManyAttestations);
+
+			debug!(
+				target: LOG,
+				"{:?} attempts to submit {:?} claims for meetup {:?}",
+				sender,
+				claims.len(),
+				meetup_index
+			);
+
+			let bundle_claimants: Vec<T::AccountId> =
+				claims.iter().map(|claim| claim.claimant_public.clone()).collect();
+			let mut verified_claims: Vec<(T::AccountId, u32)> = vec![];
+			for claim in claims.iter() {
+				let claimant = &claim.claimant_public;
+				if verified_claims.iter().any(|(c, _)| c == claimant) {
+					warn!(target: LOG, "ignoring duplicate claim of: {:?}", claimant);
+					continue
+				}
+				if !meetup_participants.contains(claimant) {
+					warn!(
+						target: LOG,
+						"ignoring claim from non meetup participant: {:?}", claimant
+					);
+					continue
+				}
+				if claim.ceremony_index != cindex ||
+					claim.community_identifier != cid ||
+					claim.meetup_index != meetup_index
+				{
+					warn!(target: LOG, "ignoring claim for another meetup: {:?}", claimant);
+					continue
+				}
+				if !Self::is_location_plausible(claim.location, meetup_location) {
+					warn!(target: LOG, "ignoring claim with implausible location: {:?}", claimant);
+					continue
+				}
+				if !Self::is_time_plausible(claim.timestamp, meetup_time) {
+					warn!(target: LOG, "ignoring claim with implausible time: {:?}", claimant);
+					continue
+				}
+				if !claim.verify_bundle_signature(&bundle_claimants) {
+					warn!(target: LOG, "ignoring claim with bad signature: {:?}", claimant);
+					continue
+				}
+				verified_claims.push((claimant.clone(), claim.number_of_participants_confirmed));
+			}
+
+			ensure!(verified_claims.len() > 1, Error::<T>::NoValidAttestations);
+			ensure!(
+				verified_claims.iter().any(|(claimant, _)| claimant == &sender),
+				Error::<T>::SenderIsNotAClaimant
+			);
+
+			let claimants: Vec<T::AccountId> =
+				verified_claims.iter().map(|c| c.0.clone()).collect();
+			for (claimant, number_of_participants_vote) in verified_claims {
+				// claims can be submitted in several bundles. a later bundle must not replace what
+				// has been registered with an earlier one, so attestations are merged
+				if !<MeetupParticipantCountVote<T>>::contains_key((cid, cindex), &claimant) {
+					<MeetupParticipantCountVote<T>>::insert(
+						(cid, cindex),
+						&claimant,
+						number_of_participants_vote,
+					);
+				}
+				let mut attestees = claimants.clone();
+				if <AttestationIndex<T>>::contains_key((cid, cindex), &claimant) {
+					let registered = Self::attestation_registry(
+						(cid, cindex),
+						Self::attestation_index((cid, cindex), &claimant),
+					)
+					.unwrap_or_default();
+					attestees.extend(registered.into_iter().filter(|a| !claimants.contains(a)));
+				}
+				Self::add_attestations_to_registry(
+					claimant,
+					&cid,
+					cindex,
+					meetup_index,
+					&meetup_participants,
+					&attestees,
+				)?;
+			}
+
+			Ok(().into())
+		}
+
+		#[pallet::call_index(16)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_meetup_validation_policy(), DispatchClass::Normal, Pays::Yes))]
+		pub fn set_meetup_validation_policy(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			policy: MeetupValidationPolicy,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
+			Self::do_set_meetup_validation_policy(cid, policy)
+		}
+
+		/// Trust the reputation of `partner` like the reputation of `cid` itself for registration
+		/// and endorsement tickets.
+		#[pallet::call_index(18)]
+		#[pallet::weight((<T as Config>::WeightInfo::add_partner_community(), DispatchClass::Normal, Pays::Yes))]
+		pub fn add_partner_community(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			partner: CommunityIdentifier,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
+			Self::do_add_partner_community(cid, partner)
+		}
+
+		/// Stop trusting the reputation of `partner`. Reputation which has already been linked to
+		/// register is not affected.
+		#[pallet::call_index(19)]
+		#[pallet::weight((<T as Config>::WeightInfo::remove_partner_community(), DispatchClass::Normal, Pays::Yes))]
+		pub fn remove_partner_community(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			partner: CommunityIdentifier,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
+			Self::do_remove_partner_community(cid, partner)
+		}
+
+		/// Opt in or out of being registered automatically for the next ceremony if the sender
+		/// ends up on the waitlist of `cid`.
+		#[pallet::call_index(21)]
+		#[pallet::weight((<T as Config>::WeightInfo::set_waitlist_auto_registration(), DispatchClass::Normal, Pays::Yes))]
+		pub fn set_waitlist_auto_registration(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+				Error::<T>::InexistentCommunity
+			);
+			if enabled {
+				<WaitlistAutoRegistration<T>>::insert(cid, &sender, true);
+			} else {
+				<WaitlistAutoRegistration<T>>::remove(cid, &sender);
+			}
+			Self::deposit_event(Event::WaitlistAutoRegistrationSet(cid, sender, enabled));
+			Ok(().into())
+		}
+
+		/// Cancel a meetup of the current ceremony, e.g. because its location has become unusable.
+		/// The reputation its participants have linked to register is unlinked again, unless they
+		/// registered before `LinkedReputations` was introduced.
+		///
+		/// Charges for a meetup of `MeetupSizeTarget` participants and refunds the difference to
+		/// the actual number of participants.
+		#[pallet::call_index(22)]
+		#[pallet::weight((<T as Config>::WeightInfo::cancel_meetup(T::MeetupSizeTarget::get() as u32), DispatchClass::Normal, Pays::Yes))]
+		pub fn cancel_meetup(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
+			Self::do_cancel_meetup(cid, cindex, meetup_index)
+		}
+
+		/// Move a meetup of the current ceremony to another location of the community. The
+		/// meetup time follows the new location.
+		#[pallet::call_index(23)]
+		#[pallet::weight((<T as Config>::WeightInfo::reschedule_meetup(), DispatchClass::Normal, Pays::Yes))]
+		pub fn reschedule_meetup(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+			location: Location,
+		) -> DispatchResultWithPostInfo {
+			<T as pallet::Config>::CeremonyMaster::ensure_origin(origin)?;
+			Self::do_reschedule_meetup(cid, cindex, meetup_index, location)
+		}
+
+		/// Register for the upcoming ceremony like `register_participant` and prefer to be assigned
+		/// to a meetup at one of the community locations within `region`.
+		#[pallet::call_index(24)]
+		#[pallet::weight((<T as Config>::WeightInfo::register_participant_in_region(), DispatchClass::Normal, Pays::Yes))]
+		pub fn register_participant_in_region(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			proof: Option<ProofOfAttendance<T::Signature, T::AccountId>>,
+			region: GeoHash,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::do_register_participant(sender, cid, proof, Some(region))
+		}
+
+		/// Choose the time of the sender's meetup within the upcoming attesting phase.
+		/// Only available for communities running `CommunityRules::LoCoFlex`.
+		///
+		/// The time can be chosen once per meetup, by one of its bootstrappers or reputables.
+		#[pallet::call_index(14)]
+		#[pallet::weight((<T as Config>::WeightInfo::choose_meetup_time(), DispatchClass::Normal, Pays::Yes))]
+		pub fn choose_meetup_time(
+			origin: OriginFor<T>,
+			cid: CommunityIdentifier,
+			meetup_time: T::Moment,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				<encointer_scheduler::Pallet<T>>::current_phase() == CeremonyPhaseType::Assigning,
+				Error::<T>::AssigningPhaseRequired
+			);
+			ensure!(
+				<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+				Error::<T>::InexistentCommunity
+			);
+			ensure!(
+				Self::community_rules(&cid) == CommunityRules::LoCoFlex,
+				Error::<T>::NotAllowedByCommunityRules
+			);
+
+			let (start, end) = Self::attesting_phase_window();
+			ensure!(
+				start <= meetup_time && meetup_time < end,
+				Error::<T>::MeetupTimeOutsideAttestingPhase
+			);
+
+			let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+			let meetup_index = Self::get_meetup_index((cid, cindex), &sender)
+				.ok_or(Error::<T>::ParticipantIsNotRegistered)?;
+			ensure!(
+				matches!(
+					Self::get_participant_type((cid, cindex), &sender),
+					Some(ParticipantType::Bootstrapper | ParticipantType::Reputable)
+				),
+				Error::<T>::OnlyBootstrappersOrReputablesCanChooseMeetupTime
+			);
+			ensure!(
+				!<MeetupTimeSlot<T>>::contains_key((cid, cindex), meetup_index),
+				Error::<T>::MeetupTimeAlreadyChosen
+			);
+
+			<MeetupTimeSlot<T>>::insert((cid, cindex), meetup_index, meetup_time);
+
+			info!(
+				target: LOG,
+				"{:?} chose meetup time {:?} for meetup {:?}, cid: {:?}",
+				sender,
+				meetup_time,
+				meetup_index,
+				cid
+			);
+			Self::deposit_event(Event::MeetupTimeChosen { cid, cindex, meetup_index, meetup_time });
+			Ok(().into())
+		}
 	}
 
 	#[pallet::event]
@@ -633,6 +917,13 @@ pub mod pallet {
 		ParticipantRegistered(CommunityIdentifier, ParticipantType, T::AccountId),
 		/// A bootstrapper (first accountid) has endorsed a participant (second accountid) who can now register as endorsee for this ceremony
 		EndorsedParticipant(CommunityIdentifier, T::AccountId, T::AccountId),
+		/// A newbie at position `index` of a batch could not be endorsed and registered
+		EndorseeRegistrationFailed {
+			cid: CommunityIdentifier,
+			index: u32,
+			newbie: T::AccountId,
+			error: DispatchError,
+		},
 		/// A participant has registered N attestations for fellow meetup participants
 		AttestationsRegistered(CommunityIdentifier, MeetupIndexType, u32, T::AccountId),
 		/// rewards have been claimed and issued successfully for N participants for their meetup at the previous ceremony
@@ -667,6 +958,71 @@ pub mod pallet {
 
 		/// Result of the meetup at the previous ceremony
 		MeetupEvaluated(CommunityIdentifier, MeetupIndexType, MeetupResult),
+
+		/// the rules to validate the meetups of a community have changed
+		MeetupValidationPolicyUpdated(CommunityIdentifier, MeetupValidationPolicy),
+
+		/// a community trusts the reputation of a partner community
+		PartnerCommunityAdded(CommunityIdentifier, CommunityIdentifier),
+
+		/// a community no longer trusts the reputation of a former partner community
+		PartnerCommunityRemoved(CommunityIdentifier, CommunityIdentifier),
+
+		/// Registered participants have not been assigned to a meetup for lack of locations or
+		/// because of the newbie limit
+		ParticipantsNotAssigned {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			unassigned: AssignmentCount,
+		},
+
+		/// A registered participant has not been assigned to a meetup and is prioritized at the
+		/// next ceremony
+		ParticipantWaitlisted {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			participant_type: ParticipantType,
+			account: T::AccountId,
+		},
+
+		/// A participant has opted in or out of automatic registration from the waitlist
+		WaitlistAutoRegistrationSet(CommunityIdentifier, T::AccountId, bool),
+
+		/// A meetup has been cancelled and the linked reputation of `refunded` participants has
+		/// been unlinked
+		MeetupCancelled {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+			refunded: u32,
+		},
+
+		/// A meetup has been moved to another location
+		MeetupRescheduled {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+			location: Location,
+			time: T::Moment,
+		},
+
+		/// The participants of a `LoCoFlex` meetup have chosen its time
+		MeetupTimeChosen {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+			meetup_time: T::Moment,
+		},
+
+		/// A participant's claims have been contradicted by the other participants of their meetup
+		ParticipantPenalized {
+			cid: CommunityIdentifier,
+			cindex: CeremonyIndexType,
+			meetup_index: MeetupIndexType,
+			account: T::AccountId,
+			reason: PenaltyReason,
+			penalty_count: PenaltyCountType,
+		},
 	}
 
 	#[pallet::error]
@@ -683,6 +1039,8 @@ pub mod pallet {
 		MeetupTimeCalculationError,
 		/// no valid claims were supplied
 		NoValidAttestations,
+		/// the sender has not supplied a valid claim of their own
+		SenderIsNotAClaimant,
 		/// the action can only be performed during ATTESTING phase
 		AttestationPhaseRequired,
 		/// the action can only be performed during REGISTERING or ATTESTING phase
@@ -743,6 +1101,42 @@ pub mod pallet {
 		InvalidMeetupIndex,
 		/// BoundedVec bound reached
 		TooManyAttestationsInBoundedVec,
+		/// the action is not supported by the rules of this community
+		NotAllowedByCommunityRules,
+		/// the action can only be performed during ASSIGNING phase
+		AssigningPhaseRequired,
+		/// the chosen meetup time does not lie within the attesting phase
+		MeetupTimeOutsideAttestingPhase,
+		/// newbie tickets are withheld from repeatedly penalized participants
+		NewbieTicketsWithheld,
+		/// the community has no locations in the preferred region
+		NoLocationsInRegion,
+		/// a community can't be its own partner
+		PartnerCommunityIsSelf,
+		/// the registration consent was given for another endorser, community or ceremony
+		RegistrationConsentMismatch,
+		/// verification of the signature of a registration consent failed
+		BadRegistrationConsentSignature,
+		/// meetups can only be changed during ASSIGNING or ATTESTING of the current ceremony
+		WrongPhaseForChangingMeetups,
+		/// the meetup has been cancelled
+		MeetupHasBeenCancelled,
+		/// the location is not a location of the community
+		UnknownMeetupLocation,
+		/// the location is already used by another meetup of the ceremony
+		MeetupLocationAlreadyInUse,
+		/// there are more bootstrappers than seats in the meetups which can be assigned
+		NotEnoughSeatsForBootstrappers,
+		/// the community already trusts the maximum number of partner communities
+		TooManyPartnerCommunities,
+		/// the community does not trust the reputation of this community
+		NotAPartnerCommunity,
+		/// bootstrappers can't be endorsed
+		CannotEndorseBootstrapper,
+		/// only bootstrappers and reputables can choose the time of their meetup
+		OnlyBootstrappersOrReputablesCanChooseMeetupTime,
+		/// the time of the meetup has already been chosen
+		MeetupTimeAlreadyChosen,
 	}
 
 	#[pallet::storage]
@@ -984,6 +1378,77 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// the next meetup of the previous ceremony to be evaluated automatically in `on_idle`
+	#[pallet::storage]
+	#[pallet::getter(fn reward_issuance_cursor)]
+	pub(super) type RewardIssuanceCursor<T: Config> =
+		StorageValue<_, (CommunityIdentifier, CeremonyIndexType, MeetupIndexType), OptionQuery>;
+
+	/// outcome of the meetup evaluations of a ceremony. Registration counts are taken from the
+	/// registries, see `get_ceremony_stats`
+	#[pallet::storage]
+	#[pallet::getter(fn ceremony_statistics)]
+	pub(super) type CeremonyStatistics<T: Config> =
+		StorageMap<_, Blake2_128Concat, CommunityCeremony, CeremonyStats, ValueQuery>;
+
+	/// geohash bucket of the community locations where a participant prefers to meet
+	#[pallet::storage]
+	#[pallet::getter(fn preferred_region)]
+	pub(super) type PreferredRegions<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		T::AccountId,
+		GeoHash,
+		OptionQuery,
+	>;
+
+	/// meetup assignments of the participants grouped by their preferred region. If there are
+	/// none, `Assignments` applies to all participants of the community ceremony.
+	#[pallet::storage]
+	#[pallet::getter(fn regional_assignments)]
+	pub(super) type RegionalAssignments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		u32,
+		RegionalAssignment,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn regional_assignment_count)]
+	pub(super) type RegionalAssignmentCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, CommunityCeremony, u32, ValueQuery>;
+
+	/// misbehaviour of participants, tracked across ceremonies
+	#[pallet::storage]
+	#[pallet::getter(fn penalties)]
+	pub(super) type Penalties<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityIdentifier,
+		Blake2_128Concat,
+		T::AccountId,
+		PenaltyRecord,
+		OptionQuery,
+	>;
+
+	/// meetup times chosen by the participants of communities running `CommunityRules::LoCoFlex`
+	#[pallet::storage]
+	#[pallet::getter(fn meetup_time_slot)]
+	pub(super) type MeetupTimeSlot<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		MeetupIndexType,
+		T::Moment,
+		OptionQuery,
+	>;
+
 	/// the default UBI for a ceremony attendee if no community specific value is set.
 	#[pallet::storage]
 	#[pallet::getter(fn ceremony_reward)]
@@ -1038,55 +1503,451 @@ pub mod pallet {
 	pub(super) type ReputationLifetime<T: Config> =
 		StorageValue<_, ReputationLifetimeType, ValueQuery>;
 
-	#[pallet::storage]
-	#[pallet::getter(fn meetup_time_offset)]
-	pub(super) type MeetupTimeOffset<T: Config> = StorageValue<_, MeetupTimeOffsetType, ValueQuery>;
+	/// community specific rules to validate meetups. Communities without an entry use the default
+	#[pallet::storage]
+	#[pallet::getter(fn meetup_validation_policy)]
+	pub(super) type MeetupValidationPolicies<T: Config> =
+		StorageMap<_, Blake2_128Concat, CommunityIdentifier, MeetupValidationPolicy, ValueQuery>;
+
+	/// the reputation (community ceremony and attendee) a reputable has linked to register
+	///
+	/// Registrations before this item has been introduced were not recorded and can't be
+	/// backfilled, as the proof of attendance is not stored. Their linked reputation is not
+	/// refunded if their meetup is cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn linked_reputations)]
+	pub(super) type LinkedReputations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		T::AccountId,
+		(CommunityCeremony, T::AccountId),
+		OptionQuery,
+	>;
+
+	/// meetups which have been cancelled and can neither be attested nor rewarded
+	#[pallet::storage]
+	#[pallet::getter(fn cancelled_meetups)]
+	pub(super) type CancelledMeetups<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		MeetupIndexType,
+		(),
+		OptionQuery,
+	>;
+
+	/// locations of meetups which have been rescheduled
+	#[pallet::storage]
+	#[pallet::getter(fn rescheduled_meetup_locations)]
+	pub(super) type RescheduledMeetupLocations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		MeetupIndexType,
+		Location,
+		OptionQuery,
+	>;
+
+	/// meetups which have been rescheduled, by their new location
+	#[pallet::storage]
+	#[pallet::getter(fn rescheduled_location_meetups)]
+	pub(super) type RescheduledLocationMeetups<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		Blake2_128Concat,
+		Location,
+		MeetupIndexType,
+		OptionQuery,
+	>;
+
+	/// registered participants who have not been assigned to a meetup. They are prioritized at
+	/// the next ceremony
+	#[pallet::storage]
+	#[pallet::getter(fn waitlist)]
+	pub(super) type Waitlist<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CommunityCeremony,
+		BoundedVec<(T::AccountId, ParticipantType), T::MaxWaitlistSize>,
+		ValueQuery,
+	>;
+
+	/// the next waitlisted participant of the previous ceremony to be registered in `on_idle`
+	/// during REGISTERING: (community, current ceremony index, position in the waitlist)
+	#[pallet::storage]
+	#[pallet::getter(fn waitlist_registration_cursor)]
+	pub(super) type WaitlistRegistrationCursor<T: Config> =
+		StorageValue<_, (CommunityIdentifier, CeremonyIndexType, u32), OptionQuery>;
+
+	/// participants who want to be registered for the next ceremony automatically if they end up
+	/// on the waitlist
+	#[pallet::storage]
+	#[pallet::getter(fn waitlist_auto_registration)]
+	pub(super) type WaitlistAutoRegistration<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityIdentifier,
+		Blake2_128Concat,
+		T::AccountId,
+		bool,
+		ValueQuery,
+	>;
+
+	/// communities whose reputation is trusted like the own reputation of a community.
+	/// Reputation from other communities only grants endorsee status
+	#[pallet::storage]
+	pub(super) type PartnerCommunities<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		CommunityIdentifier,
+		Blake2_128Concat,
+		CommunityIdentifier,
+		(),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn meetup_time_offset)]
+	pub(super) type MeetupTimeOffset<T: Config> = StorageValue<_, MeetupTimeOffsetType, ValueQuery>;
+
+	#[derive(frame_support::DefaultNoBound)]
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config>
+	where
+		<T as pallet_timestamp::Config>::Moment: MaybeSerializeDeserialize,
+	{
+		#[doc = " the default UBI for a ceremony attendee if no community specific value is set."]
+		pub ceremony_reward: BalanceType,
+		pub location_tolerance: u32,
+		pub time_tolerance: T::Moment,
+		pub inactivity_timeout: InactivityTimeoutType,
+		pub endorsement_tickets_per_bootstrapper: EndorsementTicketsType,
+		pub endorsement_tickets_per_reputable: EndorsementTicketsType,
+		pub reputation_lifetime: ReputationLifetimeType,
+		pub meetup_time_offset: MeetupTimeOffsetType,
+		#[serde(skip)]
+		pub _config: sp_std::marker::PhantomData<T>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T>
+	where
+		<T as pallet_timestamp::Config>::Moment: MaybeSerializeDeserialize,
+	{
+		fn build(&self) {
+			<CeremonyReward<T>>::put(self.ceremony_reward);
+			<LocationTolerance<T>>::put(self.location_tolerance);
+			<TimeTolerance<T>>::put(self.time_tolerance);
+			<InactivityTimeout<T>>::put(self.inactivity_timeout);
+			<EndorsementTicketsPerBootstrapper<T>>::put(self.endorsement_tickets_per_bootstrapper);
+			<EndorsementTicketsPerReputable<T>>::put(self.endorsement_tickets_per_reputable);
+			<ReputationLifetime<T>>::put(self.reputation_lifetime);
+			<MeetupTimeOffset<T>>::put(self.meetup_time_offset);
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn do_set_inactivity_timeout(
+		inactivity_timeout: InactivityTimeoutType,
+	) -> DispatchResultWithPostInfo {
+		<InactivityTimeout<T>>::put(inactivity_timeout);
+		info!(target: LOG, "set inactivity timeout to {}", inactivity_timeout);
+		Self::deposit_event(Event::InactivityTimeoutUpdated(inactivity_timeout));
+		Ok(().into())
+	}
+
+	pub fn do_set_endorsement_tickets_per_bootstrapper(
+		endorsement_tickets_per_bootstrapper: EndorsementTicketsType,
+	) -> DispatchResultWithPostInfo {
+		<EndorsementTicketsPerBootstrapper<T>>::put(endorsement_tickets_per_bootstrapper);
+		info!(
+			target: LOG,
+			"set endorsement tickets per bootstrapper to {}", endorsement_tickets_per_bootstrapper
+		);
+		Self::deposit_event(Event::EndorsementTicketsPerBootstrapperUpdated(
+			endorsement_tickets_per_bootstrapper,
+		));
+		Ok(().into())
+	}
+
+	pub fn do_set_endorsement_tickets_per_reputable(
+		endorsement_tickets_per_reputable: EndorsementTicketsType,
+	) -> DispatchResultWithPostInfo {
+		<EndorsementTicketsPerReputable<T>>::put(endorsement_tickets_per_reputable);
+		info!(
+			target: LOG,
+			"set endorsement tickets per reputable to {}", endorsement_tickets_per_reputable
+		);
+		Self::deposit_event(Event::EndorsementTicketsPerReputableUpdated(
+			endorsement_tickets_per_reputable,
+		));
+		Ok(().into())
+	}
+
+	pub fn do_set_reputation_lifetime(
+		reputation_lifetime: ReputationLifetimeType,
+	) -> DispatchResultWithPostInfo {
+		<ReputationLifetime<T>>::put(reputation_lifetime);
+		info!(target: LOG, "set reputation lifetime to {}", reputation_lifetime);
+		Self::deposit_event(Event::ReputationLifetimeUpdated(reputation_lifetime));
+		Ok(().into())
+	}
+
+	pub fn do_set_meetup_time_offset(
+		meetup_time_offset: MeetupTimeOffsetType,
+	) -> DispatchResultWithPostInfo {
+		if <encointer_scheduler::Pallet<T>>::current_phase() != CeremonyPhaseType::Registering {
+			return Err(<Error<T>>::WrongPhaseForChangingMeetupTimeOffset.into())
+		}
+
+		// Meetup time offset needs to be in [-8h, 8h]
+		if meetup_time_offset.abs() > 8 * 3600 * 1000 {
+			return Err(<Error<T>>::InvalidMeetupTimeOffset.into())
+		}
+
+		<MeetupTimeOffset<T>>::put(meetup_time_offset);
+		info!(target: LOG, "set meetup time offset to {} ms", meetup_time_offset);
+		Self::deposit_event(Event::MeetupTimeOffsetUpdated(meetup_time_offset));
+		Ok(().into())
+	}
+
+	pub fn do_set_time_tolerance(time_tolerance: T::Moment) -> DispatchResultWithPostInfo {
+		<TimeTolerance<T>>::put(time_tolerance);
+		info!(target: LOG, "set meetup time tolerance to {:?}", time_tolerance);
+		Self::deposit_event(Event::TimeToleranceUpdated(time_tolerance));
+		Ok(().into())
+	}
+
+	pub fn do_set_location_tolerance(location_tolerance: u32) -> DispatchResultWithPostInfo {
+		<LocationTolerance<T>>::put(location_tolerance);
+		info!(target: LOG, "set meetup location tolerance to {}", location_tolerance);
+		Self::deposit_event(Event::LocationToleranceUpdated(location_tolerance));
+		Ok(().into())
+	}
+
+	pub fn do_set_meetup_validation_policy(
+		cid: CommunityIdentifier,
+		policy: MeetupValidationPolicy,
+	) -> DispatchResultWithPostInfo {
+		ensure!(
+			<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+			Error::<T>::InexistentCommunity
+		);
+		<MeetupValidationPolicies<T>>::insert(cid, policy);
+		info!(target: LOG, "set meetup validation policy of {:?} to {:?}", cid, policy);
+		Self::deposit_event(Event::MeetupValidationPolicyUpdated(cid, policy));
+		Ok(().into())
+	}
+
+	pub fn do_add_partner_community(
+		cid: CommunityIdentifier,
+		partner: CommunityIdentifier,
+	) -> DispatchResultWithPostInfo {
+		let cids = <encointer_communities::Pallet<T>>::community_identifiers();
+		ensure!(cids.contains(&cid) && cids.contains(&partner), Error::<T>::InexistentCommunity);
+		ensure!(cid != partner, Error::<T>::PartnerCommunityIsSelf);
+		ensure!(
+			<PartnerCommunities<T>>::contains_key(cid, partner) ||
+				(<PartnerCommunities<T>>::iter_key_prefix(cid).count() as u32) <
+					T::MaxPartnerCommunities::get(),
+			Error::<T>::TooManyPartnerCommunities
+		);
+		<PartnerCommunities<T>>::insert(cid, partner, ());
+		info!(target: LOG, "{:?} trusts the reputation of {:?}", cid, partner);
+		Self::deposit_event(Event::PartnerCommunityAdded(cid, partner));
+		Ok(().into())
+	}
+
+	pub fn do_remove_partner_community(
+		cid: CommunityIdentifier,
+		partner: CommunityIdentifier,
+	) -> DispatchResultWithPostInfo {
+		ensure!(
+			<PartnerCommunities<T>>::contains_key(cid, partner),
+			Error::<T>::NotAPartnerCommunity
+		);
+		<PartnerCommunities<T>>::remove(cid, partner);
+		info!(target: LOG, "{:?} no longer trusts the reputation of {:?}", cid, partner);
+		Self::deposit_event(Event::PartnerCommunityRemoved(cid, partner));
+		Ok(().into())
+	}
+
+	pub fn do_cancel_meetup(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		meetup_index: MeetupIndexType,
+	) -> DispatchResultWithPostInfo {
+		Self::ensure_meetup_changeable(cid, cindex, meetup_index)?;
+		let cc = (cid, cindex);
+
+		let participants = Self::get_meetup_participants(cc, meetup_index)?;
+		let num_participants = participants.len() as u32;
+		let mut refunded = 0u32;
+		for participant in participants {
+			if let Some((reputation_cc, attendee)) = <LinkedReputations<T>>::take(cc, &participant)
+			{
+				if Self::participant_reputation(reputation_cc, &attendee) ==
+					Reputation::VerifiedLinked
+				{
+					<ParticipantReputation<T>>::insert(
+						reputation_cc,
+						&attendee,
+						Reputation::VerifiedUnlinked,
+					);
+					<ParticipantReputation<T>>::remove(cc, &participant);
+					refunded.saturating_inc();
+				}
+			} else if Self::participant_reputation(cc, &participant) ==
+				Reputation::UnverifiedReputable
+			{
+				warn!(
+					target: LOG,
+					"linked reputation of {:?} has not been recorded and can't be refunded",
+					participant
+				);
+			}
+			// invalidate reputation cache
+			sp_io::offchain_index::set(&reputation_cache_dirty_key(&participant), &true.encode());
+		}
+
+		<CancelledMeetups<T>>::insert(cc, meetup_index, ());
+		if let Some(location) = <RescheduledMeetupLocations<T>>::take(cc, meetup_index) {
+			<RescheduledLocationMeetups<T>>::remove(cc, location);
+		}
+		info!(target: LOG, "cancelled meetup {} of {:?}", meetup_index, cc);
+		Self::deposit_event(Event::MeetupCancelled { cid, cindex, meetup_index, refunded });
+		Ok(PostDispatchInfo {
+			actual_weight: Some(<T as Config>::WeightInfo::cancel_meetup(num_participants)),
+			pays_fee: Pays::Yes,
+		})
+	}
+
+	pub fn do_reschedule_meetup(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		meetup_index: MeetupIndexType,
+		location: Location,
+	) -> DispatchResultWithPostInfo {
+		Self::ensure_meetup_changeable(cid, cindex, meetup_index)?;
+		ensure!(
+			<encointer_communities::Pallet<T>>::get_locations(&cid).contains(&location),
+			Error::<T>::UnknownMeetupLocation
+		);
+		let cc = (cid, cindex);
+		ensure!(
+			!Self::is_meetup_location_in_use(cc, location, meetup_index),
+			Error::<T>::MeetupLocationAlreadyInUse
+		);
+
+		if let Some(previous_location) = <RescheduledMeetupLocations<T>>::take(cc, meetup_index) {
+			<RescheduledLocationMeetups<T>>::remove(cc, previous_location);
+		}
+		<RescheduledMeetupLocations<T>>::insert(cc, meetup_index, location);
+		<RescheduledLocationMeetups<T>>::insert(cc, location, meetup_index);
+		let time = match Self::meetup_time_slot(cc, meetup_index) {
+			Some(slot) if Self::community_rules(&cid) == CommunityRules::LoCoFlex => slot,
+			_ => meetup_time::<T::Moment>(
+				location,
+				Self::attesting_phase_window().0,
+				T::MomentsPerDay::get(),
+				Self::meetup_time_offset(),
+			),
+		};
+		info!(target: LOG, "rescheduled meetup {} of {:?} to {:?}", meetup_index, cc, location);
+		Self::deposit_event(Event::MeetupRescheduled { cid, cindex, meetup_index, location, time });
+		Ok(().into())
+	}
+
+	/// Whether a meetup other than `meetup_index` takes place at `location`.
+	///
+	/// Only the meetup rescheduled to `location` and the one assigned to it are looked up, instead
+	/// of the locations of all meetups.
+	fn is_meetup_location_in_use(
+		cc: CommunityCeremony,
+		location: Location,
+		meetup_index: MeetupIndexType,
+	) -> bool {
+		if Self::rescheduled_location_meetups(cc, location).map_or(false, |m| m != meetup_index) {
+			return true
+		}
+		Self::assigned_location_meetup(cc, location).map_or(false, |m| {
+			m != meetup_index &&
+				!<CancelledMeetups<T>>::contains_key(cc, m) &&
+				!<RescheduledMeetupLocations<T>>::contains_key(cc, m)
+		})
+	}
+
+	/// The meetup which has been assigned to `location`, regardless of whether it has been
+	/// cancelled or rescheduled since.
+	fn assigned_location_meetup(
+		cc: CommunityCeremony,
+		location: Location,
+	) -> Option<MeetupIndexType> {
+		if Self::regional_assignment_count(cc) > 0 {
+			let region = GeoHash::try_from_params(location.lat, location.lon).ok()?;
+			let regional_assignment = (0..Self::regional_assignment_count(cc))
+				.filter_map(|i| Self::regional_assignments(cc, i))
+				.find(|r| r.region == region)?;
+			let locations = <encointer_communities::Pallet<T>>::locations(cc.0, &region);
+			let location_index = locations.iter().position(|l| *l == location)? as u64;
+			return get_location_meetup_index(
+				location_index,
+				&locations,
+				regional_assignment.params.locations,
+				regional_assignment.meetup_count,
+			)
+			.map(|m| m + regional_assignment.meetup_offset)
+		}
 
-	#[derive(frame_support::DefaultNoBound)]
-	#[pallet::genesis_config]
-	pub struct GenesisConfig<T: Config>
-	where
-		<T as pallet_timestamp::Config>::Moment: MaybeSerializeDeserialize,
-	{
-		#[doc = " the default UBI for a ceremony attendee if no community specific value is set."]
-		pub ceremony_reward: BalanceType,
-		pub location_tolerance: u32,
-		pub time_tolerance: T::Moment,
-		pub inactivity_timeout: InactivityTimeoutType,
-		pub endorsement_tickets_per_bootstrapper: EndorsementTicketsType,
-		pub endorsement_tickets_per_reputable: EndorsementTicketsType,
-		pub reputation_lifetime: ReputationLifetimeType,
-		pub meetup_time_offset: MeetupTimeOffsetType,
-		#[serde(skip)]
-		pub _config: sp_std::marker::PhantomData<T>,
+		let locations = <encointer_communities::Pallet<T>>::get_locations(&cc.0);
+		let location_index = locations.iter().position(|l| *l == location)? as u64;
+		get_location_meetup_index(
+			location_index,
+			&locations,
+			Self::assignments(cc).locations,
+			Self::meetup_count(cc),
+		)
 	}
 
-	#[pallet::genesis_build]
-	impl<T: Config> BuildGenesisConfig for GenesisConfig<T>
-	where
-		<T as pallet_timestamp::Config>::Moment: MaybeSerializeDeserialize,
-	{
-		fn build(&self) {
-			<CeremonyReward<T>>::put(self.ceremony_reward);
-			<LocationTolerance<T>>::put(self.location_tolerance);
-			<TimeTolerance<T>>::put(self.time_tolerance);
-			<InactivityTimeout<T>>::put(self.inactivity_timeout);
-			<EndorsementTicketsPerBootstrapper<T>>::put(self.endorsement_tickets_per_bootstrapper);
-			<EndorsementTicketsPerReputable<T>>::put(self.endorsement_tickets_per_reputable);
-			<ReputationLifetime<T>>::put(self.reputation_lifetime);
-			<MeetupTimeOffset<T>>::put(self.meetup_time_offset);
-		}
+	fn ensure_meetup_changeable(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		meetup_index: MeetupIndexType,
+	) -> DispatchResult {
+		ensure!(
+			<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+			Error::<T>::InexistentCommunity
+		);
+		ensure!(
+			cindex == <encointer_scheduler::Pallet<T>>::current_ceremony_index() &&
+				<encointer_scheduler::Pallet<T>>::current_phase() !=
+					CeremonyPhaseType::Registering,
+			Error::<T>::WrongPhaseForChangingMeetups
+		);
+		ensure!(
+			meetup_index >= 1 && meetup_index <= Self::meetup_count((cid, cindex)),
+			Error::<T>::InvalidMeetupIndex
+		);
+		ensure!(
+			!<CancelledMeetups<T>>::contains_key((cid, cindex), meetup_index),
+			Error::<T>::MeetupHasBeenCancelled
+		);
+		Ok(())
 	}
-}
 
-impl<T: Config> Pallet<T> {
-	pub fn do_set_inactivity_timeout(
-		inactivity_timeout: InactivityTimeoutType,
-	) -> DispatchResultWithPostInfo {
-		<InactivityTimeout<T>>::put(inactivity_timeout);
-		info!(target: LOG, "set inactivity timeout to {}", inactivity_timeout);
-		Self::deposit_event(Event::InactivityTimeoutUpdated(inactivity_timeout));
-		Ok(().into())
+	pub fn partner_communities(cid: &CommunityIdentifier) -> Vec<CommunityIdentifier> {
+		<PartnerCommunities<T>>::iter_key_prefix(cid).collect()
+	}
+
+	/// Whether reputation earned in `other` counts fully in `cid`.
+	pub fn is_trusted_community(cid: &CommunityIdentifier, other: &CommunityIdentifier) -> bool {
+		cid == other || <PartnerCommunities<T>>::contains_key(cid, other)
 	}
 
 	pub fn get_reputations(
@@ -1098,6 +1959,25 @@ impl<T: Config> Pallet<T> {
 			.collect()
 	}
 
+	pub fn get_reputation_score(
+		cid: CommunityIdentifier,
+		account: &T::AccountId,
+	) -> ReputationScore {
+		Self::reputation_score(account, &cid)
+	}
+
+	pub fn get_ceremony_stats(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+	) -> CeremonyStats {
+		let cc = (cid, cindex);
+		CeremonyStats {
+			registered: Self::registered_counts(cc),
+			assigned: Self::assignment_counts(cc),
+			..Self::ceremony_statistics(cc)
+		}
+	}
+
 	pub fn get_aggregated_account_data(
 		cid: CommunityIdentifier,
 		account: &T::AccountId,
@@ -1125,15 +2005,25 @@ impl<T: Config> Pallet<T> {
 				let locations = <encointer_communities::Pallet<T>>::get_locations(&cid);
 				let location_assignment_params = Self::assignments((cid, cindex)).locations;
 
-				meetup_location_index = get_meetup_location_index(
-					participant_meetup_index,
-					&locations,
-					location_assignment_params,
-				);
+				meetup_location_index = if Self::regional_assignment_count((cid, cindex)) > 0 {
+					Self::get_meetup_location((cid, cindex), participant_meetup_index)
+						.and_then(|l| locations.iter().position(|location| *location == l))
+						.map(|i| i as MeetupIndexType)
+				} else {
+					get_meetup_location_index(
+						participant_meetup_index,
+						&locations,
+						location_assignment_params,
+					)
+				};
 				if let Some(location) =
 					Self::get_meetup_location((cid, cindex), participant_meetup_index)
 				{
-					meetup_time = Self::get_meetup_time(location);
+					meetup_time = Self::get_community_meetup_time(
+						(cid, cindex),
+						participant_meetup_index,
+						location,
+					);
 				}
 
 				meetup_registry =
@@ -1164,49 +2054,161 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Registers `sender` for the upcoming ceremony of `cid`. Participants with a
+	/// `preferred_region` are assigned to a meetup within that region if enough participants
+	/// prefer it.
+	fn do_register_participant(
+		sender: T::AccountId,
+		cid: CommunityIdentifier,
+		proof: Option<ProofOfAttendance<T::Signature, T::AccountId>>,
+		preferred_region: Option<GeoHash>,
+	) -> DispatchResultWithPostInfo {
+		let current_phase = <encointer_scheduler::Pallet<T>>::current_phase();
+		ensure!(
+			CeremonyPhaseType::is_registering_or_attesting(&current_phase),
+			Error::<T>::RegisteringOrAttestationPhaseRequired
+		);
+
+		ensure!(
+			<encointer_communities::Pallet<T>>::community_identifiers().contains(&cid),
+			Error::<T>::InexistentCommunity
+		);
+		if let Some(region) = &preferred_region {
+			ensure!(
+				!<encointer_communities::Pallet<T>>::locations(cid, region).is_empty(),
+				Error::<T>::NoLocationsInRegion
+			);
+		}
+
+		let mut cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+
+		if current_phase == CeremonyPhaseType::Attesting {
+			cindex += 1
+		};
+
+		if Self::is_registered(cid, cindex, &sender) {
+			return Err(<Error<T>>::ParticipantAlreadyRegistered.into())
+		}
+
+		// penalized participants can only register as endorsee or newbie
+		let proof = match proof {
+			Some(_) if Self::is_penalized(&sender, &cid) => {
+				debug!(target: LOG, "ignoring proof of attendance of penalized {:?}", sender);
+				None
+			},
+			proof => proof,
+		};
+
+		if let Some(p) = &proof {
+			// we accept proofs from other communities as well. proofs from communities which
+			// are not trusted by `cid` only grant endorsee status
+			ensure!(sender == p.prover_public, Error::<T>::WrongProofSubject);
+			ensure!(p.ceremony_index < cindex, Error::<T>::ProofAcausal);
+			ensure!(
+				p.ceremony_index >= cindex.saturating_sub(Self::reputation_lifetime()),
+				Error::<T>::ProofOutdated
+			);
+			ensure!(
+				Self::participant_reputation(
+					(p.community_identifier, p.ceremony_index),
+					&p.attendee_public
+				) == Reputation::VerifiedUnlinked,
+				Error::<T>::AttendanceUnverifiedOrAlreadyUsed
+			);
+			if Self::verify_attendee_signature(p.clone()).is_err() {
+				return Err(<Error<T>>::BadProofOfAttendanceSignature.into())
+			};
+
+			// this reputation must now be burned so it can not be used again
+			<ParticipantReputation<T>>::insert(
+				(p.community_identifier, p.ceremony_index),
+				&p.attendee_public,
+				Reputation::VerifiedLinked,
+			);
+			<LinkedReputations<T>>::insert(
+				(cid, cindex),
+				&sender,
+				((p.community_identifier, p.ceremony_index), p.attendee_public.clone()),
+			);
+			if Self::is_trusted_community(&cid, &p.community_identifier) {
+				// register participant as reputable
+				<ParticipantReputation<T>>::insert(
+					(cid, cindex),
+					&sender,
+					Reputation::UnverifiedReputable,
+				);
+			}
+		};
+
+		let participant_type =
+			Self::register(cid, cindex, &sender, proof.as_ref().map(|p| p.community_identifier))?;
+		if let Some(region) = preferred_region {
+			<PreferredRegions<T>>::insert((cid, cindex), &sender, region);
+		}
+
+		// invalidate reputation cache
+		sp_io::offchain_index::set(&reputation_cache_dirty_key(&sender), &true.encode());
+
+		debug!(target: LOG, "registered participant: {:?} as {:?}", sender, participant_type);
+		Self::deposit_event(Event::ParticipantRegistered(cid, participant_type, sender));
+
+		Ok(().into())
+	}
+
 	fn register(
 		cid: CommunityIdentifier,
 		cindex: CeremonyIndexType,
 		sender: &T::AccountId,
-		is_reputable: bool,
+		proof_community: Option<CommunityIdentifier>,
 	) -> Result<ParticipantType, Error<T>> {
-		let participant_type =
-			if <encointer_communities::Pallet<T>>::bootstrappers(cid).contains(sender) {
-				let participant_index = <BootstrapperCount<T>>::get((cid, cindex))
-					.checked_add(1)
-					.ok_or(Error::<T>::RegistryOverflow)?;
-				<BootstrapperRegistry<T>>::insert((cid, cindex), participant_index, sender);
-				<BootstrapperIndex<T>>::insert((cid, cindex), sender, participant_index);
-				<BootstrapperCount<T>>::insert((cid, cindex), participant_index);
-				ParticipantType::Bootstrapper
-			} else if <encointer_balances::Pallet<T>>::total_issuance(cid) <= 0 {
-				return Err(Error::<T>::OnlyBootstrappers)
-			} else if is_reputable {
-				let participant_index = <ReputableCount<T>>::get((cid, cindex))
-					.checked_add(1)
-					.ok_or(Error::<T>::RegistryOverflow)?;
-				<ReputableRegistry<T>>::insert((cid, cindex), participant_index, sender);
-				<ReputableIndex<T>>::insert((cid, cindex), sender, participant_index);
-				<ReputableCount<T>>::insert((cid, cindex), participant_index);
-				ParticipantType::Reputable
-			} else if let Some(endorsed_cindex) = Self::is_endorsed(sender, &(cid, cindex)) {
-				let participant_index = <EndorseeCount<T>>::get((cid, cindex))
-					.checked_add(1)
-					.ok_or(Error::<T>::RegistryOverflow)?;
-				<Endorsees<T>>::remove((cid, endorsed_cindex), sender);
-				<EndorseeRegistry<T>>::insert((cid, cindex), participant_index, sender);
-				<EndorseeIndex<T>>::insert((cid, cindex), sender, participant_index);
-				<EndorseeCount<T>>::insert((cid, cindex), participant_index);
-				ParticipantType::Endorsee
-			} else {
-				let participant_index = <NewbieCount<T>>::get((cid, cindex))
-					.checked_add(1)
-					.ok_or(Error::<T>::RegistryOverflow)?;
-				<NewbieRegistry<T>>::insert((cid, cindex), participant_index, sender);
-				<NewbieIndex<T>>::insert((cid, cindex), sender, participant_index);
-				<NewbieCount<T>>::insert((cid, cindex), participant_index);
-				ParticipantType::Newbie
-			};
+		let participant_type = if <encointer_communities::Pallet<T>>::bootstrappers(cid)
+			.contains(sender) &&
+			!Self::is_penalized(sender, &cid)
+		{
+			let participant_index = <BootstrapperCount<T>>::get((cid, cindex))
+				.checked_add(1)
+				.ok_or(Error::<T>::RegistryOverflow)?;
+			<BootstrapperRegistry<T>>::insert((cid, cindex), participant_index, sender);
+			<BootstrapperIndex<T>>::insert((cid, cindex), sender, participant_index);
+			<BootstrapperCount<T>>::insert((cid, cindex), participant_index);
+			ParticipantType::Bootstrapper
+		} else if <encointer_balances::Pallet<T>>::total_issuance(cid) <= 0 {
+			return Err(Error::<T>::OnlyBootstrappers)
+		} else if proof_community.map_or(false, |p| Self::is_trusted_community(&cid, &p)) {
+			let participant_index = <ReputableCount<T>>::get((cid, cindex))
+				.checked_add(1)
+				.ok_or(Error::<T>::RegistryOverflow)?;
+			<ReputableRegistry<T>>::insert((cid, cindex), participant_index, sender);
+			<ReputableIndex<T>>::insert((cid, cindex), sender, participant_index);
+			<ReputableCount<T>>::insert((cid, cindex), participant_index);
+			ParticipantType::Reputable
+		} else if proof_community.is_some() {
+			// reputation from untrusted communities still proves personhood
+			let participant_index = <EndorseeCount<T>>::get((cid, cindex))
+				.checked_add(1)
+				.ok_or(Error::<T>::RegistryOverflow)?;
+			<EndorseeRegistry<T>>::insert((cid, cindex), participant_index, sender);
+			<EndorseeIndex<T>>::insert((cid, cindex), sender, participant_index);
+			<EndorseeCount<T>>::insert((cid, cindex), participant_index);
+			ParticipantType::Endorsee
+		} else if let Some(endorsed_cindex) = Self::is_endorsed(sender, &(cid, cindex)) {
+			let participant_index = <EndorseeCount<T>>::get((cid, cindex))
+				.checked_add(1)
+				.ok_or(Error::<T>::RegistryOverflow)?;
+			<Endorsees<T>>::remove((cid, endorsed_cindex), sender);
+			<EndorseeRegistry<T>>::insert((cid, cindex), participant_index, sender);
+			<EndorseeIndex<T>>::insert((cid, cindex), sender, participant_index);
+			<EndorseeCount<T>>::insert((cid, cindex), participant_index);
+			ParticipantType::Endorsee
+		} else {
+			let participant_index = <NewbieCount<T>>::get((cid, cindex))
+				.checked_add(1)
+				.ok_or(Error::<T>::RegistryOverflow)?;
+			<NewbieRegistry<T>>::insert((cid, cindex), participant_index, sender);
+			<NewbieIndex<T>>::insert((cid, cindex), sender, participant_index);
+			<NewbieCount<T>>::insert((cid, cindex), participant_index);
+			ParticipantType::Newbie
+		};
 		Ok(participant_type)
 	}
 
@@ -1280,7 +2282,11 @@ impl<T: Config> Pallet<T> {
 		cindex: CeremonyIndexType,
 		sender: &T::AccountId,
 	) -> Result<(), Error<T>> {
-		if Self::has_reputation(sender, &cid) &&
+		if Self::is_penalized(sender, &cid) {
+			return Err(Error::<T>::NewbieTicketsWithheld)
+		}
+
+		if Self::has_trusted_reputation(sender, &cid) &&
 			<BurnedReputableNewbieTickets<T>>::get((cid, cindex), sender) <
 				Self::endorsement_tickets_per_reputable()
 		{
@@ -1335,12 +2341,22 @@ impl<T: Config> Pallet<T> {
 		<Endorsees<T>>::remove_prefix(cc, None);
 		<EndorseesCount<T>>::remove(cc);
 		<MeetupCount<T>>::remove(cc);
+		<CeremonyStatistics<T>>::remove(cc);
+		<Waitlist<T>>::remove(cc);
+		<LinkedReputations<T>>::remove_prefix(cc, None);
+		<CancelledMeetups<T>>::remove_prefix(cc, None);
+		<RescheduledMeetupLocations<T>>::remove_prefix(cc, None);
+		<RescheduledLocationMeetups<T>>::remove_prefix(cc, None);
+		<PreferredRegions<T>>::remove_prefix(cc, None);
+		<RegionalAssignments<T>>::remove_prefix(cc, None);
+		<RegionalAssignmentCount<T>>::remove(cc);
 
 		<AttestationRegistry<T>>::remove_prefix(cc, None);
 		<AttestationIndex<T>>::remove_prefix(cc, None);
 		<AttestationCount<T>>::remove(cc);
 
 		<MeetupParticipantCountVote<T>>::remove_prefix(cc, None);
+		<MeetupTimeSlot<T>>::remove_prefix(cc, None);
 		<IssuedRewards<T>>::remove_prefix(cc, None);
 		<BurnedReputableNewbieTickets<T>>::remove_prefix(cc, None);
 
@@ -1363,6 +2379,9 @@ impl<T: Config> Pallet<T> {
 			target: LOG,
 			"generating meetup assignment params for cid: {:?}", community_ceremony.0
 		);
+		if Self::generate_regional_assignment_params(community_ceremony, random_source)? {
+			return Ok(())
+		}
 		let meetup_multiplier = T::MeetupSizeTarget::get();
 		let assignment_allowance =
 			Self::compute_assignment_allowance(community_ceremony, meetup_multiplier)?;
@@ -1381,29 +2400,16 @@ impl<T: Config> Pallet<T> {
 		}
 		info!(target: LOG, "assigning {:} meetups for cid {:?}", num_meetups, community_ceremony.0);
 
+		let num_locations =
+			<encointer_communities::Pallet<T>>::get_locations(&community_ceremony.0).len() as u64;
 		<Assignments<T>>::insert(
 			community_ceremony,
-			Assignment {
-				bootstrappers_reputables: generate_assignment_function_params(
-					assignment_allowance.bootstrappers + assignment_allowance.reputables,
-					num_meetups,
-					random_source,
-				),
-				endorsees: generate_assignment_function_params(
-					assignment_allowance.endorsees,
-					num_meetups,
-					random_source,
-				),
-				newbies: generate_assignment_function_params(
-					assignment_allowance.newbies,
-					num_meetups,
-					random_source,
-				),
-				locations: Self::generate_location_assignment_params(
-					community_ceremony,
-					random_source,
-				),
-			},
+			Self::generate_assignment(
+				assignment_allowance,
+				num_meetups,
+				num_locations,
+				random_source,
+			),
 		);
 
 		<AssignmentCounts<T>>::insert(community_ceremony, assignment_allowance);
@@ -1411,13 +2417,274 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn generate_location_assignment_params(
+	/// Groups the participants by their preferred region and assigns them to meetups at the
+	/// community locations within their region.
+	///
+	/// A region is considered if it is preferred by a bootstrapper or reputable and its
+	/// participants fill at least `MIN_MEETUPS_PER_REGION` meetups at its locations. Participants
+	/// without (considered) preference are distributed evenly over the regions. Returns `false` if no region is considered or if the
+	/// bootstrappers of a region exceed the seats of its locations, in which case all participants
+	/// are assigned to meetups across all community locations.
+	fn generate_regional_assignment_params(
 		community_ceremony: CommunityCeremony,
 		random_source: &mut RandomNumberGenerator<T::Hashing>,
-	) -> AssignmentParams {
-		let num_locations =
-			<encointer_communities::Pallet<T>>::get_locations(&community_ceremony.0).len() as u64;
+	) -> Result<bool, Error<T>> {
+		if <PreferredRegions<T>>::iter_prefix(community_ceremony).next().is_none() {
+			return Ok(false)
+		}
+
+		let bootstrappers = storage_helper::registered_participants::<
+			BootstrapperRegistry<T>,
+			BootstrapperCount<T>,
+			T::AccountId,
+		>(community_ceremony);
+		let reputables = storage_helper::registered_participants::<
+			ReputableRegistry<T>,
+			ReputableCount<T>,
+			T::AccountId,
+		>(community_ceremony);
+		let endorsees = storage_helper::registered_participants::<
+			EndorseeRegistry<T>,
+			EndorseeCount<T>,
+			T::AccountId,
+		>(community_ceremony);
+		let newbies = storage_helper::registered_participants::<
+			NewbieRegistry<T>,
+			NewbieCount<T>,
+			T::AccountId,
+		>(community_ceremony);
+
+		let preferences = |participants: &[T::AccountId]| -> Vec<Option<GeoHash>> {
+			participants
+				.iter()
+				.map(|p| Self::preferred_region(community_ceremony, p))
+				.collect()
+		};
+		let RegionalGrouping {
+			regions,
+			groups: [bootstrapper_groups, reputable_groups, endorsee_groups, newbie_groups],
+			allowances,
+		} = match Self::group_by_preferred_region(
+			community_ceremony.0,
+			[
+				&preferences(&bootstrappers),
+				&preferences(&reputables),
+				&preferences(&endorsees),
+				&preferences(&newbies),
+			],
+		) {
+			Some(grouping) => grouping,
+			None => return Ok(false),
+		};
+		let meetup_multiplier = T::MeetupSizeTarget::get();
+
+		// reindex the registries such that the participants of each region are contiguous
+		let grouped = |participants: &[T::AccountId], groups: &[Vec<usize>]| -> Vec<T::AccountId> {
+			groups.iter().flatten().map(|i| participants[*i].clone()).collect()
+		};
+		storage_helper::reorder_registry::<
+			BootstrapperIndex<T>,
+			BootstrapperRegistry<T>,
+			T::AccountId,
+		>(community_ceremony, &grouped(&bootstrappers, &bootstrapper_groups));
+		storage_helper::reorder_registry::<ReputableIndex<T>, ReputableRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&grouped(&reputables, &reputable_groups),
+		);
+		storage_helper::reorder_registry::<EndorseeIndex<T>, EndorseeRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&grouped(&endorsees, &endorsee_groups),
+		);
+		storage_helper::reorder_registry::<NewbieIndex<T>, NewbieRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&grouped(&newbies, &newbie_groups),
+		);
+
+		let mut offsets = AssignmentCount::default();
+		let mut total_assigned = AssignmentCount::default();
+		let mut meetup_offset: MeetupIndexType = 0;
+		let num_regions = regions.len() as u32;
+		for (i, (region, (registered, num_locations, mut assigned))) in
+			regions.into_iter().zip(allowances).enumerate()
+		{
+			let mut meetup_count = 0;
+			let mut params = Assignment::default();
+			if assigned.get_number_of_participants() < T::MeetupMinSize::get() {
+				info!(
+					target: LOG,
+					"too few participants available for a meetup in region {:?} of cid {:?}",
+					region,
+					community_ceremony.0
+				);
+				assigned = AssignmentCount::default();
+			} else {
+				meetup_count =
+					checked_ceil_division(assigned.get_number_of_participants(), meetup_multiplier)
+						.ok_or(Error::<T>::CheckedMath)?;
+				params =
+					Self::generate_assignment(assigned, meetup_count, num_locations, random_source);
+			}
+			info!(
+				target: LOG,
+				"assigning {:} meetups in region {:?} for cid {:?}",
+				meetup_count,
+				region,
+				community_ceremony.0
+			);
+
+			<RegionalAssignments<T>>::insert(
+				community_ceremony,
+				i as u32,
+				RegionalAssignment {
+					region,
+					offsets,
+					assigned,
+					meetup_offset,
+					meetup_count,
+					params,
+				},
+			);
+
+			//safe; bounded by the number of registered participants
+			offsets = AssignmentCount {
+				bootstrappers: offsets.bootstrappers + registered.bootstrappers,
+				reputables: offsets.reputables + registered.reputables,
+				endorsees: offsets.endorsees + registered.endorsees,
+				newbies: offsets.newbies + registered.newbies,
+			};
+			total_assigned = AssignmentCount {
+				bootstrappers: total_assigned.bootstrappers + assigned.bootstrappers,
+				reputables: total_assigned.reputables + assigned.reputables,
+				endorsees: total_assigned.endorsees + assigned.endorsees,
+				newbies: total_assigned.newbies + assigned.newbies,
+			};
+			meetup_offset += meetup_count;
+		}
+
+		<RegionalAssignmentCount<T>>::insert(community_ceremony, num_regions);
+		<AssignmentCounts<T>>::insert(community_ceremony, total_assigned);
+		<MeetupCount<T>>::insert(community_ceremony, meetup_offset);
+		Ok(true)
+	}
+
+	/// Groups the participants by the regions considered for the assignment, given the preferred
+	/// regions of the bootstrappers, reputables, endorsees and newbies in registry order. Returns
+	/// `None` if no region is considered or if the bootstrappers of a region exceed the seats of
+	/// its locations.
+	///
+	/// A region is only considered if its participants are assigned to at least
+	/// `MIN_MEETUPS_PER_REGION` meetups, such that preferring a region doesn't let a group of
+	/// participants choose to share a meetup.
+	fn group_by_preferred_region(
+		cid: CommunityIdentifier,
+		preferences: [&[Option<GeoHash>]; 4],
+	) -> Option<RegionalGrouping> {
+		let [bootstrapper_preferences, reputable_preferences, endorsee_preferences, newbie_preferences] =
+			preferences;
+		let meetup_multiplier = T::MeetupSizeTarget::get();
+		let mut regions: Vec<GeoHash> = vec![];
+		for region in bootstrapper_preferences.iter().chain(reputable_preferences.iter()).flatten()
+		{
+			if regions.contains(region) {
+				continue
+			}
+			let num_preferring = bootstrapper_preferences
+				.iter()
+				.chain(reputable_preferences.iter())
+				.chain(endorsee_preferences.iter())
+				.chain(newbie_preferences.iter())
+				.filter(|p| p.as_ref() == Some(region))
+				.count() as u64;
+			let num_locations =
+				<encointer_communities::Pallet<T>>::locations(cid, region).len() as u64;
+			if num_preferring >= MIN_MEETUPS_PER_REGION * meetup_multiplier &&
+				num_locations >= MIN_MEETUPS_PER_REGION
+			{
+				regions.push(region.clone());
+			}
+		}
+		regions.sort();
 
+		// the participants of a region which is dropped are distributed over the others
+		loop {
+			if regions.is_empty() {
+				return None
+			}
+			let groups = preferences.map(|p| group_by_region(p, &regions));
+
+			let mut allowances = vec![];
+			let mut too_small = vec![];
+			for (i, region) in regions.iter().enumerate() {
+				let registered = AssignmentCount {
+					bootstrappers: groups[0][i].len() as u64,
+					reputables: groups[1][i].len() as u64,
+					endorsees: groups[2][i].len() as u64,
+					newbies: groups[3][i].len() as u64,
+				};
+				let num_locations =
+					<encointer_communities::Pallet<T>>::locations(cid, region).len() as u64;
+				match Self::assignment_allowance(num_locations, registered, meetup_multiplier) {
+					Ok(assigned) => {
+						if checked_ceil_division(
+							assigned.get_number_of_participants(),
+							meetup_multiplier,
+						)
+						.unwrap_or_default() < MIN_MEETUPS_PER_REGION
+						{
+							too_small.push(region.clone());
+						}
+						allowances.push((registered, num_locations, assigned))
+					},
+					Err(e) => {
+						warn!(
+							target: LOG,
+							"cannot assign meetups in region {:?} of cid {:?}: {:?}. ignoring regions",
+							region,
+							cid,
+							e
+						);
+						return None
+					},
+				}
+			}
+			if too_small.is_empty() {
+				return Some(RegionalGrouping { regions, groups, allowances })
+			}
+			debug!(target: LOG, "too few meetups in regions {:?} of cid {:?}", too_small, cid);
+			regions.retain(|r| !too_small.contains(r));
+		}
+	}
+
+	fn generate_assignment(
+		assigned: AssignmentCount,
+		num_meetups: u64,
+		num_locations: u64,
+		random_source: &mut RandomNumberGenerator<T::Hashing>,
+	) -> Assignment {
+		Assignment {
+			bootstrappers_reputables: generate_assignment_function_params(
+				assigned.bootstrappers + assigned.reputables,
+				num_meetups,
+				random_source,
+			),
+			endorsees: generate_assignment_function_params(
+				assigned.endorsees,
+				num_meetups,
+				random_source,
+			),
+			newbies: generate_assignment_function_params(
+				assigned.newbies,
+				num_meetups,
+				random_source,
+			),
+			locations: Self::generate_location_assignment_params(num_locations, random_source),
+		}
+	}
+
+	fn generate_location_assignment_params(
+		num_locations: u64,
+		random_source: &mut RandomNumberGenerator<T::Hashing>,
+	) -> AssignmentParams {
 		AssignmentParams {
 			m: num_locations,
 			s1: find_random_coprime_below(num_locations, random_source),
@@ -1435,14 +2702,195 @@ impl<T: Config> Pallet<T> {
 			target: LOG,
 			"Number of locations for cid {:?} is {:?}", community_ceremony.0, num_locations
 		);
+
+		let registered = Self::registered_counts(community_ceremony);
+		Self::assignment_allowance(num_locations, registered, meetup_multiplier)
+	}
+
+	fn registered_counts(community_ceremony: CommunityCeremony) -> AssignmentCount {
+		AssignmentCount {
+			bootstrappers: Self::bootstrapper_count(community_ceremony),
+			reputables: Self::reputable_count(community_ceremony),
+			endorsees: Self::endorsee_count(community_ceremony),
+			newbies: Self::newbie_count(community_ceremony),
+		}
+	}
+
+	/// Simulates the meetup assignment on the current registries during REGISTERING. Waitlisted
+	/// participants of the previous ceremony who have not been registered yet are counted as
+	/// registered and, like the other waitlisted participants, are assigned first. Returns `None`
+	/// outside of REGISTERING.
+	pub fn get_assignment_forecast(cid: CommunityIdentifier) -> Option<AssignmentForecast> {
+		if <encointer_scheduler::Pallet<T>>::current_phase() != CeremonyPhaseType::Registering {
+			return None
+		}
+		let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+		let cc = (cid, cindex);
+
+		let waitlist = Self::waitlist((cid, cindex.saturating_sub(1)));
+		let waitlisted: BTreeSet<T::AccountId> =
+			waitlist.iter().map(|(participant, _)| participant.clone()).collect();
+		// waitlisted participants who are yet to be registered in `on_idle`
+		let mut pending = AssignmentCount::default();
+		for (participant, participant_type) in waitlist.iter() {
+			if !Self::waitlist_auto_registration(cid, participant) ||
+				Self::is_registered(cid, cindex, participant)
+			{
+				continue
+			}
+			match participant_type {
+				ParticipantType::Reputable
+					if Self::waitlisted_linked_reputation(
+						cid,
+						cindex,
+						participant,
+						*participant_type,
+					)
+					.is_some() =>
+					pending.reputables += 1,
+				ParticipantType::Endorsee => pending.endorsees += 1,
+				_ => pending.newbies += 1,
+			}
+		}
+
+		// preferred regions in the order in which the participants will be assigned, along with
+		// the number of prioritized participants
+		let preferences = |participants: Vec<T::AccountId>, pending: u64| {
+			let (prioritized, others): (Vec<_>, Vec<_>) =
+				participants.into_iter().partition(|p| waitlisted.contains(p));
+			let num_prioritized = prioritized.len() as u64 + pending;
+			let preferences: Vec<Option<GeoHash>> = prioritized
+				.iter()
+				.map(|p| Self::preferred_region(cc, p))
+				.chain((0..pending).map(|_| None))
+				.chain(others.iter().map(|p| Self::preferred_region(cc, p)))
+				.collect();
+			(preferences, num_prioritized)
+		};
+		let (bootstrapper_preferences, bootstrappers_waitlisted) = preferences(
+			storage_helper::registered_participants::<
+				BootstrapperRegistry<T>,
+				BootstrapperCount<T>,
+				T::AccountId,
+			>(cc),
+			0,
+		);
+		let (reputable_preferences, reputables_waitlisted) = preferences(
+			storage_helper::registered_participants::<
+				ReputableRegistry<T>,
+				ReputableCount<T>,
+				T::AccountId,
+			>(cc),
+			pending.reputables,
+		);
+		let (endorsee_preferences, endorsees_waitlisted) = preferences(
+			storage_helper::registered_participants::<
+				EndorseeRegistry<T>,
+				EndorseeCount<T>,
+				T::AccountId,
+			>(cc),
+			pending.endorsees,
+		);
+		let (newbie_preferences, newbies_waitlisted) = preferences(
+			storage_helper::registered_participants::<
+				NewbieRegistry<T>,
+				NewbieCount<T>,
+				T::AccountId,
+			>(cc),
+			pending.newbies,
+		);
+		let registered = AssignmentCount {
+			bootstrappers: bootstrapper_preferences.len() as u64,
+			reputables: reputable_preferences.len() as u64,
+			endorsees: endorsee_preferences.len() as u64,
+			newbies: newbie_preferences.len() as u64,
+		};
+
+		let meetup_multiplier = T::MeetupSizeTarget::get();
+		let regional_grouping = Self::group_by_preferred_region(
+			cid,
+			[
+				&bootstrapper_preferences,
+				&reputable_preferences,
+				&endorsee_preferences,
+				&newbie_preferences,
+			],
+		);
+		let (assigned, missing_locations) = match regional_grouping {
+			Some(RegionalGrouping { allowances, .. }) => allowances.into_iter().fold(
+				(AssignmentCount::default(), 0),
+				|(total, missing), (registered, num_locations, assigned)| {
+					let assigned =
+						if assigned.get_number_of_participants() < T::MeetupMinSize::get() {
+							AssignmentCount::default()
+						} else {
+							assigned
+						};
+					(
+						AssignmentCount {
+							bootstrappers: total.bootstrappers + assigned.bootstrappers,
+							reputables: total.reputables + assigned.reputables,
+							endorsees: total.endorsees + assigned.endorsees,
+							newbies: total.newbies + assigned.newbies,
+						},
+						missing +
+							Self::missing_locations(registered, num_locations, meetup_multiplier),
+					)
+				},
+			),
+			None => {
+				let num_locations =
+					<encointer_communities::Pallet<T>>::get_locations(&cid).len() as u64;
+				(
+					Self::assignment_allowance(num_locations, registered, meetup_multiplier)
+						.unwrap_or_default(),
+					Self::missing_locations(registered, num_locations, meetup_multiplier),
+				)
+			},
+		};
+
+		Some(AssignmentForecast {
+			registered,
+			assigned,
+			missing_locations,
+			waitlisted: AssignmentCount {
+				bootstrappers: bootstrappers_waitlisted,
+				reputables: reputables_waitlisted,
+				endorsees: endorsees_waitlisted,
+				newbies: newbies_waitlisted,
+			},
+		})
+	}
+
+	/// Number of locations needed in addition to `num_locations` to assign all participants the
+	/// newbie limit permits.
+	fn missing_locations(
+		registered: AssignmentCount,
+		num_locations: u64,
+		meetup_multiplier: u64,
+	) -> u64 {
+		// more meetups than this are never assigned, no matter how many locations there are
+		let max_num_meetups = find_prime_below(registered.bootstrappers + registered.reputables);
+		let assignable = Self::assignment_allowance(max_num_meetups, registered, meetup_multiplier)
+			.unwrap_or_default();
+		checked_ceil_division(assignable.get_number_of_participants(), meetup_multiplier)
+			.unwrap_or_default()
+			.saturating_sub(num_locations)
+	}
+
+	fn assignment_allowance(
+		num_locations: u64,
+		registered: AssignmentCount,
+		meetup_multiplier: u64,
+	) -> Result<AssignmentCount, Error<T>> {
 		if num_locations == 0 {
 			return Err(<Error<T>>::NoLocationsAvailable)
 		}
 
-		let num_registered_bootstrappers = Self::bootstrapper_count(community_ceremony);
-		let num_registered_reputables = Self::reputable_count(community_ceremony);
-		let num_registered_endorsees = Self::endorsee_count(community_ceremony);
-		let num_registered_newbies = Self::newbie_count(community_ceremony);
+		let num_registered_bootstrappers = registered.bootstrappers;
+		let num_registered_reputables = registered.reputables;
+		let num_registered_endorsees = registered.endorsees;
+		let num_registered_newbies = registered.newbies;
 		debug!(
 			target: LOG,
 			"Number of registered bootstrappers {:?}, endorsees {:?}, reputables {:?}, newbies {:?}",
@@ -1455,10 +2903,12 @@ impl<T: Config> Pallet<T> {
 			find_prime_below(num_registered_bootstrappers + num_registered_reputables),
 		);
 
-		//safe; number of assigned bootstrappers <= max_num_meetups <=num_assigned_bootstrappers + num_reputables
-		let mut seats_left =
-			max_num_meetups.checked_mul(meetup_multiplier).ok_or(Error::<T>::CheckedMath)? -
-				num_registered_bootstrappers;
+		// bootstrappers are always assigned, so they must fit into the meetups
+		let mut seats_left = max_num_meetups
+			.checked_mul(meetup_multiplier)
+			.ok_or(Error::<T>::CheckedMath)?
+			.checked_sub(num_registered_bootstrappers)
+			.ok_or(Error::<T>::NotEnoughSeatsForBootstrappers)?;
 
 		let num_assigned_reputables = min(num_registered_reputables, seats_left);
 		seats_left -= num_assigned_reputables; //safe; given by minimum above
@@ -1494,7 +2944,8 @@ impl<T: Config> Pallet<T> {
 	) -> Vec<CommunityIdentifier> {
 		let mut inactives = vec![];
 		for cid in cids {
-			if <IssuedRewards<T>>::iter_prefix_values((cid, cindex)).next().is_some() {
+			// only meetups which have been rewarded count as activity
+			if Self::ceremony_statistics((cid, cindex)).meetup_results.ok > 0 {
 				<InactivityCounters<T>>::insert(cid, 0);
 				Self::deposit_event(Event::InactivityCounterUpdated(cid, 0));
 			} else {
@@ -1520,9 +2971,19 @@ impl<T: Config> Pallet<T> {
 		}
 
 		<InactivityCounters<T>>::remove(cid);
+		<MeetupValidationPolicies<T>>::remove(cid);
 
 		#[allow(deprecated)]
-		<BurnedBootstrapperNewbieTickets<T>>::remove_prefix(cid, None);
+		<BurnedBootstrapperNewbieTickets<T>>::remove_prefix(cid, None);
+		#[allow(deprecated)]
+		<Penalties<T>>::remove_prefix(cid, None);
+		#[allow(deprecated)]
+		<PartnerCommunities<T>>::remove_prefix(cid, None);
+		for other in <encointer_communities::Pallet<T>>::community_identifiers().iter() {
+			<PartnerCommunities<T>>::remove(other, cid);
+		}
+		#[allow(deprecated)]
+		<WaitlistAutoRegistration<T>>::remove_prefix(cid, None);
 
 		<encointer_communities::Pallet<T>>::remove_community(cid);
 	}
@@ -1536,6 +2997,7 @@ impl<T: Config> Pallet<T> {
 			RandomNumberGenerator::<T::Hashing>::new(T::RandomnessSource::random_seed().0);
 
 		for cid in cids.iter() {
+			Self::prioritize_waitlisted_participants((*cid, cindex));
 			if let Err(e) =
 				Self::generate_meetup_assignment_params((*cid, cindex), &mut random_source)
 			{
@@ -1544,9 +3006,250 @@ impl<T: Config> Pallet<T> {
 					"Could not generate meetup assignment params for cid: {:?}. {:?}", cid, e
 				);
 			}
+			Self::report_unassigned_participants((*cid, cindex));
+			Self::waitlist_unassigned_participants((*cid, cindex));
+		}
+	}
+
+	fn report_unassigned_participants(community_ceremony: CommunityCeremony) {
+		let registered = Self::registered_counts(community_ceremony);
+		let assigned = Self::assignment_counts(community_ceremony);
+		let unassigned = AssignmentCount {
+			bootstrappers: registered.bootstrappers.saturating_sub(assigned.bootstrappers),
+			reputables: registered.reputables.saturating_sub(assigned.reputables),
+			endorsees: registered.endorsees.saturating_sub(assigned.endorsees),
+			newbies: registered.newbies.saturating_sub(assigned.newbies),
+		};
+		if unassigned.get_number_of_participants() == 0 {
+			return
+		}
+		warn!(
+			target: LOG,
+			"{} registered participants of cid {:?} have not been assigned to a meetup",
+			unassigned.get_number_of_participants(),
+			community_ceremony.0
+		);
+		Self::deposit_event(Event::ParticipantsNotAssigned {
+			cid: community_ceremony.0,
+			cindex: community_ceremony.1,
+			unassigned,
+		});
+	}
+
+	/// Moves the participants who have been on the waitlist of the previous ceremony to the front
+	/// of their registry, such that they are assigned first.
+	fn prioritize_waitlisted_participants(community_ceremony: CommunityCeremony) {
+		let previous = (community_ceremony.0, community_ceremony.1.saturating_sub(1));
+		let waitlist: BTreeSet<T::AccountId> = Self::waitlist(previous)
+			.into_iter()
+			.map(|(participant, _)| participant)
+			.collect();
+		if waitlist.is_empty() {
+			return
+		}
+		let prioritized = |participants: Vec<T::AccountId>| -> Vec<T::AccountId> {
+			let (mut waitlisted, others): (Vec<_>, Vec<_>) =
+				participants.into_iter().partition(|p| waitlist.contains(p));
+			waitlisted.extend(others);
+			waitlisted
+		};
+		storage_helper::reorder_registry::<ReputableIndex<T>, ReputableRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&prioritized(storage_helper::registered_participants::<
+				ReputableRegistry<T>,
+				ReputableCount<T>,
+				T::AccountId,
+			>(community_ceremony)),
+		);
+		storage_helper::reorder_registry::<EndorseeIndex<T>, EndorseeRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&prioritized(storage_helper::registered_participants::<
+				EndorseeRegistry<T>,
+				EndorseeCount<T>,
+				T::AccountId,
+			>(community_ceremony)),
+		);
+		storage_helper::reorder_registry::<NewbieIndex<T>, NewbieRegistry<T>, T::AccountId>(
+			community_ceremony,
+			&prioritized(storage_helper::registered_participants::<
+				NewbieRegistry<T>,
+				NewbieCount<T>,
+				T::AccountId,
+			>(community_ceremony)),
+		);
+	}
+
+	/// Puts the reputables, endorsees and newbies who have not been assigned to a meetup on the
+	/// waitlist, until it is full.
+	fn waitlist_unassigned_participants(community_ceremony: CommunityCeremony) {
+		let registered = Self::registered_counts(community_ceremony);
+		let assigned = Self::assignment_counts(community_ceremony);
+
+		// registries are only read as far as needed to fill the waitlist
+		let reputables = (assigned.reputables < registered.reputables)
+			.then_some(1..=registered.reputables)
+			.into_iter()
+			.flatten()
+			.filter_map(|i| <ReputableRegistry<T>>::get(community_ceremony, i))
+			.map(|p| (p, ParticipantType::Reputable));
+		let endorsees = (assigned.endorsees < registered.endorsees)
+			.then_some(1..=registered.endorsees)
+			.into_iter()
+			.flatten()
+			.filter_map(|i| <EndorseeRegistry<T>>::get(community_ceremony, i))
+			.map(|p| (p, ParticipantType::Endorsee));
+		let newbies = (assigned.newbies < registered.newbies)
+			.then_some(1..=registered.newbies)
+			.into_iter()
+			.flatten()
+			.filter_map(|i| <NewbieRegistry<T>>::get(community_ceremony, i))
+			.map(|p| (p, ParticipantType::Newbie));
+
+		let mut waitlist = Self::waitlist(community_ceremony);
+		for (participant, participant_type) in reputables
+			.chain(endorsees)
+			.chain(newbies)
+			.filter(|(p, _)| Self::get_meetup_index(community_ceremony, p).is_none())
+		{
+			if waitlist.try_push((participant.clone(), participant_type)).is_err() {
+				warn!(
+					target: LOG,
+					"waitlist of {:?} is full. remaining unassigned participants are not waitlisted",
+					community_ceremony
+				);
+				break
+			}
+			Self::deposit_event(Event::ParticipantWaitlisted {
+				cid: community_ceremony.0,
+				cindex: community_ceremony.1,
+				participant_type,
+				account: participant,
+			});
+		}
+		<Waitlist<T>>::insert(community_ceremony, waitlist);
+	}
+
+	/// Registers the participants on the waitlist of the previous ceremony who have opted in
+	/// during REGISTERING, starting at the `WaitlistRegistrationCursor`, for as long as the
+	/// weight budget allows.
+	pub fn register_waitlisted_participants(weight_budget: Weight) -> Weight {
+		let registration_weight = <T as Config>::WeightInfo::register_participant();
+		let mut used_weight = T::DbWeight::get().reads_writes(3, 1);
+		if used_weight.any_gt(weight_budget) {
+			return Weight::zero()
+		}
+
+		let (mut cid, cindex, mut position) = match Self::waitlist_registration_cursor() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		if <encointer_scheduler::Pallet<T>>::current_phase() != CeremonyPhaseType::Registering {
+			<WaitlistRegistrationCursor<T>>::kill();
+			return used_weight
+		}
+
+		let cids = <encointer_communities::Pallet<T>>::community_identifiers();
+		let mut waitlist = Self::waitlist((cid, cindex.saturating_sub(1)));
+		loop {
+			if used_weight.saturating_add(registration_weight).any_gt(weight_budget) {
+				break
+			}
+			let (participant, participant_type) = match waitlist.get(position as usize) {
+				Some(entry) => entry.clone(),
+				None => {
+					used_weight.saturating_accrue(T::DbWeight::get().reads(1));
+					match cids.iter().position(|c| *c == cid).and_then(|i| cids.get(i + 1)) {
+						Some(next_cid) => {
+							cid = *next_cid;
+							position = 0;
+							waitlist = Self::waitlist((cid, cindex.saturating_sub(1)));
+							continue
+						},
+						None => {
+							debug!(
+								target: LOG,
+								"all waitlisted participants of ceremony {} have been processed",
+								cindex.saturating_sub(1)
+							);
+							<WaitlistRegistrationCursor<T>>::kill();
+							return used_weight
+						},
+					}
+				},
+			};
+
+			used_weight.saturating_accrue(registration_weight);
+			Self::register_waitlisted_participant(cid, cindex, participant, participant_type);
+			position = position.saturating_add(1);
+		}
+
+		<WaitlistRegistrationCursor<T>>::put((cid, cindex, position));
+		used_weight
+	}
+
+	/// Registers a participant of the previous ceremony's waitlist if they have opted in. They
+	/// keep their participant type if they are still eligible for it.
+	fn register_waitlisted_participant(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		participant: T::AccountId,
+		participant_type: ParticipantType,
+	) {
+		if !Self::waitlist_auto_registration(cid, &participant) ||
+			Self::is_registered(cid, cindex, &participant)
+		{
+			return
+		}
+		let result = with_storage_layer(|| {
+			let linked_reputation =
+				Self::waitlisted_linked_reputation(cid, cindex, &participant, participant_type);
+			if participant_type == ParticipantType::Endorsee {
+				<Endorsees<T>>::insert((cid, cindex), &participant, ());
+			}
+			let registered_type = Self::register(
+				cid,
+				cindex,
+				&participant,
+				linked_reputation.as_ref().map(|((reputation_cid, _), _)| *reputation_cid),
+			)?;
+			if let Some(linked_reputation) = linked_reputation {
+				<LinkedReputations<T>>::insert((cid, cindex), &participant, linked_reputation);
+			}
+			if registered_type == ParticipantType::Reputable {
+				<ParticipantReputation<T>>::insert(
+					(cid, cindex),
+					&participant,
+					Reputation::UnverifiedReputable,
+				);
+			}
+			Ok::<_, DispatchError>(registered_type)
+		});
+		match result {
+			Ok(registered_type) =>
+				Self::deposit_event(Event::ParticipantRegistered(cid, registered_type, participant)),
+			Err(e) => debug!(
+				target: LOG,
+				"could not register waitlisted {:?} for cid {:?}: {:?}", participant, cid, e
+			),
 		}
 	}
 
+	/// The reputation a waitlisted reputable has linked for the missed ceremony. It still counts
+	/// as long as it is within the reputation lifetime.
+	fn waitlisted_linked_reputation(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		participant: &T::AccountId,
+		participant_type: ParticipantType,
+	) -> Option<(CommunityCeremony, T::AccountId)> {
+		(participant_type == ParticipantType::Reputable && !Self::is_penalized(participant, &cid))
+			.then(|| Self::linked_reputations((cid, cindex.saturating_sub(1)), participant))
+			.flatten()
+			.filter(|((_, reputation_cindex), _)| {
+				*reputation_cindex >= cindex.saturating_sub(Self::reputation_lifetime())
+			})
+	}
+
 	fn get_participant_type(
 		community_ceremony: CommunityCeremony,
 		participant: &T::AccountId,
@@ -1570,25 +3273,64 @@ impl<T: Config> Pallet<T> {
 		community_ceremony: CommunityCeremony,
 		participant: &T::AccountId,
 	) -> Option<MeetupIndexType> {
-		let meetup_count = Self::meetup_count(community_ceremony);
-		let assignment_count = Self::assignment_counts(community_ceremony);
-
-		let assignment = Self::assignments(community_ceremony);
-
 		let participant_type = Self::get_participant_type(community_ceremony, participant)?;
+		let registry_index = match participant_type {
+			ParticipantType::Bootstrapper =>
+				Self::bootstrapper_index(community_ceremony, participant),
+			ParticipantType::Reputable => Self::reputable_index(community_ceremony, participant),
+			ParticipantType::Endorsee => Self::endorsee_index(community_ceremony, participant),
+			ParticipantType::Newbie => Self::newbie_index(community_ceremony, participant),
+		}
+		.checked_sub(1)?;
+
+		let num_regions = Self::regional_assignment_count(community_ceremony);
+		if num_regions == 0 {
+			return Self::assigned_meetup_index(
+				participant_type,
+				registry_index,
+				Self::assignments(community_ceremony),
+				Self::assignment_counts(community_ceremony),
+				Self::meetup_count(community_ceremony),
+			)
+		}
 
+		(0..num_regions)
+			.filter_map(|i| Self::regional_assignments(community_ceremony, i))
+			.find_map(|regional_assignment| {
+				let offset = match participant_type {
+					ParticipantType::Bootstrapper => regional_assignment.offsets.bootstrappers,
+					ParticipantType::Reputable => regional_assignment.offsets.reputables,
+					ParticipantType::Endorsee => regional_assignment.offsets.endorsees,
+					ParticipantType::Newbie => regional_assignment.offsets.newbies,
+				};
+				let meetup_index = Self::assigned_meetup_index(
+					participant_type,
+					registry_index.checked_sub(offset)?,
+					regional_assignment.params,
+					regional_assignment.assigned,
+					regional_assignment.meetup_count,
+				)?;
+				Some(meetup_index + regional_assignment.meetup_offset)
+			})
+	}
+
+	/// Meetup of the participant with the 0-based `participant_index` within the registry of
+	/// its `participant_type`, if it has been assigned.
+	fn assigned_meetup_index(
+		participant_type: ParticipantType,
+		participant_index: ParticipantIndexType,
+		assignment: Assignment,
+		assignment_count: AssignmentCount,
+		meetup_count: MeetupIndexType,
+	) -> Option<MeetupIndexType> {
 		let (participant_index, assignment_params) = match participant_type {
-			ParticipantType::Bootstrapper => {
-				let participant_index =
-					Self::bootstrapper_index(community_ceremony, participant) - 1;
+			ParticipantType::Bootstrapper =>
 				if participant_index < assignment_count.bootstrappers {
 					(participant_index, assignment.bootstrappers_reputables)
 				} else {
 					return None
-				}
-			},
-			ParticipantType::Reputable => {
-				let participant_index = Self::reputable_index(community_ceremony, participant) - 1;
+				},
+			ParticipantType::Reputable =>
 				if participant_index < assignment_count.reputables {
 					(
 						participant_index + assignment_count.bootstrappers,
@@ -1596,36 +3338,42 @@ impl<T: Config> Pallet<T> {
 					)
 				} else {
 					return None
-				}
-			},
+				},
 
-			ParticipantType::Endorsee => {
-				let participant_index = Self::endorsee_index(community_ceremony, participant) - 1;
+			ParticipantType::Endorsee =>
 				if participant_index < assignment_count.endorsees {
 					(participant_index, assignment.endorsees)
 				} else {
 					return None
-				}
-			},
+				},
 
-			ParticipantType::Newbie => {
-				let participant_index = Self::newbie_index(community_ceremony, participant) - 1;
+			ParticipantType::Newbie =>
 				if participant_index < assignment_count.newbies {
 					(participant_index, assignment.newbies)
 				} else {
 					return None
-				}
-			},
+				},
 		};
 
 		meetup_index(participant_index, assignment_params, meetup_count)
 	}
 
+	/// The regional assignment containing the meetup with `meetup_index`
+	fn regional_assignment_of_meetup(
+		community_ceremony: CommunityCeremony,
+		meetup_index: MeetupIndexType,
+	) -> Option<RegionalAssignment> {
+		(0..Self::regional_assignment_count(community_ceremony))
+			.filter_map(|i| Self::regional_assignments(community_ceremony, i))
+			.find(|r| {
+				meetup_index > r.meetup_offset && meetup_index <= r.meetup_offset + r.meetup_count
+			})
+	}
+
 	fn get_meetup_participants(
 		community_ceremony: CommunityCeremony,
-		mut meetup_index: MeetupIndexType,
+		meetup_index: MeetupIndexType,
 	) -> Result<Vec<T::AccountId>, Error<T>> {
-		let mut result: Vec<T::AccountId> = vec![];
 		let meetup_count = Self::meetup_count(community_ceremony);
 
 		if meetup_index > meetup_count || meetup_index < 1 {
@@ -1636,12 +3384,43 @@ impl<T: Config> Pallet<T> {
 			return Err(<Error<T>>::InvalidMeetupIndex)
 		}
 
-		//safe; meetup index conversion from 1 based to 0 based
-		meetup_index -= 1;
+		if Self::regional_assignment_count(community_ceremony) == 0 {
+			//safe; meetup index conversion from 1 based to 0 based
+			return Self::get_assigned_participants(
+				community_ceremony,
+				meetup_index - 1,
+				meetup_count,
+				Self::assignments(community_ceremony),
+				Self::assignment_counts(community_ceremony),
+				AssignmentCount::default(),
+			)
+		}
 
-		let params = Self::assignments(community_ceremony);
+		let regional_assignment =
+			Self::regional_assignment_of_meetup(community_ceremony, meetup_index)
+				.ok_or(<Error<T>>::GetMeetupParticipantsError)?;
+		//safe; meetup index conversion from 1 based to 0 based within the region
+		Self::get_assigned_participants(
+			community_ceremony,
+			meetup_index - regional_assignment.meetup_offset - 1,
+			regional_assignment.meetup_count,
+			regional_assignment.params,
+			regional_assignment.assigned,
+			regional_assignment.offsets,
+		)
+	}
 
-		let assigned = Self::assignment_counts(community_ceremony);
+	/// Participants of the 0-based `meetup_index` among `meetup_count` meetups, where the
+	/// participants of each type are found in their registry from the given `offsets` onwards.
+	fn get_assigned_participants(
+		community_ceremony: CommunityCeremony,
+		meetup_index: MeetupIndexType,
+		meetup_count: MeetupIndexType,
+		params: Assignment,
+		assigned: AssignmentCount,
+		offsets: AssignmentCount,
+	) -> Result<Vec<T::AccountId>, Error<T>> {
+		let mut result: Vec<T::AccountId> = vec![];
 
 		let bootstrappers_reputables = assignment_fn_inverse(
 			meetup_index,
@@ -1653,7 +3432,8 @@ impl<T: Config> Pallet<T> {
 		for p in bootstrappers_reputables {
 			if p < assigned.bootstrappers {
 				//safe; small number per meetup
-				match Self::bootstrapper_registry(community_ceremony, p + 1) {
+				match Self::bootstrapper_registry(community_ceremony, offsets.bootstrappers + p + 1)
+				{
 					Some(bs) => result.push(bs),
 					None => error!(
 						target: LOG,
@@ -1662,7 +3442,10 @@ impl<T: Config> Pallet<T> {
 				}
 			} else if p < assigned.bootstrappers + assigned.reputables {
 				//safe; small number per meetup
-				match Self::reputable_registry(community_ceremony, p - assigned.bootstrappers + 1) {
+				match Self::reputable_registry(
+					community_ceremony,
+					offsets.reputables + p - assigned.bootstrappers + 1,
+				) {
 					Some(r) => result.push(r),
 					None => error!(
 						target: LOG,
@@ -1678,7 +3461,7 @@ impl<T: Config> Pallet<T> {
 		for p in endorsees {
 			if p < assigned.endorsees {
 				//safe; small number per meetup
-				match Self::endorsee_registry(community_ceremony, p + 1) {
+				match Self::endorsee_registry(community_ceremony, offsets.endorsees + p + 1) {
 					Some(e) => result.push(e),
 					None => error!(
 						target: LOG,
@@ -1694,7 +3477,7 @@ impl<T: Config> Pallet<T> {
 		for p in newbies {
 			if p < assigned.newbies {
 				//safe; small number per meetup
-				match Self::newbie_registry(community_ceremony, p + 1) {
+				match Self::newbie_registry(community_ceremony, offsets.newbies + p + 1) {
 					Some(n) => result.push(n),
 					None => error!(
 						target: LOG,
@@ -1707,6 +3490,56 @@ impl<T: Config> Pallet<T> {
 		Ok(result)
 	}
 
+	fn do_endorse_and_register(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		endorser: &T::AccountId,
+		consent: &RegistrationConsentOf<T>,
+	) -> DispatchResult {
+		ensure!(
+			&consent.endorser == endorser &&
+				consent.community_identifier == cid &&
+				consent.ceremony_index == cindex,
+			Error::<T>::RegistrationConsentMismatch
+		);
+		ensure!(
+			consent.newbie_signature.verify(&consent.payload_encoded()[..], &consent.newbie),
+			Error::<T>::BadRegistrationConsentSignature
+		);
+		let newbie = &consent.newbie;
+
+		// newbies who have registered already are upgraded like in `endorse_newcomer`
+		match Self::get_participant_type((cid, cindex), newbie) {
+			Some(ParticipantType::Newbie) =>
+				Self::remove_participant_from_registry(cid, cindex, newbie)?,
+			Some(_) => return Err(Error::<T>::ParticipantAlreadyRegistered.into()),
+			None => (),
+		}
+		ensure!(Self::is_endorsed(newbie, &(cid, cindex)).is_none(), Error::<T>::AlreadyEndorsed);
+		// bootstrappers are registered as such, which would waste the ticket
+		ensure!(
+			!<encointer_communities::Pallet<T>>::bootstrappers(cid).contains(newbie),
+			Error::<T>::CannotEndorseBootstrapper
+		);
+
+		Self::burn_newbie_tickets(cid, cindex, endorser)?;
+
+		<Endorsees<T>>::insert((cid, cindex), newbie, ());
+		let new_endorsee_count = Self::endorsee_count((cid, cindex))
+			.checked_add(1)
+			.ok_or(<Error<T>>::RegistryOverflow)?;
+		<EndorseesCount<T>>::insert((cid, cindex), new_endorsee_count);
+		let participant_type = Self::register(cid, cindex, newbie, None)?;
+
+		// invalidate reputation cache
+		sp_io::offchain_index::set(&reputation_cache_dirty_key(newbie), &true.encode());
+
+		debug!(target: LOG, "{:?} endorsed and registered newbie: {:?}", endorser, newbie);
+		Self::deposit_event(Event::EndorsedParticipant(cid, endorser.clone(), newbie.clone()));
+		Self::deposit_event(Event::ParticipantRegistered(cid, participant_type, newbie.clone()));
+		Ok(())
+	}
+
 	fn verify_attendee_signature(
 		proof: ProofOfAttendance<T::Signature, T::AccountId>,
 	) -> DispatchResult {
@@ -1723,6 +3556,20 @@ impl<T: Config> Pallet<T> {
 		cc: CommunityCeremony,
 		meetup_idx: MeetupIndexType,
 	) -> Option<Location> {
+		if let Some(location) = Self::rescheduled_meetup_locations(cc, meetup_idx) {
+			return Some(location)
+		}
+		if Self::regional_assignment_count(cc) > 0 {
+			let regional_assignment = Self::regional_assignment_of_meetup(cc, meetup_idx)?;
+			let locations =
+				<encointer_communities::Pallet<T>>::locations(cc.0, &regional_assignment.region);
+			return meetup_location(
+				meetup_idx - regional_assignment.meetup_offset,
+				locations.into_inner(),
+				regional_assignment.params.locations,
+			)
+		}
+
 		let locations = <encointer_communities::Pallet<T>>::get_locations(&cc.0);
 		let assignment_params = Self::assignments(cc).locations;
 
@@ -1748,6 +3595,54 @@ impl<T: Config> Pallet<T> {
 		))
 	}
 
+	fn is_location_plausible(location: Location, meetup_location: Location) -> bool {
+		<encointer_communities::Pallet<T>>::haversine_distance(&location, &meetup_location) <=
+			Self::location_tolerance()
+	}
+
+	fn is_time_plausible(timestamp: T::Moment, meetup_time: T::Moment) -> bool {
+		let deviation =
+			if timestamp > meetup_time { timestamp - meetup_time } else { meetup_time - timestamp };
+		deviation <= Self::time_tolerance()
+	}
+
+	/// Meetups of `LoCoFlex` communities take place at the time chosen by their participants.
+	/// All other meetups, or flex meetups without a chosen time, fall back to `get_meetup_time`.
+	pub(crate) fn get_community_meetup_time(
+		cc: CommunityCeremony,
+		meetup_idx: MeetupIndexType,
+		location: Location,
+	) -> Option<T::Moment> {
+		if Self::community_rules(&cc.0) == CommunityRules::LoCoFlex {
+			if let Some(meetup_time) = Self::meetup_time_slot(cc, meetup_idx) {
+				return Some(meetup_time)
+			}
+		}
+		Self::get_meetup_time(location)
+	}
+
+	/// Start and end of the attesting phase of the current ceremony cycle. During REGISTERING
+	/// this is the attesting phase which has just ended.
+	pub(crate) fn attesting_phase_window() -> (T::Moment, T::Moment) {
+		let next = <encointer_scheduler::Pallet<T>>::next_phase_timestamp();
+		let attesting =
+			<encointer_scheduler::Pallet<T>>::phase_durations(CeremonyPhaseType::Attesting);
+		match <encointer_scheduler::Pallet<T>>::current_phase() {
+			CeremonyPhaseType::Registering => {
+				let end = next.saturating_sub(<encointer_scheduler::Pallet<T>>::phase_durations(
+					CeremonyPhaseType::Registering,
+				));
+				(end.saturating_sub(attesting), end)
+			},
+			CeremonyPhaseType::Assigning => (next, next.saturating_add(attesting)),
+			CeremonyPhaseType::Attesting => (next.saturating_sub(attesting), next),
+		}
+	}
+
+	pub fn community_rules(cid: &CommunityIdentifier) -> CommunityRules {
+		<encointer_communities::Pallet<T>>::community_metadata(cid).rules
+	}
+
 	/// Returns the community-specific nominal income if it is set. Otherwise returns the
 	/// the ceremony reward defined in the genesis config.
 	pub fn nominal_income(cid: &CommunityIdentifier) -> NominalIncome {
@@ -1762,7 +3657,12 @@ impl<T: Config> Pallet<T> {
 		meetup_participants: Vec<T::AccountId>,
 		participants_indices: Vec<usize>,
 	) -> Result<(), Error<T>> {
-		let reward = Self::nominal_income(&cid);
+		let nominal_income = Self::nominal_income(&cid);
+		let treasury_cut = nominal_income.saturating_mul(
+			BalanceType::from_num(T::CommunityTreasuryShare::get().deconstruct()) /
+				BalanceType::from_num(Permill::ACCURACY),
+		);
+		let reward = nominal_income.saturating_sub(treasury_cut);
 		let mut reputation_count = 0;
 		for i in &participants_indices {
 			let participant = &meetup_participants
@@ -1783,7 +3683,22 @@ impl<T: Config> Pallet<T> {
 		<ReputationCount<T>>::mutate((&cid, cindex), |b| *b += reputation_count); // safe, as reputation_count is limited by the number of locations available on earth
 		<GlobalReputationCount<T>>::mutate(cindex, |b| *b += reputation_count); // safe, as reputation_count is limited by the number of locations available on earth
 
+		if reputation_count > 0 && treasury_cut > 0 {
+			let treasury = <encointer_balances::Pallet<T>>::get_community_treasury_account(cid);
+			let amount = treasury_cut.saturating_mul(BalanceType::from_num(reputation_count));
+			if let Err(e) = <encointer_balances::Pallet<T>>::issue(cid, &treasury, amount) {
+				warn!(target: LOG, "failed to fund treasury of {:?}: {:?}", cid, e);
+			}
+		}
+
 		<IssuedRewards<T>>::insert((cid, cindex), meetup_idx, MeetupResult::Ok);
+		<CeremonyStatistics<T>>::mutate((cid, cindex), |stats| {
+			stats.meetup_results.record(MeetupResult::Ok);
+			stats.rewarded = stats.rewarded.saturating_add(reputation_count as u32);
+			stats.issued_rewards = stats
+				.issued_rewards
+				.saturating_add(reward.saturating_mul(BalanceType::from_num(reputation_count)));
+		});
 		info!(target: LOG, "issuing rewards completed");
 
 		Self::deposit_event(Event::RewardsIssued(
@@ -1794,6 +3709,229 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Evaluates the unclaimed meetups of the previous ceremony during REGISTERING, starting at
+	/// the `RewardIssuanceCursor`, for as long as the weight budget allows.
+	pub fn issue_pending_rewards(weight_budget: Weight) -> Weight {
+		let claim_weight = <T as Config>::WeightInfo::claim_rewards();
+		let mut used_weight = T::DbWeight::get().reads_writes(3, 1);
+		if used_weight.any_gt(weight_budget) {
+			return Weight::zero()
+		}
+
+		let (mut cid, cindex, mut meetup_index) = match Self::reward_issuance_cursor() {
+			Some(cursor) => cursor,
+			None => return T::DbWeight::get().reads(1),
+		};
+		if <encointer_scheduler::Pallet<T>>::current_phase() != CeremonyPhaseType::Registering {
+			<RewardIssuanceCursor<T>>::kill();
+			return used_weight
+		}
+
+		let cids = <encointer_communities::Pallet<T>>::community_identifiers();
+		loop {
+			if used_weight.saturating_add(claim_weight).any_gt(weight_budget) {
+				break
+			}
+			if meetup_index > Self::meetup_count((cid, cindex)) {
+				used_weight.saturating_accrue(T::DbWeight::get().reads(1));
+				match cids.iter().position(|c| *c == cid).and_then(|i| cids.get(i + 1)) {
+					Some(next_cid) => {
+						cid = *next_cid;
+						meetup_index = 1;
+						continue
+					},
+					None => {
+						debug!(target: LOG, "all meetups of ceremony {} have been evaluated", cindex);
+						<RewardIssuanceCursor<T>>::kill();
+						return used_weight
+					},
+				}
+			}
+
+			used_weight.saturating_accrue(claim_weight);
+			if let Err(e) = with_storage_layer(|| {
+				Self::do_claim_rewards(cid, cindex, meetup_index, CeremonyPhaseType::Registering)
+			}) {
+				trace!(
+					target: LOG,
+					"not evaluating meetup {:?} for cid {:?}: {:?}",
+					meetup_index,
+					cid,
+					e.error
+				);
+			}
+			meetup_index = meetup_index.saturating_add(1);
+		}
+
+		<RewardIssuanceCursor<T>>::put((cid, cindex, meetup_index));
+		used_weight
+	}
+
+	/// Validates a meetup and issues rewards to its legit participants. During REGISTERING, a
+	/// meetup which fails validation is marked as evaluated, such that it is never evaluated again.
+	fn do_claim_rewards(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		meetup_index: MeetupIndexType,
+		current_phase: CeremonyPhaseType,
+	) -> DispatchResultWithPostInfo {
+		if <IssuedRewards<T>>::contains_key((cid, cindex), meetup_index) {
+			return Err(<Error<T>>::RewardsAlreadyIssued.into())
+		}
+		if <CancelledMeetups<T>>::contains_key((cid, cindex), meetup_index) {
+			return Err(<Error<T>>::MeetupHasBeenCancelled.into())
+		}
+
+		//gather all data
+		let meetup_participants = Self::get_meetup_participants((cid, cindex), meetup_index)?;
+		let community_rules = Self::community_rules(&cid);
+		let (participant_votes, participant_attestations) = match community_rules {
+			CommunityRules::BeeDance =>
+				Self::gather_bee_dance_validation_data(meetup_participants.clone()),
+			_ => Self::gather_meetup_validation_data(cid, cindex, meetup_participants.clone()),
+		};
+
+		// initialize an array of local participant indices that are eligible for the reward
+		// indices will be deleted in the following based on various rules
+		let mut participants_eligible_for_rewards: Vec<usize> =
+			(0..meetup_participants.len()).collect();
+
+		let policy = Self::meetup_validation_policy(cid);
+		let participant_judgements = match get_participant_judgements(
+			&participants_eligible_for_rewards,
+			&participant_votes,
+			&participant_attestations,
+			|i: usize| policy.attestation_threshold(i),
+			policy.vote_majority,
+		) {
+			Ok(participant_judgements) => participant_judgements,
+			// handle errors
+			Err(err) => {
+				let (error, meetup_result) = match err {
+					MeetupValidationError::BallotEmpty => {
+						debug!(
+							target: LOG,
+							"ballot empty for meetup {:?}, cid: {:?}", meetup_index, cid
+						);
+						(
+							Err(<Error<T>>::VotesNotDependable.into()),
+							MeetupResult::VotesNotDependable,
+						)
+					},
+					MeetupValidationError::NoDependableVote => {
+						debug!(
+							target: LOG,
+							"ballot doesn't reach dependable majority for meetup {:?}, cid: {:?}",
+							meetup_index,
+							cid
+						);
+						(
+							Err(<Error<T>>::VotesNotDependable.into()),
+							MeetupResult::VotesNotDependable,
+						)
+					},
+					MeetupValidationError::IndexOutOfBounds => {
+						debug!(
+							target: LOG,
+							"index out of bounds for meetup {:?}, cid: {:?}", meetup_index, cid
+						);
+						(
+							Err(<Error<T>>::MeetupValidationIndexOutOfBounds.into()),
+							MeetupResult::MeetupValidationIndexOutOfBounds,
+						)
+					},
+				};
+				// only mark issuance as complete in registering phase
+				// because in attesting phase there could be a failing early payout attempt
+				if current_phase == CeremonyPhaseType::Registering {
+					info!(target: LOG, "marking issuance as completed for failed meetup.");
+
+					<IssuedRewards<T>>::insert((cid, cindex), meetup_index, meetup_result);
+					<CeremonyStatistics<T>>::mutate((cid, cindex), |stats| {
+						stats.meetup_results.record(meetup_result)
+					});
+					Self::deposit_event(Event::MeetupEvaluated(cid, meetup_index, meetup_result));
+					return Ok(Pays::No.into())
+				} else {
+					return error
+				}
+			},
+		};
+		if current_phase == CeremonyPhaseType::Attesting &&
+			!(policy.early_rewards_allowed && participant_judgements.early_rewards_possible)
+		{
+			debug!(
+				target: LOG,
+				"early rewards not possible for meetup {:?}, cid: {:?}", meetup_index, cid
+			);
+			return Err(<Error<T>>::EarlyRewardsNotPossible.into())
+		}
+		participants_eligible_for_rewards = participant_judgements.legit;
+
+		// penalize participants whose claims are contradicted by the majority of their meetup
+		let absentees: Vec<usize> = participant_judgements
+			.excluded
+			.iter()
+			.filter(|p| p.reason == ExclusionReason::TooFewIncomingAttestations)
+			.map(|p| p.index)
+			.collect();
+		// bee dance votes are derived from vouches, nobody has claimed them
+		let penalize_wrong_votes = community_rules != CommunityRules::BeeDance;
+		for (i, participant) in meetup_participants.iter().enumerate() {
+			let reason = if penalize_wrong_votes &&
+				participant_judgements
+					.excluded
+					.iter()
+					.any(|p| p.index == i && p.reason == ExclusionReason::WrongVote)
+			{
+				PenaltyReason::WrongVote
+			} else if participant_attestations
+				.get(i)
+				.map_or(false, |attestees| attestees.iter().any(|a| absentees.contains(a)))
+			{
+				PenaltyReason::AttestedAbsentee
+			} else {
+				continue
+			};
+			Self::penalize(cid, cindex, meetup_index, participant, reason);
+		}
+
+		// emit events
+		for p in participant_judgements.excluded {
+			let participant = meetup_participants
+				.get(p.index)
+				.ok_or(Error::<T>::MeetupValidationIndexOutOfBounds)?
+				.clone();
+			<CeremonyStatistics<T>>::mutate((cid, cindex), |stats| {
+				let count = match p.reason {
+					ExclusionReason::NoVote => &mut stats.exclusions.no_vote,
+					ExclusionReason::WrongVote => &mut stats.exclusions.wrong_vote,
+					ExclusionReason::TooFewIncomingAttestations =>
+						&mut stats.exclusions.too_few_incoming_attestations,
+					ExclusionReason::TooFewOutgoingAttestations =>
+						&mut stats.exclusions.too_few_outgoing_attestations,
+				};
+				count.saturating_inc();
+			});
+			Self::deposit_event(Event::NoReward {
+				cid,
+				cindex,
+				meetup_index,
+				account: participant,
+				reason: p.reason,
+			});
+		}
+
+		Self::issue_rewards(
+			cid,
+			cindex,
+			meetup_index,
+			meetup_participants,
+			participants_eligible_for_rewards,
+		)?;
+		Ok(Pays::No.into())
+	}
+
 	fn gather_meetup_validation_data(
 		cid: CommunityIdentifier,
 		cindex: CeremonyIndexType,
@@ -1823,6 +3961,39 @@ impl<T: Config> Pallet<T> {
 		(participant_votes, participant_attestations)
 	}
 
+	/// In `BeeDance` communities, a participant attests every fellow meetup participant for whom
+	/// they have vouched to have met live during the attesting phase. Their vote is the number of
+	/// participants they have met, including themselves.
+	fn gather_bee_dance_validation_data(
+		meetup_participants: Vec<T::AccountId>,
+	) -> (Vec<u32>, Vec<Vec<usize>>) {
+		let (start, end) = Self::attesting_phase_window();
+		let mut participant_votes: Vec<u32> = vec![];
+		let mut participant_attestations: Vec<Vec<usize>> = vec![];
+
+		for attester in meetup_participants.iter() {
+			let attestation_indices: Vec<usize> = meetup_participants
+				.iter()
+				.enumerate()
+				.filter(|(_, attestee)| *attestee != attester)
+				.filter(|(_, attestee)| {
+					T::Vouches::vouches(attestee, attester).iter().any(|v| {
+						v.vouch_kind.is_live_human_encounter() &&
+							start <= v.timestamp && v.timestamp < end
+					})
+				})
+				.map(|(i, _)| i)
+				.collect();
+
+			participant_votes.push(match attestation_indices.len() {
+				0 => 0,
+				n => n as u32 + 1,
+			});
+			participant_attestations.push(attestation_indices);
+		}
+		(participant_votes, participant_attestations)
+	}
+
 	fn gather_meetup_data(
 		cid: &CommunityIdentifier,
 		participant: &T::AccountId,
@@ -1831,13 +4002,18 @@ impl<T: Config> Pallet<T> {
 
 		let meetup_index = Self::get_meetup_index((*cid, cindex), participant)
 			.ok_or(Error::<T>::ParticipantIsNotRegistered)?;
+		ensure!(
+			!<CancelledMeetups<T>>::contains_key((*cid, cindex), meetup_index),
+			Error::<T>::MeetupHasBeenCancelled
+		);
 		let meetup_participants = Self::get_meetup_participants((*cid, cindex), meetup_index)?;
 
 		let meetup_location = Self::get_meetup_location((*cid, cindex), meetup_index)
 			.ok_or(Error::<T>::MeetupLocationNotFound)?;
 
 		let meetup_time =
-			Self::get_meetup_time(meetup_location).ok_or(Error::<T>::MeetupTimeCalculationError)?;
+			Self::get_community_meetup_time((*cid, cindex), meetup_index, meetup_location)
+				.ok_or(Error::<T>::MeetupTimeCalculationError)?;
 
 		Ok((cindex, meetup_index, meetup_participants, meetup_location, meetup_time))
 	}
@@ -1898,7 +4074,7 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn has_reputation(participant: &T::AccountId, cid: &CommunityIdentifier) -> bool {
+	pub fn has_reputation(participant: &T::AccountId, cid: &CommunityIdentifier) -> bool {
 		let reputation_lifetime = Self::reputation_lifetime();
 		let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
 		for i in 0..=reputation_lifetime {
@@ -1911,6 +4087,74 @@ impl<T: Config> Pallet<T> {
 		false
 	}
 
+	/// Like `has_reputation`, but also considers the reputation earned in partner communities.
+	pub fn has_trusted_reputation(participant: &T::AccountId, cid: &CommunityIdentifier) -> bool {
+		Self::has_reputation(participant, cid) ||
+			Self::partner_communities(cid)
+				.iter()
+				.any(|partner| Self::has_reputation(participant, partner))
+	}
+
+	/// Recency weighted reputation score of `participant` within the reputation lifetime.
+	///
+	/// Can serve as an alternative eligibility measure to `has_reputation`.
+	pub fn reputation_score(
+		participant: &T::AccountId,
+		cid: &CommunityIdentifier,
+	) -> ReputationScore {
+		let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+		let window = Self::reputation_lifetime().min(cindex.saturating_sub(1));
+		let history: Vec<bool> = (0..=window)
+			.map(|i| {
+				Self::participant_reputation((*cid, cindex.saturating_sub(i)), participant)
+					.is_verified()
+			})
+			.collect();
+		ReputationScore::from_history(&history)
+	}
+
+	/// Whether `participant` has been penalized at least `PenaltyThreshold` times within the
+	/// reputation lifetime.
+	pub fn is_penalized(participant: &T::AccountId, cid: &CommunityIdentifier) -> bool {
+		match Self::penalties(cid, participant) {
+			Some(record) =>
+				record.count >= T::PenaltyThreshold::get() &&
+					!record.has_lapsed(
+						<encointer_scheduler::Pallet<T>>::current_ceremony_index(),
+						Self::reputation_lifetime(),
+					),
+			None => false,
+		}
+	}
+
+	fn penalize(
+		cid: CommunityIdentifier,
+		cindex: CeremonyIndexType,
+		meetup_index: MeetupIndexType,
+		participant: &T::AccountId,
+		reason: PenaltyReason,
+	) {
+		let penalty_count = match Self::penalties(cid, participant) {
+			Some(record) if !record.has_lapsed(cindex, Self::reputation_lifetime()) =>
+				record.count.saturating_add(1),
+			_ => 1,
+		};
+		<Penalties<T>>::insert(
+			cid,
+			participant,
+			PenaltyRecord { count: penalty_count, last_penalized: cindex },
+		);
+		debug!(target: LOG, "penalized {:?} for {:?}", participant, reason);
+		Self::deposit_event(Event::ParticipantPenalized {
+			cid,
+			cindex,
+			meetup_index,
+			account: participant.clone(),
+			reason,
+			penalty_count,
+		});
+	}
+
 	fn is_endorsed(
 		participant: &T::AccountId,
 		cc: &CommunityCeremony,
@@ -1964,6 +4208,14 @@ impl<T: Config> OnCeremonyPhaseChange for Pallet<T> {
 			CeremonyPhaseType::Attesting => {},
 			CeremonyPhaseType::Registering => {
 				let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+				// evaluate the meetups nobody has claimed rewards for in on_idle
+				if let Some(cid) =
+					<encointer_communities::Pallet<T>>::community_identifiers().first()
+				{
+					<RewardIssuanceCursor<T>>::put((*cid, cindex.saturating_sub(1), 1));
+					// waitlisted participants who have opted in are registered in on_idle as well
+					<WaitlistRegistrationCursor<T>>::put((*cid, cindex, 0));
+				}
 				// Clean up with a time delay, such that participants can claim their UBI in the following cycle.
 				if cindex > Self::reputation_lifetime() {
 					Self::purge_registry(
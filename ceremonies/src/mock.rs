@@ -40,6 +40,7 @@ frame_support::construct_runtime!(
 		EncointerCeremonies: dut::{Pallet, Call, Storage, Config<T>, Event<T>},
 		EncointerCommunities: encointer_communities::{Pallet, Call, Storage, Event<T>},
 		EncointerBalances: encointer_balances::{Pallet, Call, Storage, Event<T>},
+		EncointerVouches: encointer_vouches::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -65,6 +66,19 @@ impl dut::Config for TestRuntime {
 	type MeetupNewbieLimitDivider = MeetupNewbieLimitDivider;
 	type WeightInfo = ();
 	type MaxAttestations = ConstU32<10>;
+	type CommunityTreasuryShare = CommunityTreasuryShare;
+	type Vouches = EncointerVouches;
+	type PenaltyThreshold = ConstU32<2>;
+	type MaxEndorsementBatchSize = ConstU32<10>;
+	type MaxClaimBatch = ConstU32<10>;
+	type MaxWaitlistSize = ConstU32<10>;
+	type MaxPartnerCommunities = ConstU32<10>;
+}
+
+impl encointer_vouches::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type MaxVouchesPerAttester = ConstU32<4>;
 }
 
 // boilerplate
@@ -4,6 +4,7 @@ use encointer_primitives::{
 	communities::CommunityIdentifier,
 	scheduler::CeremonyIndexType,
 };
+use sp_std::vec::Vec;
 pub fn remove_participant_from_registry<Index, Registry, Count, AccountId>(
 	cid: CommunityIdentifier,
 	cindex: CeremonyIndexType,
@@ -42,3 +43,53 @@ pub fn remove_participant_from_registry<Index, Registry, Count, AccountId>(
 		Count::insert((cid, cindex), participant_count.saturating_sub(1));
 	}
 }
+
+/// The registered participants in the order of their registry index
+pub fn registered_participants<Registry, Count, AccountId>(
+	community_ceremony: CommunityCeremony,
+) -> Vec<AccountId>
+where
+	Registry: frame_support::StorageDoubleMap<
+		CommunityCeremony,
+		ParticipantIndexType,
+		AccountId,
+		Query = Option<AccountId>,
+	>,
+	Count: frame_support::StorageMap<
+		CommunityCeremony,
+		ParticipantIndexType,
+		Query = ParticipantIndexType,
+	>,
+	AccountId: EncodeLike + Decode,
+{
+	(1..=Count::get(community_ceremony))
+		.filter_map(|i| Registry::get(community_ceremony, i))
+		.collect()
+}
+
+/// Reindexes a registry such that the participants are ordered like `participants`
+pub fn reorder_registry<Index, Registry, AccountId>(
+	community_ceremony: CommunityCeremony,
+	participants: &[AccountId],
+) where
+	Index: frame_support::StorageDoubleMap<
+		CommunityCeremony,
+		AccountId,
+		ParticipantIndexType,
+		Query = ParticipantIndexType,
+	>,
+	Registry: frame_support::StorageDoubleMap<
+		CommunityCeremony,
+		ParticipantIndexType,
+		AccountId,
+		Query = Option<AccountId>,
+	>,
+	AccountId: EncodeLike + Decode,
+{
+	for (i, participant) in participants.iter().enumerate() {
+		//safe; registry indices are 1-based
+		let participant_index = i as ParticipantIndexType + 1;
+		Registry::insert(community_ceremony, participant_index, participant);
+		Index::insert(community_ceremony, participant, participant_index);
+	}
+}
@@ -18,21 +18,28 @@ use super::*;
 use approx::assert_abs_diff_eq;
 use encointer_balances::Event as BalancesEvent;
 use encointer_primitives::{
-	communities::{CommunityIdentifier, Degree, Location, LossyInto},
+	communities::{
+		CommunityIdentifier, CommunityMetadata, CommunityRules, Degree, GeoHash, Location,
+		LossyInto,
+	},
 	scheduler::{CeremonyIndexType, CeremonyPhaseType},
+	vouches::{PresenceType, VouchKind, VouchQuality},
 };
 use frame_support::{
 	assert_err, assert_ok,
-	traits::{OnFinalize, OnInitialize},
+	dispatch::GetDispatchInfo,
+	traits::{OnFinalize, OnIdle, OnInitialize},
+	weights::{RuntimeDbWeight, Weight},
 };
 use itertools::Itertools;
 use mock::{
 	master, new_test_ext, EncointerBalances, EncointerCeremonies, EncointerCommunities,
-	EncointerScheduler, RuntimeOrigin, System, TestProofOfAttendance, TestRuntime, Timestamp,
+	EncointerScheduler, EncointerVouches, RuntimeOrigin, System, TestProofOfAttendance,
+	TestRuntime, Timestamp,
 };
 use rstest::*;
 use sp_core::{bounded_vec, sr25519, Pair, H256};
-use sp_runtime::{traits::BlakeTwo256, DispatchError};
+use sp_runtime::{traits::BlakeTwo256, DispatchError, Perbill, Permill};
 use std::{ops::Rem, str::FromStr};
 use test_utils::{
 	helpers::{
@@ -141,6 +148,23 @@ fn prove_attendance(
 	}
 }
 
+fn consent_to_registration(
+	endorser: AccountId,
+	cid: CommunityIdentifier,
+	cindex: CeremonyIndexType,
+	newbie: &sr25519::Pair,
+) -> RegistrationConsentOf<TestRuntime> {
+	let mut consent = RegistrationConsent {
+		newbie: account_id(newbie),
+		endorser,
+		ceremony_index: cindex,
+		community_identifier: cid,
+		newbie_signature: Signature::from(sr25519::Signature::from_raw([0u8; 64])),
+	};
+	consent.newbie_signature = Signature::from(newbie.sign(&consent.payload_encoded()));
+	consent
+}
+
 /// Wrapper for EncointerCeremonies::register_participant that reduces boilerplate code.
 fn register(
 	account: AccountId,
@@ -220,7 +244,62 @@ fn create_locations(n_locations: u32) -> Vec<Location> {
 		.collect()
 }
 
+/// Signed claim of attendance for the meetup the claimant is assigned to.
+/// Signs a claim of `claimant` to be submitted together with the claims of `bundle`
+fn signed_claim(
+	claimant: &sr25519::Pair,
+	bundle: &[&sr25519::Pair],
+	cid: CommunityIdentifier,
+	location: Location,
+	timestamp: Moment,
+	n_participants: u32,
+) -> ClaimOfAttendance<Signature, AccountId, Moment> {
+	let cindex = EncointerScheduler::current_ceremony_index();
+	let mindex =
+		EncointerCeremonies::get_meetup_index((cid, cindex), &account_id(claimant)).unwrap();
+	ClaimOfAttendance::new_unsigned(
+		account_id(claimant),
+		cindex,
+		cid,
+		mindex,
+		location,
+		timestamp,
+		n_participants,
+	)
+	.sign_for_bundle(claimant, &bundle.iter().map(|p| account_id(p)).collect::<Vec<_>>())
+}
+
+fn set_community_rules(cid: CommunityIdentifier, rules: CommunityRules) {
+	assert_ok!(EncointerCommunities::update_community_metadata(
+		RuntimeOrigin::signed(master()),
+		cid,
+		CommunityMetadata { rules, ..Default::default() }
+	));
+}
+
+/// Every participant vouches for every other participant with the given kind of vouch.
+fn vouch_for_each_other(participants: &[AccountId], vouch_kind: VouchKind) {
+	for attester in participants.iter() {
+		for attestee in participants.iter().filter(|p| *p != attester) {
+			assert_ok!(EncointerVouches::vouch_for(
+				RuntimeOrigin::signed(attester.clone()),
+				attestee.clone(),
+				vouch_kind,
+				VouchQuality::Unspecified
+			));
+		}
+	}
+}
+
 /// perform bootstrapping ceremony for test community with either the supplied bootstrappers or the default bootstrappers
+/// Records a rewarded meetup in `(cid, cindex)`, which keeps the community active
+fn fake_rewarded_meetup(cid: CommunityIdentifier, cindex: CeremonyIndexType) {
+	IssuedRewards::<TestRuntime>::insert((cid, cindex), 0, MeetupResult::Ok);
+	CeremonyStatistics::<TestRuntime>::mutate((cid, cindex), |stats| {
+		stats.meetup_results.record(MeetupResult::Ok)
+	});
+}
+
 fn perform_bootstrapping_ceremony(
 	custom_bootstrappers: Option<Vec<AccountId>>,
 	n_locations: u32,
@@ -646,6 +725,28 @@ fn claim_rewards_works() {
 			}
 			.into()
 		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::ParticipantPenalized {
+				cid,
+				cindex,
+				meetup_index: 1,
+				account: dave.clone(),
+				reason: PenaltyReason::WrongVote,
+				penalty_count: 1,
+			}
+			.into()
+		));
+
+		let stats = EncointerCeremonies::get_ceremony_stats(cid, cindex);
+		assert_eq!(stats.registered.bootstrappers, 6);
+		assert_eq!(stats.meetup_results.ok, 1);
+		assert_eq!(stats.exclusions.no_vote, 2);
+		assert_eq!(stats.exclusions.wrong_vote, 1);
+		assert_eq!(stats.rewarded, 3);
+		assert_eq!(
+			stats.issued_rewards,
+			EncointerBalances::balance(cid, &alice) * BalanceType::from_num(3)
+		);
 
 		// Claiming twice does not work for any of the meetup participants
 		for sender in vec![alice, bob, charlie, dave, ferdie].iter() {
@@ -661,6 +762,181 @@ fn claim_rewards_works() {
 	});
 }
 
+#[test]
+fn claim_rewards_penalizes_attesting_absentees() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let charlie = AccountKeyring::Charlie.to_account_id();
+		let dave = AccountKeyring::Dave.to_account_id();
+		let eve = AccountKeyring::Eve.to_account_id();
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
+		let cindex = EncointerScheduler::current_ceremony_index();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+		// Scenario:
+		//      ferdie doesn't show up but claims to have attended
+		//      dave attests ferdie nevertheless
+		let present = vec![alice.clone(), bob.clone(), charlie.clone(), dave.clone(), eve.clone()];
+		for attestor in present.iter() {
+			let mut attestees: Vec<AccountId> =
+				present.iter().filter(|a| *a != attestor).cloned().collect();
+			if *attestor == dave {
+				attestees.push(ferdie.clone());
+			}
+			attest_all(attestor.clone(), attestees, cid, 5);
+		}
+		attest_all(ferdie.clone(), vec![alice.clone(), dave.clone()], cid, 5);
+
+		run_to_next_phase();
+		// Registering
+		EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice.clone()), cid, None)
+			.unwrap();
+		assert!(event_deposited::<TestRuntime>(
+			Event::NoReward {
+				cid,
+				cindex,
+				meetup_index: 1,
+				account: ferdie.clone(),
+				reason: ExclusionReason::TooFewIncomingAttestations,
+			}
+			.into()
+		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::ParticipantPenalized {
+				cid,
+				cindex,
+				meetup_index: 1,
+				account: dave.clone(),
+				reason: PenaltyReason::AttestedAbsentee,
+				penalty_count: 1,
+			}
+			.into()
+		));
+		assert_eq!(
+			EncointerCeremonies::penalties(cid, &dave),
+			Some(PenaltyRecord { count: 1, last_penalized: cindex })
+		);
+		// dave still gets his reward, but ferdie's false claim is not penalized
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), &dave),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(EncointerCeremonies::penalties(cid, &alice), None);
+		assert_eq!(EncointerCeremonies::penalties(cid, &ferdie), None);
+		assert!(!EncointerCeremonies::is_penalized(&dave, &cid));
+	});
+}
+
+#[test]
+fn penalized_participants_are_downgraded() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let newbie = account_id(&add_population(1, 6)[0]);
+		assert_ok!(EncointerCeremonies::claim_rewards(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			None
+		));
+
+		Penalties::<TestRuntime>::insert(
+			cid,
+			&alice,
+			PenaltyRecord { count: 2, last_penalized: cindex - 1 },
+		);
+		// below the threshold
+		Penalties::<TestRuntime>::insert(
+			cid,
+			&bob,
+			PenaltyRecord { count: 1, last_penalized: cindex - 1 },
+		);
+		assert!(EncointerCeremonies::is_penalized(&alice, &cid));
+		assert!(!EncointerCeremonies::is_penalized(&bob, &cid));
+
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(alice.clone()),
+				cid,
+				newbie.clone()
+			),
+			Error::<TestRuntime>::NewbieTicketsWithheld
+		);
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(bob.clone()),
+			cid,
+			newbie
+		));
+
+		// alice is a bootstrapper, but registers as a newbie
+		assert_ok!(EncointerCeremonies::register_participant(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			Some(prove_attendance(alice.clone(), cid, cindex - 1, &AccountKeyring::Alice.pair()))
+		));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &alice),
+			Some(ParticipantType::Newbie)
+		);
+		// the proof of attendance has not been used up
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &alice),
+			Reputation::VerifiedUnlinked
+		);
+		assert_ok!(register(bob.clone(), cid, None));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &bob),
+			Some(ParticipantType::Bootstrapper)
+		);
+
+		// penalties lapse after the reputation lifetime
+		EncointerCeremonies::do_set_reputation_lifetime(1).unwrap();
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		assert!(!EncointerCeremonies::is_penalized(&alice, &cid));
+	});
+}
+
+#[test]
+fn issue_rewards_funds_community_treasury() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		CommunityTreasuryShare::set(Permill::from_percent(10));
+
+		assert_ok!(EncointerCeremonies::issue_rewards(
+			cid,
+			cindex,
+			1,
+			vec![alice.clone(), bob.clone()],
+			vec![0, 1]
+		));
+
+		let reward: f64 = EncointerCeremonies::nominal_income(&cid).lossy_into();
+		for participant in [&alice, &bob] {
+			let balance: f64 = EncointerBalances::balance(cid, participant).lossy_into();
+			assert_abs_diff_eq!(balance, reward * 0.9, epsilon = 1.0e-6);
+		}
+		let treasury_balance: f64 = EncointerBalances::balance(cid, &treasury).lossy_into();
+		assert_abs_diff_eq!(treasury_balance, reward * 0.2, epsilon = 1.0e-6);
+		// the treasury does not gain reputation
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 2);
+	});
+}
+
 #[test]
 fn claim_rewards_works_with_one_missing_attestation() {
 	new_test_ext().execute_with(|| {
@@ -976,6 +1252,9 @@ fn meetup_marked_as_completed_in_registration_when_claim_rewards_validation_erro
 		assert!(event_deposited::<TestRuntime>(
 			Event::MeetupEvaluated(cid, 1, MeetupResult::VotesNotDependable).into()
 		));
+		let stats = EncointerCeremonies::get_ceremony_stats(cid, cindex);
+		assert_eq!(stats.meetup_results.votes_not_dependable, 1);
+		assert_eq!(stats.rewarded, 0);
 	});
 }
 
@@ -1170,967 +1449,2933 @@ fn early_rewards_does_not_work_with_one_missing_submission_of_attestations() {
 }
 
 #[test]
-fn bootstrapping_works() {
+fn set_meetup_validation_policy_works() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountKeyring::Alice.pair();
-		let bob = AccountKeyring::Bob.pair();
-		let charlie = AccountKeyring::Charlie.pair();
-		let dave = AccountKeyring::Dave.pair();
-		let eve = AccountKeyring::Eve.pair();
-		let ferdie = AccountKeyring::Ferdie.pair();
-
-		EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(&alice)), cid, None)
-			.ok();
-		let cindex = EncointerScheduler::current_ceremony_index();
-
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&alice)),
-			Reputation::VerifiedUnlinked
-		);
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&bob)),
-			Reputation::VerifiedUnlinked
-		);
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let policy = MeetupValidationPolicy {
+			min_attestation_ratio: Some(Perbill::from_percent(50)),
+			vote_majority: VoteMajority::Unanimity,
+			early_rewards_allowed: false,
+		};
 		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&charlie)),
-			Reputation::VerifiedUnlinked
+			EncointerCeremonies::meetup_validation_policy(cid),
+			MeetupValidationPolicy::default()
 		);
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&dave)),
-			Reputation::VerifiedUnlinked
+
+		assert_dispatch_err(
+			EncointerCeremonies::set_meetup_validation_policy(
+				RuntimeOrigin::signed(AccountKeyring::Bob.into()),
+				cid,
+				policy,
+			),
+			DispatchError::BadOrigin,
 		);
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&eve)),
-			Reputation::VerifiedUnlinked
+		assert_err!(
+			EncointerCeremonies::set_meetup_validation_policy(
+				RuntimeOrigin::signed(master()),
+				CommunityIdentifier::default(),
+				policy,
+			),
+			Error::<TestRuntime>::InexistentCommunity
 		);
+
+		assert_ok!(EncointerCeremonies::set_meetup_validation_policy(
+			RuntimeOrigin::signed(master()),
+			cid,
+			policy,
+		));
+		assert_eq!(EncointerCeremonies::meetup_validation_policy(cid), policy);
 		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&ferdie)),
-			Reputation::VerifiedUnlinked
+			last_event::<TestRuntime>(),
+			Some(Event::MeetupValidationPolicyUpdated(cid, policy).into())
 		);
 	});
 }
 
 #[test]
-fn register_with_reputation_works() {
+fn early_rewards_are_denied_by_meetup_validation_policy() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-
-		// a non-bootstrapper
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
-		let zoran_new = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
-
-		// another non-bootstrapper
-		let yuri = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let all_participants: Vec<AccountId> = [
+			AccountKeyring::Alice,
+			AccountKeyring::Bob,
+			AccountKeyring::Charlie,
+			AccountKeyring::Dave,
+			AccountKeyring::Eve,
+			AccountKeyring::Ferdie,
+		]
+		.iter()
+		.map(|k| k.to_account_id())
+		.collect();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		assert_ok!(EncointerCeremonies::set_meetup_validation_policy(
+			RuntimeOrigin::signed(master()),
+			cid,
+			MeetupValidationPolicy { early_rewards_allowed: false, ..Default::default() },
+		));
 
-		let cindex = EncointerScheduler::current_ceremony_index();
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
 
-		// fake reputation registry for first ceremony
-		assert!(
-			EncointerBalances::issue(cid, &account_id(&zoran), NominalIncome::from_num(1)).is_ok()
-		);
-		EncointerCeremonies::fake_reputation(
-			(cid, cindex - 1),
-			&account_id(&zoran),
-			Reputation::VerifiedUnlinked,
-		);
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), account_id(&zoran)),
-			Reputation::VerifiedUnlinked
-		);
+		fully_attest_attendees(all_participants, cid, 6);
 
-		let cindex = EncointerScheduler::current_ceremony_index();
-		println!("cindex {cindex}");
-		// wrong sender of good proof fails
-		let proof = prove_attendance(account_id(&zoran_new), cid, cindex - 1, &zoran);
 		assert_err!(
-			register(account_id(&yuri), cid, Some(proof)),
-			Error::<TestRuntime>::WrongProofSubject
+			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice.clone()), cid, None),
+			Error::<TestRuntime>::EarlyRewardsNotPossible
 		);
 
-		// see if Zoran can register with his fresh key
-		// for the next ceremony claiming his former attendance
-		IssuedRewards::<TestRuntime>::insert((cid, cindex - 1), 0, MeetupResult::Ok);
-		let proof = prove_attendance(account_id(&zoran_new), cid, cindex - 1, &zoran);
-		assert_ok!(register(account_id(&zoran_new), cid, Some(proof)));
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex), account_id(&zoran_new)),
-			Reputation::UnverifiedReputable
-		);
-		assert_eq!(
-			EncointerCeremonies::participant_reputation((cid, cindex - 1), account_id(&zoran)),
-			Reputation::VerifiedLinked
-		);
-
-		// double signing (re-using reputation) fails
-		let proof_second = prove_attendance(account_id(&yuri), cid, cindex - 1, &zoran);
-		assert_err!(
-			register(account_id(&yuri), cid, Some(proof_second)),
-			Error::<TestRuntime>::AttendanceUnverifiedOrAlreadyUsed
-		);
+		run_to_next_phase();
+		// Registering
 
-		// signer without reputation fails
-		let proof = prove_attendance(account_id(&yuri), cid, cindex - 1, &yuri);
-		assert_err!(
-			register(account_id(&yuri), cid, Some(proof)),
-			Error::<TestRuntime>::AttendanceUnverifiedOrAlreadyUsed
-		);
+		assert_ok!(EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice), cid, None));
 	});
 }
 
 #[test]
-fn endorsement_by_bootstrapper_for_newbie_works_until_no_more_tickets() {
+fn min_attestation_ratio_of_meetup_validation_policy_is_applied() {
 	new_test_ext().execute_with(|| {
-		System::set_block_number(System::block_number() + 1); // this is needed to assert events
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountId::from(AccountKeyring::Alice);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let charlie = AccountKeyring::Charlie.to_account_id();
+		let dave = AccountKeyring::Dave.to_account_id();
+		let eve = AccountKeyring::Eve.to_account_id();
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		assert_ok!(EncointerCeremonies::set_meetup_validation_policy(
+			RuntimeOrigin::signed(master()),
+			cid,
+			MeetupValidationPolicy {
+				min_attestation_ratio: Some(Perbill::from_percent(50)),
+				..Default::default()
+			},
+		));
 
-		let endorsees = add_population(
-			(EncointerCeremonies::endorsement_tickets_per_bootstrapper() + 1) as usize,
-			6,
-		);
-		for i in 0..EncointerCeremonies::endorsement_tickets_per_bootstrapper() {
-			assert_ok!(EncointerCeremonies::endorse_newcomer(
-				RuntimeOrigin::signed(alice.clone()),
-				cid,
-				account_id(&endorsees[i as usize])
-			));
-			assert_eq!(
-				last_event::<TestRuntime>(),
-				Some(
-					Event::EndorsedParticipant(
-						cid,
-						alice.clone(),
-						account_id(&endorsees[i as usize])
-					)
-					.into()
-				)
-			);
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+
+		// everybody attests three of their five fellow participants
+		let all_participants = vec![alice.clone(), bob, charlie, dave, eve, ferdie];
+		for (i, attestor) in all_participants.iter().enumerate() {
+			let attestees: Vec<AccountId> =
+				(1..=3).map(|j| all_participants[(i + j) % 6].clone()).collect();
+			attest_all(attestor.clone(), attestees, cid, 6);
 		}
 
-		assert_err!(
-			EncointerCeremonies::endorse_newcomer(
-				RuntimeOrigin::signed(alice),
-				cid,
-				account_id(
-					&endorsees
-						[EncointerCeremonies::endorsement_tickets_per_bootstrapper() as usize]
-				),
-			),
-			Error::<TestRuntime>::NoMoreNewbieTickets,
-		);
+		run_to_next_phase();
+		// Registering
+
+		assert_ok!(EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice), cid, None));
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 6);
 	});
 }
 
 #[test]
-fn endorsing_newbie_for_next_ceremony_works_after_registering_phase() {
+fn attest_meetup_works() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
 		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
-		let alice = AccountId::from(AccountKeyring::Alice);
 		let cindex = EncointerScheduler::current_ceremony_index();
+		let pairs: Vec<sr25519::Pair> = [
+			AccountKeyring::Alice,
+			AccountKeyring::Bob,
+			AccountKeyring::Charlie,
+			AccountKeyring::Dave,
+			AccountKeyring::Eve,
+			AccountKeyring::Ferdie,
+		]
+		.iter()
+		.map(|k| k.pair())
+		.collect();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+
+		run_to_next_phase();
+		// Assigning
 		run_to_next_phase();
+		// Attesting
 
-		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Assigning);
-		// a newbie
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice.clone()),
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let time = correct_meetup_time(&cid, 1);
+		let bundle: Vec<_> = pairs.iter().collect();
+		let claims: Vec<_> =
+			pairs.iter().map(|p| signed_claim(p, &bundle, cid, location, time, 6)).collect();
+
+		// one participant submits the claims of everybody
+		assert_ok!(EncointerCeremonies::attest_meetup(
+			RuntimeOrigin::signed(account_id(&pairs[0])),
 			cid,
-			account_id(&zoran)
+			BoundedVec::try_from(claims).unwrap()
 		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex + 1), &account_id(&zoran)));
 
-		run_to_next_phase();
+		assert_eq!(EncointerCeremonies::attestation_count((cid, cindex)), 6);
+		for p in pairs.iter() {
+			assert_eq!(
+				EncointerCeremonies::meetup_participant_count_vote((cid, cindex), account_id(p)),
+				6
+			);
+		}
 
-		let bogdan = sr25519::Pair::from_seed_slice(&[99u8; 32]).unwrap();
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice),
+		assert_ok!(EncointerCeremonies::claim_rewards(
+			RuntimeOrigin::signed(account_id(&pairs[0])),
 			cid,
-			account_id(&bogdan)
+			None
 		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex + 1), &account_id(&bogdan)));
+		assert_eq!(last_event::<TestRuntime>(), Some(Event::RewardsIssued(cid, 1, 6).into()));
 	});
 }
 
 #[test]
-fn endorsing_newbie_twice_fails() {
+fn attest_meetup_ignores_invalid_claims() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountId::from(AccountKeyring::Alice);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
 		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountKeyring::Alice.pair();
+		let bob = AccountKeyring::Bob.pair();
+		let charlie = AccountKeyring::Charlie.pair();
+		let dave = AccountKeyring::Dave.pair();
+		let eve = AccountKeyring::Eve.pair();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		// a newbie
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice.clone()),
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let time = correct_meetup_time(&cid, 1);
+		let far_away = Location { lat: location.lat + Degree::from_num(1), lon: location.lon };
+
+		let bundle = [&alice, &bob, &charlie, &dave, &eve];
+		let mut forged_claim = signed_claim(&charlie, &bundle, cid, location, time, 6);
+		forged_claim.number_of_participants_confirmed = 3;
+
+		let claims = vec![
+			signed_claim(&alice, &bundle, cid, location, time, 6),
+			signed_claim(&bob, &bundle, cid, far_away, time, 6),
+			forged_claim,
+			signed_claim(&dave, &bundle, cid, location, time + TIME_TOLERANCE + 1, 6),
+			signed_claim(&eve, &bundle, cid, location, time - TIME_TOLERANCE, 6),
+		];
+		assert_ok!(EncointerCeremonies::attest_meetup(
+			RuntimeOrigin::signed(account_id(&alice)),
 			cid,
-			account_id(&zoran)
+			BoundedVec::try_from(claims).unwrap()
 		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
+
+		assert_eq!(EncointerCeremonies::attestation_count((cid, cindex)), 2);
+		for p in [&bob, &charlie, &dave] {
+			assert!(!AttestationIndex::<TestRuntime>::contains_key((cid, cindex), account_id(p)));
+		}
+		let alice_index = EncointerCeremonies::attestation_index((cid, cindex), account_id(&alice));
+		assert_eq!(
+			EncointerCeremonies::attestation_registry((cid, cindex), alice_index).unwrap(),
+			vec![account_id(&eve)]
+		);
+
+		// a single valid claim doesn't attest anybody
 		assert_err!(
-			EncointerCeremonies::endorse_newcomer(
-				RuntimeOrigin::signed(alice),
+			EncointerCeremonies::attest_meetup(
+				RuntimeOrigin::signed(account_id(&alice)),
 				cid,
-				account_id(&zoran),
+				bounded_vec![signed_claim(&bob, &[&bob], cid, location, time, 6)]
 			),
-			Error::<TestRuntime>::AlreadyEndorsed,
+			Error::<TestRuntime>::NoValidAttestations
 		);
 	});
 }
 
 #[test]
-fn endorsing_two_newbies_works() {
+fn attest_meetup_ignores_claims_of_altered_bundles() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountId::from(AccountKeyring::Alice);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
 		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountKeyring::Alice.pair();
+		let bob = AccountKeyring::Bob.pair();
+		let charlie = AccountKeyring::Charlie.pair();
+		let eve = AccountKeyring::Eve.pair();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		// a newbie
-		let yran = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice.clone()),
-			cid,
-			account_id(&zoran)
-		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice),
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let time = correct_meetup_time(&cid, 1);
+		let bundle = [&alice, &bob, &charlie];
+		let claims: Vec<_> = bundle
+			.iter()
+			.map(|p| signed_claim(p, &bundle, cid, location, time, 3))
+			.collect();
+
+		// eve's claim is added to the bundle after bob and charlie have signed it
+		let mut altered_claims = claims.clone();
+		altered_claims.push(signed_claim(
+			&eve,
+			&[&alice, &bob, &charlie, &eve],
 			cid,
-			account_id(&yran)
+			location,
+			time,
+			4,
 		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&yran)));
-	});
-}
+		assert_err!(
+			EncointerCeremonies::attest_meetup(
+				RuntimeOrigin::signed(account_id(&eve)),
+				cid,
+				BoundedVec::try_from(altered_claims).unwrap()
+			),
+			Error::<TestRuntime>::NoValidAttestations
+		);
 
-#[test]
-fn endorsement_survives_idle_cycle() {
-	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountId::from(AccountKeyring::Alice);
+		// charlie's claim is removed from the bundle
+		assert_err!(
+			EncointerCeremonies::attest_meetup(
+				RuntimeOrigin::signed(account_id(&alice)),
+				cid,
+				BoundedVec::try_from(claims[..2].to_vec()).unwrap()
+			),
+			Error::<TestRuntime>::NoValidAttestations
+		);
+		assert_eq!(EncointerCeremonies::attestation_count((cid, cindex)), 0);
 
-		// a newbie
-		let zoran = account_id(&sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap());
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice.clone()),
+		assert_ok!(EncointerCeremonies::attest_meetup(
+			RuntimeOrigin::signed(account_id(&alice)),
 			cid,
-			zoran.clone()
+			BoundedVec::try_from(claims).unwrap()
 		));
-		assert!(EncointerCeremonies::is_endorsed(&zoran, &(cid, 4)).is_some());
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
-		assert!(EncointerCeremonies::is_endorsed(&zoran, &(cid, 4)).is_some());
+		assert_eq!(EncointerCeremonies::attestation_count((cid, cindex)), 3);
 	});
 }
 
 #[test]
-fn endorsing_works_after_subject_has_already_registered() {
+fn attest_meetup_merges_later_bundles() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let alice = AccountId::from(AccountKeyring::Alice);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
 		let cindex = EncointerScheduler::current_ceremony_index();
+		let pairs: Vec<sr25519::Pair> = [
+			AccountKeyring::Alice,
+			AccountKeyring::Bob,
+			AccountKeyring::Charlie,
+			AccountKeyring::Dave,
+			AccountKeyring::Eve,
+			AccountKeyring::Ferdie,
+		]
+		.iter()
+		.map(|k| k.pair())
+		.collect();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		// a newbie
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
 
-		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
-		assert_ok!(register(yran.clone(), cid, None));
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let time = correct_meetup_time(&cid, 1);
+		let bundle: Vec<_> = pairs.iter().collect();
+		let claims: Vec<_> =
+			pairs.iter().map(|p| signed_claim(p, &bundle, cid, location, time, 6)).collect();
+		let attestations = |p: &sr25519::Pair| {
+			let index = EncointerCeremonies::attestation_index((cid, cindex), account_id(p));
+			EncointerCeremonies::attestation_registry((cid, cindex), index).unwrap().len()
+		};
 
-		assert!(NewbieIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+		// the sender has to submit their own claim
+		let partial_bundle = [&pairs[1], &pairs[2]];
+		assert_err!(
+			EncointerCeremonies::attest_meetup(
+				RuntimeOrigin::signed(account_id(&pairs[0])),
+				cid,
+				bounded_vec![
+					signed_claim(&pairs[1], &partial_bundle, cid, location, time, 2),
+					signed_claim(&pairs[2], &partial_bundle, cid, location, time, 2),
+				]
+			),
+			Error::<TestRuntime>::SenderIsNotAClaimant
+		);
 
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice),
+		assert_ok!(EncointerCeremonies::attest_meetup(
+			RuntimeOrigin::signed(account_id(&pairs[0])),
 			cid,
-			yran.clone()
+			BoundedVec::try_from(claims.clone()).unwrap()
 		));
+		for p in pairs.iter() {
+			assert_eq!(attestations(p), 5);
+		}
 
-		assert!(EndorseeIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
-		assert!(!NewbieIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+		// a later partial bundle doesn't reduce the attestations or change the votes
+		assert_ok!(EncointerCeremonies::attest_meetup(
+			RuntimeOrigin::signed(account_id(&pairs[1])),
+			cid,
+			bounded_vec![
+				signed_claim(&pairs[1], &partial_bundle, cid, location, time, 2),
+				signed_claim(&pairs[2], &partial_bundle, cid, location, time, 2),
+			]
+		));
+		assert_eq!(EncointerCeremonies::attestation_count((cid, cindex)), 6);
+		for p in pairs.iter() {
+			assert_eq!(attestations(p), 5);
+			assert_eq!(
+				EncointerCeremonies::meetup_participant_count_vote((cid, cindex), account_id(p)),
+				6
+			);
+		}
 	});
 }
 
 #[test]
-fn endorse_newbie_works_for_reputables() {
+fn choose_meetup_time_works_for_loco_flex() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let reputable = account_id(&sr25519::Pair::from_seed_slice(&[10u8; 32]).unwrap());
-
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		set_community_rules(cid, CommunityRules::LoCoFlex);
+		let alice = AccountKeyring::Alice.to_account_id();
 		let cindex = EncointerScheduler::current_ceremony_index();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		EncointerCeremonies::fake_reputation(
-			(cid, cindex - 1),
-			&reputable,
-			Reputation::VerifiedUnlinked,
-		);
+		run_to_next_phase();
+		// Assigning
 
-		// a newbie
-		let yran = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
-		let bob = sr25519::Pair::from_seed_slice(&[10u8; 32]).unwrap();
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(reputable.clone()),
-			cid,
-			account_id(&zoran)
-		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
-		assert_eq!(BurnedReputableNewbieTickets::<TestRuntime>::get((cid, cindex), &reputable), 1);
-		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::get(cid, &reputable), 0);
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(reputable.clone()),
+		let (start, _) = EncointerCeremonies::attesting_phase_window();
+		let meetup_time = start + ONE_DAY / 4;
+		assert_ok!(EncointerCeremonies::choose_meetup_time(
+			RuntimeOrigin::signed(alice.clone()),
 			cid,
-			account_id(&yran)
+			meetup_time
 		));
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&yran)));
-		assert_eq!(BurnedReputableNewbieTickets::<TestRuntime>::get((cid, cindex), &reputable), 2);
-		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::get(cid, &reputable), 0);
+		assert_eq!(EncointerCeremonies::meetup_time_slot((cid, cindex), 1), Some(meetup_time));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(Event::MeetupTimeChosen { cid, cindex, meetup_index: 1, meetup_time }.into())
+		);
 
+		// the time slot is locked once chosen
 		assert_err!(
-			EncointerCeremonies::endorse_newcomer(
-				RuntimeOrigin::signed(reputable),
+			EncointerCeremonies::choose_meetup_time(
+				RuntimeOrigin::signed(AccountKeyring::Bob.to_account_id()),
 				cid,
-				account_id(&bob)
+				start
 			),
-			Error::<TestRuntime>::NoMoreNewbieTickets,
+			Error::<TestRuntime>::MeetupTimeAlreadyChosen
 		);
+		assert_eq!(EncointerCeremonies::meetup_time_slot((cid, cindex), 1), Some(meetup_time));
+
+		run_to_next_phase();
+		// Attesting
+
+		let personal =
+			EncointerCeremonies::get_aggregated_account_data(cid, &alice).personal.unwrap();
+		assert_eq!(personal.meetup_time, Some(meetup_time));
 	});
 }
 
 #[test]
-fn endorse_newbie_fails_if_already_endorsed_in_previous_ceremony() {
+fn choose_meetup_time_fails_for_newbies() {
 	new_test_ext().execute_with(|| {
 		let cid = perform_bootstrapping_ceremony(None, 1);
+		set_community_rules(cid, CommunityRules::LoCoFlex);
+		let cindex = EncointerScheduler::current_ceremony_index();
 		let alice = AccountId::from(AccountKeyring::Alice);
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		let newbie = account_id(&add_population(1, 6)[0]);
+		assert_ok!(register(newbie.clone(), cid, None));
 
-		// a newbie
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
-		assert_ok!(EncointerCeremonies::endorse_newcomer(
-			RuntimeOrigin::signed(alice.clone()),
-			cid,
-			yran.clone()
-		));
-
-		run_to_next_phase();
-		run_to_next_phase();
 		run_to_next_phase();
+		// Assigning
+		assert!(EncointerCeremonies::get_meetup_index((cid, cindex), &newbie).is_some());
 
+		let (start, _) = EncointerCeremonies::attesting_phase_window();
 		assert_err!(
-			EncointerCeremonies::endorse_newcomer(RuntimeOrigin::signed(alice), cid, yran),
-			Error::<TestRuntime>::AlreadyEndorsed
+			EncointerCeremonies::choose_meetup_time(RuntimeOrigin::signed(newbie), cid, start),
+			Error::<TestRuntime>::OnlyBootstrappersOrReputablesCanChooseMeetupTime
 		);
 	});
 }
 
 #[test]
-fn endorse_newbie_fails_if_sender_has_no_reputation_and_is_not_bootstrapper() {
+fn choose_meetup_time_fails_for_loco() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
-		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		run_to_next_phase();
+		// Assigning
+
+		let (start, _) = EncointerCeremonies::attesting_phase_window();
 		assert_err!(
-			EncointerCeremonies::endorse_newcomer(
-				RuntimeOrigin::signed(account_id(&zoran)),
+			EncointerCeremonies::choose_meetup_time(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
 				cid,
-				yran
+				start
 			),
-			Error::<TestRuntime>::NoMoreNewbieTickets
+			Error::<TestRuntime>::NotAllowedByCommunityRules
 		);
 	});
 }
 
 #[test]
-fn registering_in_attestation_phase_works() {
+fn choose_meetup_time_outside_attesting_phase_fails() {
 	new_test_ext().execute_with(|| {
 		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
-		let cindex = EncointerScheduler::current_ceremony_index();
-		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+		set_community_rules(cid, CommunityRules::LoCoFlex);
+		let alice = AccountKeyring::Alice.to_account_id();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+
+		assert_err!(
+			EncointerCeremonies::choose_meetup_time(RuntimeOrigin::signed(alice.clone()), cid, 0),
+			Error::<TestRuntime>::AssigningPhaseRequired
+		);
 
 		run_to_next_phase();
-		run_to_next_phase();
-		register(yran.clone(), cid, None).unwrap();
+		// Assigning
 
-		assert!(NewbieIndex::<TestRuntime>::contains_key((cid, cindex + 1), &yran));
+		let (start, end) = EncointerCeremonies::attesting_phase_window();
+		for meetup_time in [start - 1, end] {
+			assert_err!(
+				EncointerCeremonies::choose_meetup_time(
+					RuntimeOrigin::signed(alice.clone()),
+					cid,
+					meetup_time
+				),
+				Error::<TestRuntime>::MeetupTimeOutsideAttestingPhase
+			);
+		}
 	});
 }
 
 #[test]
-fn registering_in_assigning_phase_fails() {
+fn bee_dance_rewards_participants_who_vouched_for_each_other() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
 		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
-		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+		set_community_rules(cid, CommunityRules::BeeDance);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let charlie = AccountKeyring::Charlie.to_account_id();
+		let dave = AccountKeyring::Dave.to_account_id();
+		let eve = AccountKeyring::Eve.to_account_id();
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
+		let cindex = EncointerScheduler::current_ceremony_index();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
 		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
 
 		assert_err!(
-			register(yran, cid, None),
-			Error::<TestRuntime>::RegisteringOrAttestationPhaseRequired,
+			EncointerCeremonies::attest_attendees(
+				RuntimeOrigin::signed(alice.clone()),
+				cid,
+				6,
+				bounded_vec![bob.clone()]
+			),
+			Error::<TestRuntime>::NotAllowedByCommunityRules
+		);
+
+		// vouches which don't testify a live encounter are ignored
+		vouch_for_each_other(
+			&[alice.clone(), bob.clone(), ferdie.clone()],
+			VouchKind::EncounteredHuman(PresenceType::Asynchronous),
+		);
+		vouch_for_each_other(
+			&[alice.clone(), bob, charlie, dave, eve],
+			VouchKind::EncounteredHuman(PresenceType::LivePhysical),
+		);
+
+		// Ferdie didn't show up
+		assert_ok!(EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice), cid, None));
+		assert_eq!(last_event::<TestRuntime>(), Some(Event::RewardsIssued(cid, 1, 5).into()));
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 5);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), ferdie),
+			Reputation::Unverified
 		);
 	});
 }
 
 #[test]
-fn registering_endorsee_removes_endorsement() {
+fn bee_dance_does_not_penalize_wrong_votes() {
 	new_test_ext().execute_with(|| {
 		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
-		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		set_community_rules(cid, CommunityRules::BeeDance);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let ferdie = AccountKeyring::Ferdie.to_account_id();
 		let cindex = EncointerScheduler::current_ceremony_index();
-		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		Endorsees::<TestRuntime>::insert((cid, cindex), &yran, ());
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
 
-		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), &yran));
-		register(yran.clone(), cid, None).unwrap();
+		vouch_for_each_other(
+			&[
+				alice.clone(),
+				AccountKeyring::Bob.to_account_id(),
+				AccountKeyring::Charlie.to_account_id(),
+				AccountKeyring::Dave.to_account_id(),
+				AccountKeyring::Eve.to_account_id(),
+			],
+			VouchKind::EncounteredHuman(PresenceType::LivePhysical),
+		);
+		// ferdie only vouches for alice, which counts as a vote for two participants
+		assert_ok!(EncointerVouches::vouch_for(
+			RuntimeOrigin::signed(ferdie.clone()),
+			alice.clone(),
+			VouchKind::EncounteredHuman(PresenceType::LivePhysical),
+			VouchQuality::Unspecified
+		));
 
-		assert!(EndorseeIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
-		assert!(!Endorsees::<TestRuntime>::contains_key((cid, cindex), &yran));
+		run_to_next_phase();
+		// Registering
+		assert_ok!(EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(alice), cid, None));
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 5);
+		assert_eq!(EncointerCeremonies::get_ceremony_stats(cid, cindex).exclusions.wrong_vote, 1);
+		assert_eq!(EncointerCeremonies::penalties(cid, &ferdie), None);
 	});
 }
 
-// integration tests ////////////////////////////////
-
-#[rstest(lat_micro, lon_micro, meetup_time_offset,
-case(0, 0, 0),
-case(1_000_000, 1_000_000, 0),
-case(0, 2_234_567, 0),
-case(2_000_000, 155_000_000, 0),
-case(1_000_000, -2_000_000, 0),
-case(-31_000_000, -155_000_000, 0),
-case(0, 0, 100_000),
-case(1_000_000, 1_000_000, 100_000),
-case(0, 2_234_567, 100_000),
-case(2_000_000, 155_000_000, 100_000),
-case(1_000_000, -2_000_000, 100_000),
-case(-31_000_000, -155_000_000, 100_000),
-case(1_000_000, 1_000_000, -100_000),
-case(0, 2_234_567, -100_000),
-case(2_000_000, 155_000_000, -100_000),
-case(1_000_000, -2_000_000, -100_000),
-case(-31_000_000, -155_000_000, -100_000),
-)]
-fn get_meetup_time_works(lat_micro: i64, lon_micro: i64, meetup_time_offset: i64) {
+#[test]
+fn bee_dance_ignores_vouches_before_attesting_phase() {
 	new_test_ext().execute_with(|| {
-		System::set_block_number(0);
-		run_to_block(1);
-
-		let cid = register_test_community::<TestRuntime>(
-			None,
-			lat_micro as f64 / 1_000_000.0,
-			lon_micro as f64 / 1_000_000.0,
-		);
-		// locations will not generally be returned in the order they were registered
-		// and meetups will be at randomized locations after https://github.com/encointer/pallets/issues/65
-		// that would break this test if we had more than one location registered
-
-		let cindex = EncointerScheduler::current_ceremony_index();
-		assert_eq!(cindex, 1);
-		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Registering);
-		assert_eq!(
-			EncointerScheduler::next_phase_timestamp(),
-			(GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY)) + ONE_DAY
-		);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		set_community_rules(cid, CommunityRules::BeeDance);
+		let participants: Vec<AccountId> = [
+			AccountKeyring::Alice,
+			AccountKeyring::Bob,
+			AccountKeyring::Charlie,
+			AccountKeyring::Dave,
+			AccountKeyring::Eve,
+			AccountKeyring::Ferdie,
+		]
+		.iter()
+		.map(|k| k.to_account_id())
+		.collect();
 		register_alice_bob_ferdie(cid);
-
-		EncointerCeremonies::set_meetup_time_offset(
-			RuntimeOrigin::signed(master()),
-			meetup_time_offset as i32,
-		)
-		.ok();
+		register_charlie_dave_eve(cid);
 
 		run_to_next_phase();
+		// Assigning
 
-		assert_eq!(cindex, 1);
-		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Assigning);
+		vouch_for_each_other(&participants, VouchKind::KnownHuman(PresenceType::LivePhysical));
 
 		run_to_next_phase();
+		// Attesting
 
-		assert_eq!(cindex, 1);
-		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Attesting);
-
-		let mtime = if lon_micro >= 0 {
-			GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY) + 2 * ONE_DAY + ONE_DAY / 2 -
-				(lon_micro * ONE_DAY as i64 / 360_000_000) as u64
-		} else {
-			GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY) +
-				2 * ONE_DAY + ONE_DAY / 2 +
-				(lon_micro.abs() * ONE_DAY as i64 / 360_000_000) as u64
-		};
+		assert_err!(
+			EncointerCeremonies::claim_rewards(
+				RuntimeOrigin::signed(participants[0].clone()),
+				cid,
+				None
+			),
+			Error::<TestRuntime>::VotesNotDependable
+		);
+	});
+}
 
-		let adjusted_mtime = mtime as i64 + meetup_time_offset;
+#[test]
+fn claim_rewards_batch_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountKeyring::Alice.to_account_id();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+		let cindex = EncointerScheduler::current_ceremony_index();
+		fully_attest_meetup(cid, 1);
 
-		let tol = 60_000; // [ms]
+		run_to_next_phase();
+		// Registering
 
-		println!(
-			"difference {:?}",
-			EncointerCeremonies::get_meetup_time(location).unwrap() as i64 - adjusted_mtime
+		let meetup_indices: BoundedVec<MeetupIndexType, <TestRuntime as Config>::MaxClaimBatch> =
+			vec![1, 1, 2].try_into().unwrap();
+		let call = crate::Call::<TestRuntime>::claim_rewards_batch {
+			cid,
+			meetup_indices: meetup_indices.clone(),
+		};
+		assert_eq!(
+			call.get_dispatch_info().weight,
+			<TestRuntime as Config>::WeightInfo::claim_rewards().saturating_mul(3)
 		);
-		println!("lon before {:?}", lon_micro as f64 / 1_000_000.0);
-		assert!(
-			tol > (EncointerCeremonies::get_meetup_time(location).unwrap() as i64 - adjusted_mtime)
-				.unsigned_abs()
+
+		// the duplicate and the inexistent meetup are skipped, so the whole batch is paid for
+		let post_info = EncointerCeremonies::claim_rewards_batch(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			meetup_indices,
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<TestRuntime as Config>::WeightInfo::claim_rewards().saturating_mul(3))
 		);
+		assert!(event_deposited::<TestRuntime>(Event::RewardsIssued(cid, 1, 6).into()));
+		assert_eq!(EncointerCeremonies::issued_rewards((cid, cindex), 1), Some(MeetupResult::Ok));
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 6);
+
+		// nothing left to evaluate
+		let post_info = EncointerCeremonies::claim_rewards_batch(
+			RuntimeOrigin::signed(alice),
+			cid,
+			vec![1].try_into().unwrap(),
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::Yes);
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 6);
 	});
 }
 
 #[test]
-fn ceremony_index_and_purging_registry_works() {
+fn claim_rewards_batch_is_free_if_no_meetup_is_skipped() {
 	new_test_ext().execute_with(|| {
 		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
-		let alice = AccountId::from(AccountKeyring::Alice);
-		let cindex = EncointerScheduler::current_ceremony_index();
-		let reputation_lifetime = EncointerCeremonies::reputation_lifetime();
-
-		assert_ok!(register(alice.clone(), cid, None));
-		assert_eq!(EncointerCeremonies::bootstrapper_registry((cid, cindex), 1).unwrap(), alice);
+		let alice = AccountKeyring::Alice.to_account_id();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		for _ in 0..reputation_lifetime {
-			// issue some rewards such that the inactivity counter is not increased
-			IssuedRewards::<TestRuntime>::insert(
-				(cid, EncointerScheduler::current_ceremony_index()),
-				0,
-				MeetupResult::Ok,
-			);
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+		fully_attest_meetup(cid, 1);
+
+		run_to_next_phase();
+		// Registering
+
+		let post_info = EncointerCeremonies::claim_rewards_batch(
+			RuntimeOrigin::signed(alice),
+			cid,
+			vec![1].try_into().unwrap(),
+		)
+		.unwrap();
+		assert_eq!(post_info.pays_fee, Pays::No);
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<TestRuntime as Config>::WeightInfo::claim_rewards())
+		);
+	});
+}
+
+#[test]
+fn on_idle_issues_unclaimed_rewards() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		for c in [cid, cid2] {
+			register_alice_bob_ferdie(c);
+			register_charlie_dave_eve(c);
+		}
+
+		run_to_next_phase();
+		// Assigning
+		run_to_next_phase();
+		// Attesting
+		let cindex = EncointerScheduler::current_ceremony_index();
+		fully_attest_meetup(cid, 1);
+		fully_attest_meetup(cid2, 1);
+
+		// nothing to do outside the registering phase
+		assert_eq!(EncointerCeremonies::reward_issuance_cursor(), None);
+
+		run_to_next_phase();
+		// Registering
+		assert_eq!(EncointerCeremonies::reward_issuance_cursor(), Some((cid, cindex, 1)));
+
+		// the budget suffices for a single meetup
+		let claim_weight = <TestRuntime as Config>::WeightInfo::claim_rewards();
+		let db_weight: RuntimeDbWeight = <TestRuntime as frame_system::Config>::DbWeight::get();
+		let budget = claim_weight.saturating_add(db_weight.reads_writes(3, 1));
+		let used = EncointerCeremonies::on_idle(System::block_number(), budget);
+		assert!(used.all_lte(budget));
+		assert_eq!(EncointerCeremonies::reputation_count((cid, cindex)), 6);
+		assert_eq!(EncointerCeremonies::reputation_count((cid2, cindex)), 0);
+		assert_eq!(EncointerCeremonies::reward_issuance_cursor(), Some((cid, cindex, 2)));
+
+		EncointerCeremonies::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(EncointerCeremonies::reputation_count((cid2, cindex)), 6);
+		assert_eq!(EncointerCeremonies::issued_rewards((cid2, cindex), 1), Some(MeetupResult::Ok));
+		assert_eq!(EncointerCeremonies::reward_issuance_cursor(), None);
+
+		// manual claims don't interfere
+		assert_err!(
+			EncointerCeremonies::claim_rewards(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				None
+			),
+			Error::<TestRuntime>::RewardsAlreadyIssued
+		);
+	});
+}
+
+#[test]
+fn bootstrapping_works() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountKeyring::Alice.pair();
+		let bob = AccountKeyring::Bob.pair();
+		let charlie = AccountKeyring::Charlie.pair();
+		let dave = AccountKeyring::Dave.pair();
+		let eve = AccountKeyring::Eve.pair();
+		let ferdie = AccountKeyring::Ferdie.pair();
+
+		EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(&alice)), cid, None)
+			.ok();
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&alice)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&bob)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&charlie)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&dave)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&eve)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), &account_id(&ferdie)),
+			Reputation::VerifiedUnlinked
+		);
+	});
+}
+
+#[test]
+fn register_with_reputation_works() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+
+		// a non-bootstrapper
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		let zoran_new = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
+
+		// another non-bootstrapper
+		let yuri = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		// fake reputation registry for first ceremony
+		assert!(
+			EncointerBalances::issue(cid, &account_id(&zoran), NominalIncome::from_num(1)).is_ok()
+		);
+		EncointerCeremonies::fake_reputation(
+			(cid, cindex - 1),
+			&account_id(&zoran),
+			Reputation::VerifiedUnlinked,
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedUnlinked
+		);
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		println!("cindex {cindex}");
+		// wrong sender of good proof fails
+		let proof = prove_attendance(account_id(&zoran_new), cid, cindex - 1, &zoran);
+		assert_err!(
+			register(account_id(&yuri), cid, Some(proof)),
+			Error::<TestRuntime>::WrongProofSubject
+		);
+
+		// see if Zoran can register with his fresh key
+		// for the next ceremony claiming his former attendance
+		IssuedRewards::<TestRuntime>::insert((cid, cindex - 1), 0, MeetupResult::Ok);
+		let proof = prove_attendance(account_id(&zoran_new), cid, cindex - 1, &zoran);
+		assert_ok!(register(account_id(&zoran_new), cid, Some(proof)));
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), account_id(&zoran_new)),
+			Reputation::UnverifiedReputable
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedLinked
+		);
+
+		// double signing (re-using reputation) fails
+		let proof_second = prove_attendance(account_id(&yuri), cid, cindex - 1, &zoran);
+		assert_err!(
+			register(account_id(&yuri), cid, Some(proof_second)),
+			Error::<TestRuntime>::AttendanceUnverifiedOrAlreadyUsed
+		);
+
+		// signer without reputation fails
+		let proof = prove_attendance(account_id(&yuri), cid, cindex - 1, &yuri);
+		assert_err!(
+			register(account_id(&yuri), cid, Some(proof)),
+			Error::<TestRuntime>::AttendanceUnverifiedOrAlreadyUsed
+		);
+	});
+}
+
+#[test]
+fn register_with_reputation_from_partner_community_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		let yuri = sr25519::Pair::from_seed_slice(&[7u8; 32]).unwrap();
+		let newbie = account_id(&sr25519::Pair::from_seed_slice(&[6u8; 32]).unwrap());
+		assert!(
+			EncointerBalances::issue(cid, &account_id(&zoran), NominalIncome::from_num(1)).is_ok()
+		);
+		for p in [&zoran, &yuri] {
+			EncointerCeremonies::fake_reputation(
+				(cid2, cindex - 1),
+				&account_id(p),
+				Reputation::VerifiedUnlinked,
+			);
+		}
+
+		// reputation from other communities only grants endorsee status
+		let proof = prove_attendance(account_id(&zoran), cid2, cindex - 1, &zoran);
+		assert_ok!(register(account_id(&zoran), cid, Some(proof)));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&zoran)),
+			Some(ParticipantType::Endorsee)
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), account_id(&zoran)),
+			Reputation::Unverified
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid2, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedLinked
+		);
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(account_id(&yuri)),
+				cid,
+				newbie.clone()
+			),
+			Error::<TestRuntime>::NoMoreNewbieTickets
+		);
+
+		assert_err!(
+			EncointerCeremonies::add_partner_community(RuntimeOrigin::signed(master()), cid, cid),
+			Error::<TestRuntime>::PartnerCommunityIsSelf
+		);
+		assert_ok!(EncointerCeremonies::add_partner_community(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cid2
+		));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(Event::PartnerCommunityAdded(cid, cid2).into())
+		);
+		assert!(EncointerCeremonies::is_trusted_community(&cid, &cid2));
+		// trust is not mutual
+		assert!(!EncointerCeremonies::is_trusted_community(&cid2, &cid));
+
+		// reputation from partner communities counts fully
+		let proof = prove_attendance(account_id(&yuri), cid2, cindex - 1, &yuri);
+		assert_ok!(register(account_id(&yuri), cid, Some(proof)));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&yuri)),
+			Some(ParticipantType::Reputable)
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), account_id(&yuri)),
+			Reputation::UnverifiedReputable
+		);
+		assert!(!EncointerCeremonies::has_reputation(&account_id(&yuri), &cid));
+		assert!(EncointerCeremonies::has_trusted_reputation(&account_id(&yuri), &cid));
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(account_id(&yuri)),
+			cid,
+			newbie
+		));
+
+		assert_ok!(EncointerCeremonies::remove_partner_community(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cid2
+		));
+		assert!(!EncointerCeremonies::is_trusted_community(&cid, &cid2));
+		assert_err!(
+			EncointerCeremonies::remove_partner_community(
+				RuntimeOrigin::signed(master()),
+				cid,
+				cid2
+			),
+			Error::<TestRuntime>::NotAPartnerCommunity
+		);
+	});
+}
+
+#[test]
+fn add_partner_community_fails_if_too_many_partners() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let partner = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let max_partners = <<TestRuntime as Config>::MaxPartnerCommunities as Get<u32>>::get();
+		for i in 0..max_partners {
+			let other = CommunityIdentifier::new(Location::default(), vec![i]).unwrap();
+			PartnerCommunities::<TestRuntime>::insert(cid, other, ());
+		}
+
+		assert_err!(
+			EncointerCeremonies::add_partner_community(
+				RuntimeOrigin::signed(master()),
+				cid,
+				partner
+			),
+			Error::<TestRuntime>::TooManyPartnerCommunities
+		);
+
+		// existing partners can still be added again
+		let other = CommunityIdentifier::new(Location::default(), vec![0u32]).unwrap();
+		PartnerCommunities::<TestRuntime>::remove(cid, other);
+		PartnerCommunities::<TestRuntime>::insert(cid, partner, ());
+		assert_ok!(EncointerCeremonies::add_partner_community(
+			RuntimeOrigin::signed(master()),
+			cid,
+			partner
+		));
+	});
+}
+
+#[test]
+fn purge_community_removes_its_partnerships() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		assert_ok!(EncointerCeremonies::add_partner_community(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cid2
+		));
+		assert_ok!(EncointerCeremonies::add_partner_community(
+			RuntimeOrigin::signed(master()),
+			cid2,
+			cid
+		));
+
+		EncointerCeremonies::purge_community(cid2);
+
+		assert!(!EncointerCeremonies::is_trusted_community(&cid, &cid2));
+		assert_eq!(EncointerCeremonies::partner_communities(&cid), vec![]);
+		assert_eq!(EncointerCeremonies::partner_communities(&cid2), vec![]);
+	});
+}
+
+#[test]
+fn endorsement_by_bootstrapper_for_newbie_works_until_no_more_tickets() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+
+		let endorsees = add_population(
+			(EncointerCeremonies::endorsement_tickets_per_bootstrapper() + 1) as usize,
+			6,
+		);
+		for i in 0..EncointerCeremonies::endorsement_tickets_per_bootstrapper() {
+			assert_ok!(EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(alice.clone()),
+				cid,
+				account_id(&endorsees[i as usize])
+			));
+			assert_eq!(
+				last_event::<TestRuntime>(),
+				Some(
+					Event::EndorsedParticipant(
+						cid,
+						alice.clone(),
+						account_id(&endorsees[i as usize])
+					)
+					.into()
+				)
+			);
+		}
+
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(alice),
+				cid,
+				account_id(
+					&endorsees
+						[EncointerCeremonies::endorsement_tickets_per_bootstrapper() as usize]
+				),
+			),
+			Error::<TestRuntime>::NoMoreNewbieTickets,
+		);
+	});
+}
+
+#[test]
+fn endorse_and_register_batch_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountId::from(AccountKeyring::Alice);
+		assert_ok!(EncointerCeremonies::do_set_endorsement_tickets_per_bootstrapper(3));
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
+
+		let newbies = add_population(6, 6);
+		// a newbie who has registered already gets upgraded
+		assert_ok!(register(account_id(&newbies[0]), cid, None));
+
+		let mut consents: Vec<_> = newbies[..4]
+			.iter()
+			.map(|n| consent_to_registration(alice.clone(), cid, cindex, n))
+			.collect();
+		// signed by someone else
+		let mut forged = consent_to_registration(alice.clone(), cid, cindex, &newbies[5]);
+		forged.newbie = account_id(&newbies[4]);
+		consents.insert(1, forged);
+		// consent for another endorser
+		consents.push(consent_to_registration(
+			AccountId::from(AccountKeyring::Bob),
+			cid,
+			cindex,
+			&newbies[5],
+		));
+		// bootstrappers are rejected before a ticket is burned
+		consents.insert(
+			0,
+			consent_to_registration(alice.clone(), cid, cindex, &AccountKeyring::Bob.pair()),
+		);
+
+		let post_info = EncointerCeremonies::endorse_and_register_batch(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			BoundedVec::try_from(consents).unwrap(),
+		)
+		.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<TestRuntime as Config>::WeightInfo::endorse_and_register_batch(3, 4))
+		);
+
+		for newbie in &newbies[..3] {
+			assert_eq!(
+				EncointerCeremonies::get_participant_type((cid, cindex), &account_id(newbie)),
+				Some(ParticipantType::Endorsee)
+			);
+			assert!(event_deposited::<TestRuntime>(
+				Event::ParticipantRegistered(cid, ParticipantType::Endorsee, account_id(newbie))
+					.into()
+			));
+		}
+		assert_eq!(EncointerCeremonies::endorsee_count((cid, cindex)), 3);
+		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 0);
+		for newbie in &newbies[3..] {
+			assert_eq!(
+				EncointerCeremonies::get_participant_type((cid, cindex), &account_id(newbie)),
+				None
+			);
+		}
+
+		assert!(event_deposited::<TestRuntime>(
+			Event::EndorseeRegistrationFailed {
+				cid,
+				index: 2,
+				newbie: account_id(&newbies[4]),
+				error: Error::<TestRuntime>::BadRegistrationConsentSignature.into(),
+			}
+			.into()
+		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::EndorseeRegistrationFailed {
+				cid,
+				index: 5,
+				newbie: account_id(&newbies[3]),
+				error: Error::<TestRuntime>::NoMoreNewbieTickets.into(),
+			}
+			.into()
+		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::EndorseeRegistrationFailed {
+				cid,
+				index: 6,
+				newbie: account_id(&newbies[5]),
+				error: Error::<TestRuntime>::RegistrationConsentMismatch.into(),
+			}
+			.into()
+		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::EndorseeRegistrationFailed {
+				cid,
+				index: 0,
+				newbie: AccountKeyring::Bob.to_account_id(),
+				error: Error::<TestRuntime>::CannotEndorseBootstrapper.into(),
+			}
+			.into()
+		));
+	});
+}
+
+#[test]
+fn endorsing_newbie_for_next_ceremony_works_after_registering_phase() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		run_to_next_phase();
+
+		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Assigning);
+		// a newbie
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			account_id(&zoran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex + 1), &account_id(&zoran)));
+
+		run_to_next_phase();
+
+		let bogdan = sr25519::Pair::from_seed_slice(&[99u8; 32]).unwrap();
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice),
+			cid,
+			account_id(&bogdan)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex + 1), &account_id(&bogdan)));
+	});
+}
+
+#[test]
+fn endorsing_newbie_twice_fails() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		// a newbie
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			account_id(&zoran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(alice),
+				cid,
+				account_id(&zoran),
+			),
+			Error::<TestRuntime>::AlreadyEndorsed,
+		);
+	});
+}
+
+#[test]
+fn endorsing_two_newbies_works() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		// a newbie
+		let yran = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			account_id(&zoran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice),
+			cid,
+			account_id(&yran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&yran)));
+	});
+}
+
+#[test]
+fn endorsement_survives_idle_cycle() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+
+		// a newbie
+		let zoran = account_id(&sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap());
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			zoran.clone()
+		));
+		assert!(EncointerCeremonies::is_endorsed(&zoran, &(cid, 4)).is_some());
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		assert!(EncointerCeremonies::is_endorsed(&zoran, &(cid, 4)).is_some());
+	});
+}
+
+#[test]
+fn endorsing_works_after_subject_has_already_registered() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		// a newbie
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
+		assert_ok!(register(yran.clone(), cid, None));
+
+		assert!(NewbieIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice),
+			cid,
+			yran.clone()
+		));
+
+		assert!(EndorseeIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+		assert!(!NewbieIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+	});
+}
+
+#[test]
+fn endorse_newbie_works_for_reputables() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let reputable = account_id(&sr25519::Pair::from_seed_slice(&[10u8; 32]).unwrap());
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		EncointerCeremonies::fake_reputation(
+			(cid, cindex - 1),
+			&reputable,
+			Reputation::VerifiedUnlinked,
+		);
+
+		// a newbie
+		let yran = sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap();
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		let bob = sr25519::Pair::from_seed_slice(&[10u8; 32]).unwrap();
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(reputable.clone()),
+			cid,
+			account_id(&zoran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&zoran)));
+		assert_eq!(BurnedReputableNewbieTickets::<TestRuntime>::get((cid, cindex), &reputable), 1);
+		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::get(cid, &reputable), 0);
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(reputable.clone()),
+			cid,
+			account_id(&yran)
+		));
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), account_id(&yran)));
+		assert_eq!(BurnedReputableNewbieTickets::<TestRuntime>::get((cid, cindex), &reputable), 2);
+		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::get(cid, &reputable), 0);
+
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(reputable),
+				cid,
+				account_id(&bob)
+			),
+			Error::<TestRuntime>::NoMoreNewbieTickets,
+		);
+	});
+}
+
+#[test]
+fn endorse_newbie_fails_if_already_endorsed_in_previous_ceremony() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let alice = AccountId::from(AccountKeyring::Alice);
+
+		// a newbie
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		assert_ok!(EncointerCeremonies::endorse_newcomer(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			yran.clone()
+		));
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(RuntimeOrigin::signed(alice), cid, yran),
+			Error::<TestRuntime>::AlreadyEndorsed
+		);
+	});
+}
+
+#[test]
+fn endorse_newbie_fails_if_sender_has_no_reputation_and_is_not_bootstrapper() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert_err!(
+			EncointerCeremonies::endorse_newcomer(
+				RuntimeOrigin::signed(account_id(&zoran)),
+				cid,
+				yran
+			),
+			Error::<TestRuntime>::NoMoreNewbieTickets
+		);
+	});
+}
+
+#[test]
+fn registering_in_attestation_phase_works() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		let cindex = EncointerScheduler::current_ceremony_index();
+		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+
+		run_to_next_phase();
+		run_to_next_phase();
+		register(yran.clone(), cid, None).unwrap();
+
+		assert!(NewbieIndex::<TestRuntime>::contains_key((cid, cindex + 1), &yran));
+	});
+}
+
+#[test]
+fn registering_in_assigning_phase_fails() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+
+		run_to_next_phase();
+
+		assert_err!(
+			register(yran, cid, None),
+			Error::<TestRuntime>::RegisteringOrAttestationPhaseRequired,
+		);
+	});
+}
+
+#[test]
+fn registering_endorsee_removes_endorsement() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let yran = account_id(&sr25519::Pair::from_seed_slice(&[8u8; 32]).unwrap());
+		let cindex = EncointerScheduler::current_ceremony_index();
+		assert!(EncointerBalances::issue(cid, &yran, NominalIncome::from_num(1)).is_ok());
+
+		Endorsees::<TestRuntime>::insert((cid, cindex), &yran, ());
+
+		assert!(Endorsees::<TestRuntime>::contains_key((cid, cindex), &yran));
+		register(yran.clone(), cid, None).unwrap();
+
+		assert!(EndorseeIndex::<TestRuntime>::contains_key((cid, cindex), &yran));
+		assert!(!Endorsees::<TestRuntime>::contains_key((cid, cindex), &yran));
+	});
+}
+
+// integration tests ////////////////////////////////
+
+#[rstest(lat_micro, lon_micro, meetup_time_offset,
+case(0, 0, 0),
+case(1_000_000, 1_000_000, 0),
+case(0, 2_234_567, 0),
+case(2_000_000, 155_000_000, 0),
+case(1_000_000, -2_000_000, 0),
+case(-31_000_000, -155_000_000, 0),
+case(0, 0, 100_000),
+case(1_000_000, 1_000_000, 100_000),
+case(0, 2_234_567, 100_000),
+case(2_000_000, 155_000_000, 100_000),
+case(1_000_000, -2_000_000, 100_000),
+case(-31_000_000, -155_000_000, 100_000),
+case(1_000_000, 1_000_000, -100_000),
+case(0, 2_234_567, -100_000),
+case(2_000_000, 155_000_000, -100_000),
+case(1_000_000, -2_000_000, -100_000),
+case(-31_000_000, -155_000_000, -100_000),
+)]
+fn get_meetup_time_works(lat_micro: i64, lon_micro: i64, meetup_time_offset: i64) {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(0);
+		run_to_block(1);
+
+		let cid = register_test_community::<TestRuntime>(
+			None,
+			lat_micro as f64 / 1_000_000.0,
+			lon_micro as f64 / 1_000_000.0,
+		);
+		// locations will not generally be returned in the order they were registered
+		// and meetups will be at randomized locations after https://github.com/encointer/pallets/issues/65
+		// that would break this test if we had more than one location registered
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		assert_eq!(cindex, 1);
+		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Registering);
+		assert_eq!(
+			EncointerScheduler::next_phase_timestamp(),
+			(GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY)) + ONE_DAY
+		);
+		register_alice_bob_ferdie(cid);
+
+		EncointerCeremonies::set_meetup_time_offset(
+			RuntimeOrigin::signed(master()),
+			meetup_time_offset as i32,
+		)
+		.ok();
+
+		run_to_next_phase();
+
+		assert_eq!(cindex, 1);
+		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Assigning);
+
+		run_to_next_phase();
+
+		assert_eq!(cindex, 1);
+		assert_eq!(EncointerScheduler::current_phase(), CeremonyPhaseType::Attesting);
+
+		let mtime = if lon_micro >= 0 {
+			GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY) + 2 * ONE_DAY + ONE_DAY / 2 -
+				(lon_micro * ONE_DAY as i64 / 360_000_000) as u64
+		} else {
+			GENESIS_TIME - GENESIS_TIME.rem(ONE_DAY) +
+				2 * ONE_DAY + ONE_DAY / 2 +
+				(lon_micro.abs() * ONE_DAY as i64 / 360_000_000) as u64
+		};
+
+		let adjusted_mtime = mtime as i64 + meetup_time_offset;
+
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+
+		let tol = 60_000; // [ms]
+
+		println!(
+			"difference {:?}",
+			EncointerCeremonies::get_meetup_time(location).unwrap() as i64 - adjusted_mtime
+		);
+		println!("lon before {:?}", lon_micro as f64 / 1_000_000.0);
+		assert!(
+			tol > (EncointerCeremonies::get_meetup_time(location).unwrap() as i64 - adjusted_mtime)
+				.unsigned_abs()
+		);
+	});
+}
+
+#[test]
+fn ceremony_index_and_purging_registry_works() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountId::from(AccountKeyring::Alice);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let reputation_lifetime = EncointerCeremonies::reputation_lifetime();
+
+		assert_ok!(register(alice.clone(), cid, None));
+		assert_eq!(EncointerCeremonies::bootstrapper_registry((cid, cindex), 1).unwrap(), alice);
+		CeremonyStatistics::<TestRuntime>::mutate((cid, cindex), |stats| {
+			stats.meetup_results.record(MeetupResult::Ok)
+		});
+
+		for _ in 0..reputation_lifetime {
+			// issue some rewards such that the inactivity counter is not increased
+			fake_rewarded_meetup(cid, EncointerScheduler::current_ceremony_index());
+
+			run_to_next_phase();
+			run_to_next_phase();
+			run_to_next_phase();
+
+			// still not purged
+			assert_eq!(
+				EncointerCeremonies::bootstrapper_registry((cid, cindex), 1).unwrap(),
+				alice
+			);
+		}
+
+		// only after n=ReputationLifetimes cycles everything should be purged
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		// now again registering
+		let new_cindex = EncointerScheduler::current_ceremony_index();
+		assert_eq!(new_cindex, cindex + reputation_lifetime + 1);
+		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 0);
+		assert_eq!(EncointerCeremonies::bootstrapper_registry((cid, cindex), 1), None);
+		assert_eq!(EncointerCeremonies::bootstrapper_index((cid, cindex), &alice), 0);
+		assert_eq!(EncointerCeremonies::get_ceremony_stats(cid, cindex), Default::default());
+	});
+}
+
+#[test]
+fn after_inactive_cycle_forbid_non_bootstrapper_registration() {
+	new_test_ext().execute_with(|| {
+		let cid = CommunityIdentifier::default();
+		let mut cindex = 1;
+
+		let bootstrapper = account_id(&AccountKeyring::Alice.pair());
+		EncointerCommunities::insert_bootstrappers(cid, bounded_vec![bootstrapper.clone()]);
+		let reputable = account_id(&AccountKeyring::Bob.pair());
+		let newbie = account_id(&AccountKeyring::Eve.pair());
+
+		assert!(EncointerCeremonies::register(cid, cindex, &bootstrapper, None).is_ok());
+		assert!(EncointerCeremonies::register(cid, cindex, &reputable, None).is_err());
+		assert!(EncointerCeremonies::register(cid, cindex, &newbie, None).is_err());
+
+		assert!(EncointerBalances::issue(cid, &reputable, NominalIncome::from_num(1)).is_ok());
+		cindex += 1;
+
+		assert!(EncointerCeremonies::register(cid, cindex, &bootstrapper, None).is_ok());
+		assert!(EncointerCeremonies::register(cid, cindex, &reputable, None).is_ok());
+		assert!(EncointerCeremonies::register(cid, cindex, &newbie, None).is_ok());
+	});
+}
+
+#[test]
+fn grow_population_and_removing_community_works() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 3);
+		let mut participants = bootstrappers();
+
+		// generate many keys and register all of them
+		// they will use the same keys per participant throughout to following ceremonies
+		participants.extend(add_population(14, participants.len()));
+		fake_rewarded_meetup(cid, EncointerScheduler::current_ceremony_index() - 1);
+		participants.iter().for_each(|p| {
+			assert_ok!(EncointerBalances::issue(cid, &account_id(p), NominalIncome::from_num(1)));
+			assert_ok!(register(account_id(p), cid, None));
+		});
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		run_to_next_phase();
+		// Assigning
+		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
+		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 0);
+		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 14);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 1);
+
+		run_to_next_phase();
+		// WITNESSING
+
+		fully_attest_meetup(cid, 1);
+
+		run_to_next_phase();
+		// Registering
+		for pair in participants.iter() {
+			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
+				.ok();
+		}
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		// register everybody again. also those who didn't have the chance last time
+		for pair in participants.iter() {
+			let proof = get_proof(cid, cindex - 1, pair);
+			register(account_id(pair), cid, proof).unwrap();
+		}
+		run_to_next_phase();
+		// Assigning
+
+		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
+		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 2);
+		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 12);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 1);
+
+		run_to_next_phase();
+
+		fully_attest_meetup(cid, 1);
+
+		run_to_next_phase();
+		// Registering
+		for pair in participants.iter() {
+			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
+				.ok();
+		}
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		// register everybody again. also those who didn't have the chance last time
+		for pair in participants.iter() {
+			let proof = get_proof(cid, cindex - 1, pair);
+			register(account_id(pair), cid, proof).unwrap();
+		}
+		run_to_next_phase();
+		// Assigning
+
+		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
+		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 4);
+		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 10);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 2);
+
+		run_to_next_phase();
+		// WITNESSING
+		fully_attest_meetup(cid, 1);
+		fully_attest_meetup(cid, 2);
+
+		run_to_next_phase();
+		// Registering
+		for pair in participants.iter() {
+			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
+				.ok();
+		}
+
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let mut proof_count = 0;
+		for pair in participants.iter() {
+			let proof = get_proof(cid, cindex - 1, pair);
+			if proof.is_some() {
+				proof_count += 1;
+			}
+			register(account_id(pair), cid, proof).unwrap();
+		}
+		run_to_next_phase();
+		// Assigning
+		assert_eq!(proof_count, 13);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 2);
+
+		// now we remove the community
+		EncointerCeremonies::purge_community(cid);
+
+		let reputation_lifetime = EncointerCeremonies::reputation_lifetime();
+		let current_cindex =
+			EncointerScheduler::current_ceremony_index().saturating_sub(reputation_lifetime);
+
+		// only sanity check. Community removal is better tested in the communities pallet.
+		assert_eq!(EncointerCommunities::community_identifiers().contains(&cid), false);
+
+		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::iter_prefix(cid).next(), None);
+
+		for cindex in current_cindex.saturating_sub(reputation_lifetime)..=current_cindex {
+			assert_eq!(
+				BootstrapperRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(),
+				None
+			);
+			assert_eq!(BootstrapperIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(BootstrapperCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(ReputableRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(ReputableIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(ReputableCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(EndorseeRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(EndorseeIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(EndorseeCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(NewbieRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(NewbieIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(NewbieCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(AssignmentCounts::<TestRuntime>::contains_key((cid, cindex)), false);
+			assert_eq!(Assignments::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(
+				ParticipantReputation::<TestRuntime>::iter_prefix((cid, cindex)).next(),
+				None
+			);
+
+			assert_eq!(ReputationCount::<TestRuntime>::contains_key((cid, cindex)), false);
+			assert_eq!(GlobalReputationCount::<TestRuntime>::contains_key(cindex), false);
+
+			assert_eq!(Endorsees::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(EndorseesCount::<TestRuntime>::contains_key((cid, cindex)), false);
+			assert_eq!(MeetupCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(AttestationRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(AttestationIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+			assert_eq!(AttestationCount::<TestRuntime>::contains_key((cid, cindex)), false);
+
+			assert_eq!(
+				MeetupParticipantCountVote::<TestRuntime>::iter_prefix((cid, cindex)).next(),
+				None
+			);
+
+			assert_eq!(IssuedRewards::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+
+			assert_eq!(
+				BurnedReputableNewbieTickets::<TestRuntime>::iter_prefix((cid, cindex)).next(),
+				None
+			);
+
+			assert_eq!(InactivityCounters::<TestRuntime>::contains_key(cid), false);
+		}
+	});
+}
+
+#[test]
+fn dont_create_assignment_with_less_than_three() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		assert_ok!(register(account_id(&AccountKeyring::Charlie.pair()), cid, None));
+		assert_ok!(register(account_id(&AccountKeyring::Dave.pair()), cid, None));
+		run_to_next_phase();
+		assert_eq!(EncointerCeremonies::assignments((cid, cindex)), Assignment::default());
+	});
+}
+
+#[test]
+fn get_assignment_params_works() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let assignment = EncointerCeremonies::assignments((cid, cindex));
+
+		assert_eq!(assignment.bootstrappers_reputables.m, 0);
+		assert_eq!(assignment.bootstrappers_reputables.s1, 0);
+		assert_eq!(assignment.bootstrappers_reputables.s2, 0);
+		assert_eq!(assignment.endorsees.m, 0);
+		assert_eq!(assignment.endorsees.s1, 0);
+		assert_eq!(assignment.endorsees.s2, 0);
+		assert_eq!(assignment.newbies.m, 0);
+		assert_eq!(assignment.newbies.s1, 0);
+		assert_eq!(assignment.newbies.s2, 0);
+
+		register_charlie_dave_eve(cid);
+		run_to_next_phase();
+
+		let assignment = EncointerCeremonies::assignments((cid, cindex));
+
+		assert!(assignment.bootstrappers_reputables.m > 0);
+		assert!(assignment.bootstrappers_reputables.s1 > 0);
+		assert!(assignment.bootstrappers_reputables.s2 > 0);
+		assert!(assignment.endorsees.m > 0);
+		assert!(assignment.endorsees.s1 > 0);
+		assert!(assignment.endorsees.s2 > 0);
+		assert!(assignment.newbies.m > 0);
+		assert!(assignment.newbies.s1 > 0);
+		assert!(assignment.newbies.s2 > 0);
+	});
+}
+
+#[test]
+fn update_inactivity_counters_works() {
+	new_test_ext().execute_with(|| {
+		let cid0 = CommunityIdentifier::default();
+		let cid1 = CommunityIdentifier::new(
+			Location::new(Degree::from_num(1f64), Degree::from_num(1f64)),
+			Vec::<i64>::new(),
+		)
+		.unwrap();
+
+		let mut cindex = 5;
+
+		fake_rewarded_meetup(cid0, cindex);
+		fake_rewarded_meetup(cid1, cindex);
+
+		let timeout = 1;
+		assert_eq!(
+			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
+			vec![]
+		);
+
+		cindex += 1;
+		fake_rewarded_meetup(cid0, cindex);
+		assert_eq!(
+			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
+			vec![]
+		);
+
+		cindex += 1;
+		assert_eq!(
+			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
+			vec![cid1]
+		);
+
+		cindex += 1;
+		assert_eq!(
+			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
+			vec![cid0, cid1]
+		);
+	});
+}
+
+#[test]
+fn communities_without_rewarded_meetups_are_purged() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let bootstrappers = bootstrappers();
+		let is_registered =
+			|| <encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid);
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid), Some(1));
+
+		for counter in 2..=EncointerCeremonies::inactivity_timeout() {
+			// participants keep registering, but never attest each other
+			let cindex = EncointerScheduler::current_ceremony_index();
+			bootstrappers.iter().for_each(|b| {
+				assert_ok!(register(account_id(b), cid, None));
+			});
+			run_to_next_phase();
+			// Assigning
+			assert!(is_registered());
+			assert_eq!(EncointerCeremonies::inactivity_counters(cid), Some(counter));
+			run_to_next_phase();
+			// Attesting
+			run_to_next_phase();
+			// Registering
+			assert_ok!(EncointerCeremonies::claim_rewards(
+				RuntimeOrigin::signed(account_id(&bootstrappers[0])),
+				cid,
+				None
+			));
+			assert_eq!(
+				EncointerCeremonies::issued_rewards((cid, cindex), 1),
+				Some(MeetupResult::VotesNotDependable)
+			);
+		}
+
+		bootstrappers.iter().for_each(|b| {
+			assert_ok!(register(account_id(b), cid, None));
+		});
+		run_to_next_phase();
+		// Assigning
+		assert!(!is_registered());
+	});
+}
+
+#[test]
+fn purge_inactive_communities_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+
+		// inactivity counter is 1, beacuse of a full ceremony cycle in the bootstrapping ceremony
+		// without any rewards being claimed
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 1);
+
+		run_to_next_phase();
+		// Assigning
+		assert_eq!(
+			event_at_index::<TestRuntime>(get_num_events::<TestRuntime>() - 2),
+			Some(Event::InactivityCounterUpdated(cid, 2).into())
+		);
+
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 2);
+
+		// issued rewards will cause inactivity counter to go to 0 in the next cycle
+		fake_rewarded_meetup(cid, EncointerScheduler::current_ceremony_index());
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_eq!(
+			event_at_index::<TestRuntime>(get_num_events::<TestRuntime>() - 2),
+			Some(Event::InactivityCounterUpdated(cid, 0).into())
+		);
+
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 0);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-			run_to_next_phase();
-			run_to_next_phase();
-			run_to_next_phase();
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 1);
 
-			// still not purged
-			assert_eq!(
-				EncointerCeremonies::bootstrapper_registry((cid, cindex), 1).unwrap(),
-				alice
-			);
-		}
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		// only after n=ReputationLifetimes cycles everything should be purged
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 2);
 
 		run_to_next_phase();
 		run_to_next_phase();
 		run_to_next_phase();
-		// now again registering
-		let new_cindex = EncointerScheduler::current_ceremony_index();
-		assert_eq!(new_cindex, cindex + reputation_lifetime + 1);
-		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 0);
-		assert_eq!(EncointerCeremonies::bootstrapper_registry((cid, cindex), 1), None);
-		assert_eq!(EncointerCeremonies::bootstrapper_index((cid, cindex), &alice), 0);
+
+		assert!(
+			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
+		// now the inactivity counter is 3 == inactivity_timeout, so in the next cycle the community will be purged
+		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 3);
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		// here community gets purged
+		assert!(
+			!<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		);
 	});
 }
 
 #[test]
-fn after_inactive_cycle_forbid_non_bootstrapper_registration() {
+fn get_meetup_index_works() {
 	new_test_ext().execute_with(|| {
-		let cid = CommunityIdentifier::default();
-		let mut cindex = 1;
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
 
-		let bootstrapper = account_id(&AccountKeyring::Alice.pair());
-		EncointerCommunities::insert_bootstrappers(cid, bounded_vec![bootstrapper.clone()]);
-		let reputable = account_id(&AccountKeyring::Bob.pair());
-		let newbie = account_id(&AccountKeyring::Eve.pair());
+		let participants = add_population(4, 0);
+		let p1 = account_id(&participants[0]);
+		let p2 = account_id(&participants[1]);
+		let p3 = account_id(&participants[2]);
+		let p4 = account_id(&participants[3]);
 
-		assert!(EncointerCeremonies::register(cid, cindex, &bootstrapper, false).is_ok());
-		assert!(EncointerCeremonies::register(cid, cindex, &reputable, false).is_err());
-		assert!(EncointerCeremonies::register(cid, cindex, &newbie, false).is_err());
+		MeetupCount::<TestRuntime>::insert((cid, cindex), 10);
 
-		assert!(EncointerBalances::issue(cid, &reputable, NominalIncome::from_num(1)).is_ok());
-		cindex += 1;
+		BootstrapperIndex::<TestRuntime>::insert((cid, cindex), p1.clone(), 1);
+		AssignmentCounts::<TestRuntime>::insert(
+			(cid, cindex),
+			AssignmentCount { bootstrappers: 1, reputables: 0, endorsees: 0, newbies: 0 },
+		);
+
+		ReputableIndex::<TestRuntime>::insert((cid, cindex), p2.clone(), 1);
+
+		EndorseeIndex::<TestRuntime>::insert((cid, cindex), p3.clone(), 3);
+		NewbieIndex::<TestRuntime>::insert((cid, cindex), p4.clone(), 4);
+
+		Assignments::<TestRuntime>::insert(
+			(cid, cindex),
+			Assignment {
+				bootstrappers_reputables: AssignmentParams { m: 2, s1: 1, s2: 1 },
+				endorsees: AssignmentParams { m: 5, s1: 2, s2: 3 },
+				newbies: AssignmentParams { m: 5, s1: 2, s2: 3 },
+				locations: AssignmentParams { m: 9, s1: 8, s2: 7 },
+			},
+		);
 
-		assert!(EncointerCeremonies::register(cid, cindex, &bootstrapper, false).is_ok());
-		assert!(EncointerCeremonies::register(cid, cindex, &reputable, false).is_ok());
-		assert!(EncointerCeremonies::register(cid, cindex, &newbie, false).is_ok());
+		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p1).unwrap(), 2);
+		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p2), None);
+		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p3), None);
+		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p4), None);
 	});
 }
 
+fn region_of(location: Location) -> GeoHash {
+	GeoHash::try_from_params(location.lat, location.lon).unwrap()
+}
+
 #[test]
-fn grow_population_and_removing_community_works() {
+fn register_participant_in_region_works() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 3);
-		let mut participants = bootstrappers();
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = AccountKeyring::Alice.to_account_id();
+		let bob = AccountKeyring::Bob.to_account_id();
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let region = region_of(Location::new(Degree::from_num(0.0), Degree::from_num(0.0)));
 
-		// generate many keys and register all of them
-		// they will use the same keys per participant throughout to following ceremonies
-		participants.extend(add_population(14, participants.len()));
-		IssuedRewards::<TestRuntime>::insert(
-			(cid, EncointerScheduler::current_ceremony_index() - 1),
-			0,
-			MeetupResult::Ok,
+		assert_err!(
+			EncointerCeremonies::register_participant_in_region(
+				RuntimeOrigin::signed(alice.clone()),
+				cid,
+				None,
+				region_of(Location::new(Degree::from_num(10.0), Degree::from_num(10.0)))
+			),
+			Error::<TestRuntime>::NoLocationsInRegion
 		);
-		participants.iter().for_each(|p| {
-			assert_ok!(EncointerBalances::issue(cid, &account_id(p), NominalIncome::from_num(1)));
-			assert_ok!(register(account_id(p), cid, None));
-		});
 
-		let cindex = EncointerScheduler::current_ceremony_index();
-		run_to_next_phase();
-		// Assigning
-		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
-		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 0);
-		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 14);
-		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 1);
+		assert_ok!(EncointerCeremonies::register_participant_in_region(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			None,
+			region.clone()
+		));
+		assert_ok!(register(bob.clone(), cid, None));
+		assert_eq!(EncointerCeremonies::preferred_region((cid, cindex), &alice), Some(region));
+		assert_eq!(EncointerCeremonies::preferred_region((cid, cindex), &bob), None);
 
-		run_to_next_phase();
-		// WITNESSING
+		assert_ok!(EncointerCeremonies::unregister_participant(
+			RuntimeOrigin::signed(alice.clone()),
+			cid,
+			None
+		));
+		assert_eq!(EncointerCeremonies::preferred_region((cid, cindex), &alice), None);
+	});
+}
 
-		fully_attest_meetup(cid, 1);
+fn add_locations(cid: CommunityIdentifier, coordinates: &[f64]) {
+	for c in coordinates {
+		assert_ok!(EncointerCommunities::add_location(
+			RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+			cid,
+			Location::new(Degree::from_num(*c), Degree::from_num(*c))
+		));
+	}
+}
 
-		run_to_next_phase();
-		// Registering
-		for pair in participants.iter() {
-			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
-				.ok();
+#[test]
+fn assigning_meetups_groups_participants_by_region() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		// two locations in each region
+		add_locations(cid, &[0.01, 0.02, 1.0, 1.01]);
+		let cc = (cid, EncointerScheduler::current_ceremony_index());
+		let home = region_of(Location::new(Degree::from_num(0.01), Degree::from_num(0.01)));
+		let away = region_of(Location::new(Degree::from_num(1.0), Degree::from_num(1.0)));
+
+		let [bootstrappers, reputables, ..] = fill_registries(
+			cc,
+			AssignmentCount { bootstrappers: 6, reputables: 34, endorsees: 0, newbies: 0 },
+		);
+		let home_participants: Vec<AccountId> =
+			bootstrappers[..3].iter().chain(reputables[..17].iter()).cloned().collect();
+		let away_participants: Vec<AccountId> =
+			bootstrappers[3..].iter().chain(reputables[17..].iter()).cloned().collect();
+		for (participants, region) in [(&home_participants, &home), (&away_participants, &away)] {
+			for p in participants {
+				PreferredRegions::<TestRuntime>::insert(cc, p, region.clone());
+			}
 		}
 
-		let cindex = EncointerScheduler::current_ceremony_index();
-		// register everybody again. also those who didn't have the chance last time
-		for pair in participants.iter() {
-			let proof = get_proof(cid, cindex - 1, pair);
-			register(account_id(pair), cid, proof).unwrap();
-		}
 		run_to_next_phase();
 		// Assigning
 
-		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
-		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 2);
-		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 12);
-		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 1);
-
-		run_to_next_phase();
+		assert_eq!(EncointerCeremonies::regional_assignment_count(cc), 2);
+		assert_eq!(EncointerCeremonies::meetup_count(cc), 4);
+		let locations = EncointerCommunities::get_locations(&cid);
+		for (participants, region) in [(home_participants, home), (away_participants, away)] {
+			for p in &participants {
+				let meetup_index = EncointerCeremonies::get_meetup_index(cc, p).unwrap();
+				let location = EncointerCeremonies::get_meetup_location(cc, meetup_index).unwrap();
+				assert_eq!(region_of(location), region);
+			}
+			assert_eq!(
+				EncointerCeremonies::get_aggregated_account_data(cid, &participants[0])
+					.personal
+					.unwrap()
+					.meetup_location_index
+					.map(|i| region_of(locations[i as usize])),
+				Some(region)
+			);
+		}
+	});
+}
 
-		fully_attest_meetup(cid, 1);
+#[test]
+fn assigning_meetups_does_not_group_a_few_colluders() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		add_locations(cid, &[0.01, 1.0, 1.01]);
+		let cc = (cid, EncointerScheduler::current_ceremony_index());
+		let obscure = region_of(Location::new(Degree::from_num(1.0), Degree::from_num(1.0)));
 
-		run_to_next_phase();
-		// Registering
-		for pair in participants.iter() {
-			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
-				.ok();
+		let [bootstrappers, reputables, ..] = fill_registries(
+			cc,
+			AssignmentCount { bootstrappers: 6, reputables: 24, endorsees: 0, newbies: 0 },
+		);
+		let colluders: Vec<AccountId> =
+			bootstrappers[..1].iter().chain(reputables[..2].iter()).cloned().collect();
+		assert_eq!(colluders.len() as u64, <TestRuntime as Config>::MeetupMinSize::get());
+		for p in &colluders {
+			PreferredRegions::<TestRuntime>::insert(cc, p, obscure.clone());
 		}
 
-		let cindex = EncointerScheduler::current_ceremony_index();
-		// register everybody again. also those who didn't have the chance last time
-		for pair in participants.iter() {
-			let proof = get_proof(cid, cindex - 1, pair);
-			register(account_id(pair), cid, proof).unwrap();
-		}
 		run_to_next_phase();
 		// Assigning
 
-		assert_eq!(EncointerCeremonies::bootstrapper_count((cid, cindex)), 6);
-		assert_eq!(EncointerCeremonies::reputable_count((cid, cindex)), 4);
-		assert_eq!(EncointerCeremonies::newbie_count((cid, cindex)), 10);
-		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 2);
-
-		run_to_next_phase();
-		// WITNESSING
-		fully_attest_meetup(cid, 1);
-		fully_attest_meetup(cid, 2);
+		assert_eq!(EncointerCeremonies::regional_assignment_count(cc), 0);
+		assert_eq!(EncointerCeremonies::meetup_count(cc), 3);
+		let meetups: Vec<MeetupIndexType> = colluders
+			.iter()
+			.map(|p| EncointerCeremonies::get_meetup_index(cc, p).unwrap())
+			.collect();
+		assert!(meetups.iter().any(|m| *m != meetups[0]));
+	});
+}
 
-		run_to_next_phase();
-		// Registering
-		for pair in participants.iter() {
-			EncointerCeremonies::claim_rewards(RuntimeOrigin::signed(account_id(pair)), cid, None)
-				.ok();
+#[test]
+fn assigning_meetups_ignores_regions_with_too_few_participants() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		for location in create_locations(3) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
 		}
-
 		let cindex = EncointerScheduler::current_ceremony_index();
-		let mut proof_count = 0;
-		for pair in participants.iter() {
-			let proof = get_proof(cid, cindex - 1, pair);
-			if proof.is_some() {
-				proof_count += 1;
-			}
-			register(account_id(pair), cid, proof).unwrap();
-		}
+		assert_ok!(EncointerCeremonies::register_participant_in_region(
+			RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+			cid,
+			None,
+			region_of(Location::new(Degree::from_num(1.0), Degree::from_num(1.0)))
+		));
+		register_charlie_dave_eve(cid);
+
 		run_to_next_phase();
 		// Assigning
-		assert_eq!(proof_count, 13);
-		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 2);
-
-		// now we remove the community
-		EncointerCeremonies::purge_community(cid);
-
-		let reputation_lifetime = EncointerCeremonies::reputation_lifetime();
-		let current_cindex =
-			EncointerScheduler::current_ceremony_index().saturating_sub(reputation_lifetime);
 
-		// only sanity check. Community removal is better tested in the communities pallet.
-		assert_eq!(EncointerCommunities::community_identifiers().contains(&cid), false);
-
-		assert_eq!(BurnedBootstrapperNewbieTickets::<TestRuntime>::iter_prefix(cid).next(), None);
-
-		for cindex in current_cindex.saturating_sub(reputation_lifetime)..=current_cindex {
-			assert_eq!(
-				BootstrapperRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(),
-				None
-			);
-			assert_eq!(BootstrapperIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(BootstrapperCount::<TestRuntime>::contains_key((cid, cindex)), false);
-
-			assert_eq!(ReputableRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(ReputableIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(ReputableCount::<TestRuntime>::contains_key((cid, cindex)), false);
+		assert_eq!(EncointerCeremonies::regional_assignment_count((cid, cindex)), 0);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 1);
+		assert_eq!(
+			EncointerCeremonies::get_meetup_participants((cid, cindex), 1).unwrap().len(),
+			4
+		);
+	});
+}
 
-			assert_eq!(EndorseeRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(EndorseeIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(EndorseeCount::<TestRuntime>::contains_key((cid, cindex)), false);
+#[test]
+fn assigning_meetups_ignores_regions_if_bootstrappers_exceed_their_seats() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		for location in create_locations(3) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
+		}
+		add_locations(cid, &[0.01, 0.02]);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		// the home region has two locations which seat 20 participants only. More bootstrappers
+		// than that can't be registered regularly.
+		let home = region_of(Location::new(Degree::from_num(0.01), Degree::from_num(0.01)));
+		let bootstrappers: Vec<AccountId> = add_population(21, 0).iter().map(account_id).collect();
+		for (i, b) in bootstrappers.iter().enumerate() {
+			let index = i as ParticipantIndexType + 1;
+			BootstrapperRegistry::<TestRuntime>::insert((cid, cindex), index, b);
+			BootstrapperIndex::<TestRuntime>::insert((cid, cindex), b, index);
+			BootstrapperCount::<TestRuntime>::insert((cid, cindex), index);
+			PreferredRegions::<TestRuntime>::insert((cid, cindex), b, home.clone());
+		}
 
-			assert_eq!(NewbieRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(NewbieIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(NewbieCount::<TestRuntime>::contains_key((cid, cindex)), false);
+		run_to_next_phase();
+		// Assigning
 
-			assert_eq!(AssignmentCounts::<TestRuntime>::contains_key((cid, cindex)), false);
-			assert_eq!(Assignments::<TestRuntime>::contains_key((cid, cindex)), false);
+		assert_eq!(EncointerCeremonies::regional_assignment_count((cid, cindex)), 0);
+		assert_eq!(EncointerCeremonies::meetup_count((cid, cindex)), 3);
+		for b in &bootstrappers {
+			assert!(EncointerCeremonies::get_meetup_index((cid, cindex), b).is_some());
+		}
+	});
+}
 
-			assert_eq!(
-				ParticipantReputation::<TestRuntime>::iter_prefix((cid, cindex)).next(),
-				None
-			);
+#[test]
+fn assignment_allowance_fails_if_bootstrappers_exceed_the_seats() {
+	new_test_ext().execute_with(|| {
+		let registered = AssignmentCount { bootstrappers: 11, ..Default::default() };
+		assert!(matches!(
+			EncointerCeremonies::assignment_allowance(1, registered, 10),
+			Err(Error::<TestRuntime>::NotEnoughSeatsForBootstrappers)
+		));
+		assert_eq!(
+			EncointerCeremonies::assignment_allowance(2, registered, 10).unwrap(),
+			registered
+		);
+	});
+}
 
-			assert_eq!(ReputationCount::<TestRuntime>::contains_key((cid, cindex)), false);
-			assert_eq!(GlobalReputationCount::<TestRuntime>::contains_key(cindex), false);
+#[test]
+fn cancel_meetup_refunds_linked_reputation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert!(
+			EncointerBalances::issue(cid, &account_id(&zoran), NominalIncome::from_num(1)).is_ok()
+		);
+		EncointerCeremonies::fake_reputation(
+			(cid, cindex - 1),
+			&account_id(&zoran),
+			Reputation::VerifiedUnlinked,
+		);
 
-			assert_eq!(Endorsees::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(EndorseesCount::<TestRuntime>::contains_key((cid, cindex)), false);
-			assert_eq!(MeetupCount::<TestRuntime>::contains_key((cid, cindex)), false);
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		let proof = prove_attendance(account_id(&zoran), cid, cindex - 1, &zoran);
+		assert_ok!(register(account_id(&zoran), cid, Some(proof)));
 
-			assert_eq!(AttestationRegistry::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(AttestationIndex::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
-			assert_eq!(AttestationCount::<TestRuntime>::contains_key((cid, cindex)), false);
+		// meetups don't exist yet
+		assert_err!(
+			EncointerCeremonies::cancel_meetup(RuntimeOrigin::signed(master()), cid, cindex, 1),
+			Error::<TestRuntime>::WrongPhaseForChangingMeetups
+		);
 
-			assert_eq!(
-				MeetupParticipantCountVote::<TestRuntime>::iter_prefix((cid, cindex)).next(),
-				None
-			);
+		run_to_next_phase();
+		// Assigning
+		assert_eq!(
+			EncointerCeremonies::get_meetup_index((cid, cindex), &account_id(&zoran)),
+			Some(1)
+		);
+		assert_err!(
+			EncointerCeremonies::cancel_meetup(
+				RuntimeOrigin::signed(account_id(&zoran)),
+				cid,
+				cindex,
+				1
+			),
+			DispatchError::BadOrigin
+		);
+		assert_err!(
+			EncointerCeremonies::cancel_meetup(RuntimeOrigin::signed(master()), cid, cindex, 2),
+			Error::<TestRuntime>::InvalidMeetupIndex
+		);
+		// only the actual participants are paid for
+		let participants = EncointerCeremonies::get_meetup_participants((cid, cindex), 1).unwrap();
+		assert_eq!(participants.len(), 7);
+		let post_info =
+			EncointerCeremonies::cancel_meetup(RuntimeOrigin::signed(master()), cid, cindex, 1)
+				.unwrap();
+		assert_eq!(
+			post_info.actual_weight,
+			Some(<TestRuntime as Config>::WeightInfo::cancel_meetup(7))
+		);
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(Event::MeetupCancelled { cid, cindex, meetup_index: 1, refunded: 1 }.into())
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid, cindex), account_id(&zoran)),
+			Reputation::Unverified
+		);
+		assert_err!(
+			EncointerCeremonies::cancel_meetup(RuntimeOrigin::signed(master()), cid, cindex, 1),
+			Error::<TestRuntime>::MeetupHasBeenCancelled
+		);
 
-			assert_eq!(IssuedRewards::<TestRuntime>::iter_prefix((cid, cindex)).next(), None);
+		run_to_next_phase();
+		// Attesting
+		let alice = AccountKeyring::Alice.pair();
+		assert_err!(
+			EncointerCeremonies::attest_attendees(
+				RuntimeOrigin::signed(account_id(&alice)),
+				cid,
+				7,
+				bounded_vec![account_id(&AccountKeyring::Bob.pair())]
+			),
+			Error::<TestRuntime>::MeetupHasBeenCancelled
+		);
 
-			assert_eq!(
-				BurnedReputableNewbieTickets::<TestRuntime>::iter_prefix((cid, cindex)).next(),
+		run_to_next_phase();
+		// Registering
+		assert_err!(
+			EncointerCeremonies::claim_rewards(
+				RuntimeOrigin::signed(account_id(&alice)),
+				cid,
 				None
-			);
-
-			assert_eq!(InactivityCounters::<TestRuntime>::contains_key(cid), false);
-		}
+			),
+			Error::<TestRuntime>::MeetupHasBeenCancelled
+		);
 	});
 }
 
 #[test]
-fn dont_create_assignment_with_less_than_three() {
+fn reschedule_meetup_works() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		for location in create_locations(3) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
+		}
 		let cindex = EncointerScheduler::current_ceremony_index();
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		assert_ok!(register(account_id(&AccountKeyring::Charlie.pair()), cid, None));
-		assert_ok!(register(account_id(&AccountKeyring::Dave.pair()), cid, None));
 		run_to_next_phase();
-		assert_eq!(EncointerCeremonies::assignments((cid, cindex)), Assignment::default());
+		run_to_next_phase();
+		// Attesting
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let new_location = *EncointerCommunities::get_locations(&cid)
+			.iter()
+			.find(|l| **l != location)
+			.unwrap();
+
+		assert_err!(
+			EncointerCeremonies::reschedule_meetup(
+				RuntimeOrigin::signed(master()),
+				cid,
+				cindex,
+				1,
+				Location::new(Degree::from_num(5.0), Degree::from_num(5.0))
+			),
+			Error::<TestRuntime>::UnknownMeetupLocation
+		);
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			1,
+			new_location
+		));
+		assert_eq!(EncointerCeremonies::get_meetup_location((cid, cindex), 1), Some(new_location));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::MeetupRescheduled {
+					cid,
+					cindex,
+					meetup_index: 1,
+					location: new_location,
+					time: EncointerCeremonies::get_meetup_time(new_location).unwrap(),
+				}
+				.into()
+			)
+		);
 	});
 }
 
 #[test]
-fn get_assignment_params_works() {
+fn reschedule_meetup_fails_for_a_location_in_use() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		for location in create_locations(3) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
+		}
 		let cindex = EncointerScheduler::current_ceremony_index();
-		let assignment = EncointerCeremonies::assignments((cid, cindex));
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
 
-		assert_eq!(assignment.bootstrappers_reputables.m, 0);
-		assert_eq!(assignment.bootstrappers_reputables.s1, 0);
-		assert_eq!(assignment.bootstrappers_reputables.s2, 0);
-		assert_eq!(assignment.endorsees.m, 0);
-		assert_eq!(assignment.endorsees.s1, 0);
-		assert_eq!(assignment.endorsees.s2, 0);
-		assert_eq!(assignment.newbies.m, 0);
-		assert_eq!(assignment.newbies.s1, 0);
-		assert_eq!(assignment.newbies.s2, 0);
+		run_to_next_phase();
+		run_to_next_phase();
+		// Attesting
+		MeetupCount::<TestRuntime>::insert((cid, cindex), 2);
+		let location = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let new_location = *EncointerCommunities::get_locations(&cid)
+			.iter()
+			.find(|l| **l != location)
+			.unwrap();
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			2,
+			new_location
+		));
+
+		assert_err!(
+			EncointerCeremonies::reschedule_meetup(
+				RuntimeOrigin::signed(master()),
+				cid,
+				cindex,
+				1,
+				new_location
+			),
+			Error::<TestRuntime>::MeetupLocationAlreadyInUse
+		);
+		// the location of a cancelled meetup can be reused
+		assert_ok!(EncointerCeremonies::cancel_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			2
+		));
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			1,
+			new_location
+		));
+		assert_eq!(EncointerCeremonies::get_meetup_location((cid, cindex), 1), Some(new_location));
+	});
+}
 
+#[test]
+fn reschedule_meetup_frees_the_assigned_location() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		for location in create_locations(3) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
+		}
+		let cindex = EncointerScheduler::current_ceremony_index();
+		register_alice_bob_ferdie(cid);
 		register_charlie_dave_eve(cid);
-		run_to_next_phase();
 
-		let assignment = EncointerCeremonies::assignments((cid, cindex));
+		run_to_next_phase();
+		run_to_next_phase();
+		// Attesting
+		MeetupCount::<TestRuntime>::insert((cid, cindex), 2);
+		let location_1 = EncointerCeremonies::get_meetup_location((cid, cindex), 1).unwrap();
+		let location_2 = EncointerCeremonies::get_meetup_location((cid, cindex), 2).unwrap();
+		let free_location = *EncointerCommunities::get_locations(&cid)
+			.iter()
+			.find(|l| **l != location_1 && **l != location_2)
+			.unwrap();
 
-		assert!(assignment.bootstrappers_reputables.m > 0);
-		assert!(assignment.bootstrappers_reputables.s1 > 0);
-		assert!(assignment.bootstrappers_reputables.s2 > 0);
-		assert!(assignment.endorsees.m > 0);
-		assert!(assignment.endorsees.s1 > 0);
-		assert!(assignment.endorsees.s2 > 0);
-		assert!(assignment.newbies.m > 0);
-		assert!(assignment.newbies.s1 > 0);
-		assert!(assignment.newbies.s2 > 0);
+		assert_err!(
+			EncointerCeremonies::reschedule_meetup(
+				RuntimeOrigin::signed(master()),
+				cid,
+				cindex,
+				1,
+				location_2
+			),
+			Error::<TestRuntime>::MeetupLocationAlreadyInUse
+		);
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			2,
+			free_location
+		));
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			1,
+			location_2
+		));
+		// meetup 1 has left its assigned location, and meetup 2 can move on again
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			2,
+			location_1
+		));
+		assert_ok!(EncointerCeremonies::reschedule_meetup(
+			RuntimeOrigin::signed(master()),
+			cid,
+			cindex,
+			1,
+			free_location
+		));
+		assert_eq!(EncointerCeremonies::get_meetup_location((cid, cindex), 1), Some(free_location));
+		assert_eq!(EncointerCeremonies::get_meetup_location((cid, cindex), 2), Some(location_1));
 	});
 }
 
 #[test]
-fn update_inactivity_counters_works() {
+fn unassigned_participants_are_waitlisted_and_prioritized() {
 	new_test_ext().execute_with(|| {
-		let cid0 = CommunityIdentifier::default();
-		let cid1 = CommunityIdentifier::new(
-			Location::new(Degree::from_num(1f64), Degree::from_num(1f64)),
-			Vec::<i64>::new(),
-		)
-		.unwrap();
-
-		let mut cindex = 5;
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountId::from(AccountKeyring::Alice);
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
 
-		IssuedRewards::<TestRuntime>::insert((cid0, cindex), 0, MeetupResult::Ok);
-		IssuedRewards::<TestRuntime>::insert((cid1, cindex), 0, MeetupResult::Ok);
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		let newbies = add_population(5, 6);
+		for newbie in &newbies {
+			assert_ok!(register(account_id(newbie), cid, None));
+		}
+		assert_ok!(EncointerCeremonies::set_waitlist_auto_registration(
+			RuntimeOrigin::signed(account_id(&newbies[2])),
+			cid,
+			true
+		));
 
-		let timeout = 1;
+		run_to_next_phase();
+		// Assigning
+		// only 6 / 3 = 2 newbies can be assigned
+		assert_eq!(EncointerCeremonies::assignment_counts((cid, cindex)).newbies, 2);
 		assert_eq!(
-			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
-			vec![]
+			EncointerCeremonies::waitlist((cid, cindex)).into_inner(),
+			newbies[2..]
+				.iter()
+				.map(|newbie| (account_id(newbie), ParticipantType::Newbie))
+				.collect::<Vec<_>>()
 		);
+		assert!(event_deposited::<TestRuntime>(
+			Event::ParticipantWaitlisted {
+				cid,
+				cindex,
+				participant_type: ParticipantType::Newbie,
+				account: account_id(&newbies[4]),
+			}
+			.into()
+		));
 
-		cindex += 1;
-		IssuedRewards::<TestRuntime>::insert((cid0, cindex), 0, MeetupResult::Ok);
+		run_to_next_phase();
+		run_to_next_phase();
+		// Registering
+		let cindex = cindex + 1;
 		assert_eq!(
-			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
-			vec![]
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&newbies[2])),
+			None
 		);
-
-		cindex += 1;
+		EncointerCeremonies::on_idle(System::block_number(), Weight::MAX);
+		assert_eq!(EncointerCeremonies::waitlist_registration_cursor(), None);
 		assert_eq!(
-			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
-			vec![cid1]
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&newbies[2])),
+			Some(ParticipantType::Newbie)
 		);
-
-		cindex += 1;
 		assert_eq!(
-			EncointerCeremonies::update_inactivity_counters(cindex, timeout, vec![cid0, cid1]),
-			vec![cid0, cid1]
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&newbies[3])),
+			None
 		);
+
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		let latecomers = add_population(2, 11);
+		for newbie in latecomers.iter().chain(&newbies[3..4]) {
+			assert_ok!(register(account_id(newbie), cid, None));
+		}
+
+		run_to_next_phase();
+		// Assigning
+		// waitlisted newbies are assigned before new ones
+		for newbie in &newbies[2..4] {
+			assert!(
+				EncointerCeremonies::get_meetup_index((cid, cindex), &account_id(newbie)).is_some()
+			);
+		}
+		for newbie in &latecomers {
+			assert!(
+				EncointerCeremonies::get_meetup_index((cid, cindex), &account_id(newbie)).is_none()
+			);
+			assert!(EncointerCeremonies::waitlist((cid, cindex))
+				.contains(&(account_id(newbie), ParticipantType::Newbie)));
+		}
 	});
 }
 
 #[test]
-fn purge_inactive_communities_works() {
+fn waitlist_is_bounded() {
 	new_test_ext().execute_with(|| {
-		System::set_block_number(System::block_number() + 1); // this is needed to assert events
 		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountId::from(AccountKeyring::Alice);
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
 
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
-		);
-
-		// inactivity counter is 1, beacuse of a full ceremony cycle in the bootstrapping ceremony
-		// without any rewards being claimed
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 1);
+		register_alice_bob_ferdie(cid);
+		register_charlie_dave_eve(cid);
+		let newbies = add_population(20, 6);
+		for newbie in &newbies {
+			assert_ok!(register(account_id(newbie), cid, None));
+		}
 
 		run_to_next_phase();
 		// Assigning
+		assert_eq!(EncointerCeremonies::assignment_counts((cid, cindex)).newbies, 2);
+		let max_waitlist_size =
+			<<TestRuntime as Config>::MaxWaitlistSize as Get<u32>>::get() as usize;
+		assert_eq!(EncointerCeremonies::waitlist((cid, cindex)).len(), max_waitlist_size);
 		assert_eq!(
-			event_at_index::<TestRuntime>(get_num_events::<TestRuntime>() - 2),
-			Some(Event::InactivityCounterUpdated(cid, 2).into())
+			EncointerCeremonies::waitlist((cid, cindex)).last(),
+			Some(&(account_id(&newbies[1 + max_waitlist_size]), ParticipantType::Newbie))
 		);
+	});
+}
 
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
-		);
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 2);
+#[test]
+fn waitlisted_reputables_stay_reputable_only_within_the_reputation_lifetime() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let alice = AccountId::from(AccountKeyring::Alice);
+		assert!(EncointerBalances::issue(cid, &alice, NominalIncome::from_num(1)).is_ok());
+		ReputationLifetime::<TestRuntime>::put(2);
+		let waitlisted = add_population(2, 6);
+		let (zoran, yuri) = (waitlisted[0].clone(), waitlisted[1].clone());
+		for (participant, reputation_cindex) in [(&zoran, cindex - 1), (&yuri, cindex - 2)] {
+			let account = account_id(participant);
+			<Waitlist<TestRuntime>>::mutate((cid, cindex), |waitlist| {
+				waitlist.try_push((account.clone(), ParticipantType::Reputable)).unwrap()
+			});
+			<LinkedReputations<TestRuntime>>::insert(
+				(cid, cindex),
+				&account,
+				((cid, reputation_cindex), account.clone()),
+			);
+			assert_ok!(EncointerCeremonies::set_waitlist_auto_registration(
+				RuntimeOrigin::signed(account),
+				cid,
+				true
+			));
+		}
 
-		// issued rewards will cause inactivity counter to go to 0 in the next cycle
-		IssuedRewards::<TestRuntime>::insert(
-			(cid, EncointerScheduler::current_ceremony_index()),
-			0,
-			MeetupResult::Ok,
-		);
 		run_to_next_phase();
 		run_to_next_phase();
 		run_to_next_phase();
+		// Registering
+		let cindex = cindex + 1;
+		EncointerCeremonies::on_idle(System::block_number(), Weight::MAX);
 
 		assert_eq!(
-			event_at_index::<TestRuntime>(get_num_events::<TestRuntime>() - 2),
-			Some(Event::InactivityCounterUpdated(cid, 0).into())
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&zoran)),
+			Some(ParticipantType::Reputable)
 		);
-
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		assert_eq!(
+			EncointerCeremonies::linked_reputations((cid, cindex), account_id(&zoran)),
+			Some(((cid, cindex - 2), account_id(&zoran)))
 		);
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 0);
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
-
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		// the reputation linked by yuri has expired
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&yuri)),
+			Some(ParticipantType::Newbie)
 		);
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 1);
+	});
+}
 
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
+/// Fills the registries of a ceremony with participants who are otherwise unknown.
+fn fill_registries(cc: CommunityCeremony, registered: AssignmentCount) -> [Vec<AccountId>; 4] {
+	let accounts = |kind: u8, n: u64| -> Vec<AccountId> {
+		(1..=n)
+			.map(|i| {
+				let mut raw = [kind; 32];
+				raw[..8].copy_from_slice(&i.to_le_bytes());
+				AccountId::from(raw)
+			})
+			.collect()
+	};
+	let bootstrappers = accounts(1, registered.bootstrappers);
+	let reputables = accounts(2, registered.reputables);
+	let endorsees = accounts(3, registered.endorsees);
+	let newbies = accounts(4, registered.newbies);
+	for (i, p) in (1..).zip(bootstrappers.iter()) {
+		BootstrapperRegistry::<TestRuntime>::insert(cc, i, p);
+		BootstrapperIndex::<TestRuntime>::insert(cc, p, i);
+	}
+	for (i, p) in (1..).zip(reputables.iter()) {
+		ReputableRegistry::<TestRuntime>::insert(cc, i, p);
+		ReputableIndex::<TestRuntime>::insert(cc, p, i);
+	}
+	for (i, p) in (1..).zip(endorsees.iter()) {
+		EndorseeRegistry::<TestRuntime>::insert(cc, i, p);
+		EndorseeIndex::<TestRuntime>::insert(cc, p, i);
+	}
+	for (i, p) in (1..).zip(newbies.iter()) {
+		NewbieRegistry::<TestRuntime>::insert(cc, i, p);
+		NewbieIndex::<TestRuntime>::insert(cc, p, i);
+	}
+	BootstrapperCount::<TestRuntime>::insert(cc, registered.bootstrappers);
+	ReputableCount::<TestRuntime>::insert(cc, registered.reputables);
+	EndorseeCount::<TestRuntime>::insert(cc, registered.endorsees);
+	NewbieCount::<TestRuntime>::insert(cc, registered.newbies);
+	[bootstrappers, reputables, endorsees, newbies]
+}
 
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
-		);
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 2);
+#[test]
+fn get_assignment_forecast_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		assert_eq!(EncointerCommunities::get_locations(&cid).len(), 1);
 
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
+		let registered =
+			AssignmentCount { bootstrappers: 6, reputables: 20, endorsees: 5, newbies: 20 };
+		fill_registries((cid, cindex), registered);
 
-		assert!(
-			<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
+		// one meetup only seats 4 reputables besides the bootstrappers
+		let assigned =
+			AssignmentCount { bootstrappers: 6, reputables: 4, endorsees: 0, newbies: 0 };
+		assert_eq!(
+			EncointerCeremonies::get_assignment_forecast(cid),
+			Some(AssignmentForecast {
+				registered,
+				assigned,
+				missing_locations: 4,
+				waitlisted: AssignmentCount::default(),
+			})
 		);
-		// now the inactivity counter is 3 == inactivity_timeout, so in the next cycle the community will be purged
-		assert_eq!(EncointerCeremonies::inactivity_counters(cid).unwrap(), 3);
 
+		for location in create_locations(5) {
+			assert_ok!(EncointerCommunities::add_location(
+				RuntimeOrigin::signed(AccountKeyring::Alice.to_account_id()),
+				cid,
+				location
+			));
+		}
+		// newbies are still limited by the `MeetupNewbieLimitDivider`
+		assert_eq!(
+			EncointerCeremonies::get_assignment_forecast(cid),
+			Some(AssignmentForecast {
+				registered,
+				assigned: AssignmentCount {
+					bootstrappers: 6,
+					reputables: 20,
+					endorsees: 5,
+					newbies: 10
+				},
+				missing_locations: 0,
+				waitlisted: AssignmentCount::default(),
+			})
+		);
+	});
+}
+
+#[test]
+fn get_assignment_forecast_is_none_outside_of_registering() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		assert!(EncointerCeremonies::get_assignment_forecast(cid).is_some());
 		run_to_next_phase();
+		// Assigning
+		assert_eq!(EncointerCeremonies::get_assignment_forecast(cid), None);
 		run_to_next_phase();
-		run_to_next_phase();
-
-		// here community gets purged
-		assert!(
-			!<encointer_communities::Pallet<TestRuntime>>::community_identifiers().contains(&cid)
-		);
+		// Attesting
+		assert_eq!(EncointerCeremonies::get_assignment_forecast(cid), None);
 	});
 }
 
 #[test]
-fn get_meetup_index_works() {
+fn get_assignment_forecast_considers_regions() {
 	new_test_ext().execute_with(|| {
-		let cid = perform_bootstrapping_ceremony(None, 1);
-		let cindex = EncointerScheduler::current_ceremony_index();
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		// three locations at home, two away
+		add_locations(cid, &[0.01, 0.02, 0.03, 1.0, 1.01]);
+		let cc = (cid, EncointerScheduler::current_ceremony_index());
+		let home = region_of(Location::new(Degree::from_num(0.01), Degree::from_num(0.01)));
+		let away = region_of(Location::new(Degree::from_num(1.0), Degree::from_num(1.0)));
+
+		let registered =
+			AssignmentCount { bootstrappers: 3, reputables: 42, endorsees: 0, newbies: 0 };
+		let [bootstrappers, reputables, ..] = fill_registries(cc, registered);
+		for p in bootstrappers.iter().chain(reputables.iter().take(17)) {
+			PreferredRegions::<TestRuntime>::insert(cc, p, home.clone());
+		}
+		for p in reputables.iter().skip(17) {
+			PreferredRegions::<TestRuntime>::insert(cc, p, away.clone());
+		}
 
-		let participants = add_population(4, 0);
-		let p1 = account_id(&participants[0]);
-		let p2 = account_id(&participants[1]);
-		let p3 = account_id(&participants[2]);
-		let p4 = account_id(&participants[3]);
+		// the two locations of the away region only seat 20 of its 25 reputables, although
+		// there would be enough seats at home
+		let forecast = EncointerCeremonies::get_assignment_forecast(cid).unwrap();
+		assert_eq!(
+			forecast,
+			AssignmentForecast {
+				registered,
+				assigned: AssignmentCount {
+					bootstrappers: 3,
+					reputables: 37,
+					endorsees: 0,
+					newbies: 0
+				},
+				missing_locations: 1,
+				waitlisted: AssignmentCount::default(),
+			}
+		);
 
-		MeetupCount::<TestRuntime>::insert((cid, cindex), 10);
+		run_to_next_phase();
+		// Assigning
+		assert_eq!(EncointerCeremonies::regional_assignment_count(cc), 2);
+		assert_eq!(EncointerCeremonies::assignment_counts(cc), forecast.assigned);
+	});
+}
 
-		BootstrapperIndex::<TestRuntime>::insert((cid, cindex), p1.clone(), 1);
-		AssignmentCounts::<TestRuntime>::insert(
-			(cid, cindex),
-			AssignmentCount { bootstrappers: 1, reputables: 0, endorsees: 0, newbies: 0 },
+#[test]
+fn get_assignment_forecast_counts_waitlisted_participants() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		let registered =
+			AssignmentCount { bootstrappers: 3, reputables: 0, endorsees: 0, newbies: 2 };
+		let [_, _, _, newbies] = fill_registries((cid, cindex), registered);
+
+		let waitlisted_newbie = account_id(&sr25519::Pair::from_seed(&[1; 32]));
+		let waitlisted_endorsee = account_id(&sr25519::Pair::from_seed(&[2; 32]));
+		let opted_out = account_id(&sr25519::Pair::from_seed(&[3; 32]));
+		Waitlist::<TestRuntime>::insert(
+			(cid, cindex - 1),
+			BoundedVec::try_from(vec![
+				(waitlisted_newbie.clone(), ParticipantType::Newbie),
+				(newbies[1].clone(), ParticipantType::Newbie),
+				(waitlisted_endorsee.clone(), ParticipantType::Endorsee),
+				(opted_out, ParticipantType::Newbie),
+			])
+			.unwrap(),
 		);
+		for p in [&waitlisted_newbie, &newbies[1], &waitlisted_endorsee] {
+			WaitlistAutoRegistration::<TestRuntime>::insert(cid, p, true);
+		}
 
-		ReputableIndex::<TestRuntime>::insert((cid, cindex), p2.clone(), 1);
-
-		EndorseeIndex::<TestRuntime>::insert((cid, cindex), p3.clone(), 3);
-		NewbieIndex::<TestRuntime>::insert((cid, cindex), p4.clone(), 4);
+		// the waitlisted participants yet to be registered are counted, those who have opted out
+		// are not
+		let forecast = EncointerCeremonies::get_assignment_forecast(cid).unwrap();
+		assert_eq!(
+			forecast.registered,
+			AssignmentCount { bootstrappers: 3, reputables: 0, endorsees: 1, newbies: 3 }
+		);
+		assert_eq!(
+			forecast.waitlisted,
+			AssignmentCount { bootstrappers: 0, reputables: 0, endorsees: 1, newbies: 2 }
+		);
+		// the newbie limit only admits one newbie, who is a waitlisted one
+		assert_eq!(forecast.assigned.newbies, 1);
+	});
+}
 
-		Assignments::<TestRuntime>::insert(
+#[test]
+fn unassigned_participants_are_reported() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+		fill_registries(
 			(cid, cindex),
-			Assignment {
-				bootstrappers_reputables: AssignmentParams { m: 2, s1: 1, s2: 1 },
-				endorsees: AssignmentParams { m: 5, s1: 2, s2: 3 },
-				newbies: AssignmentParams { m: 5, s1: 2, s2: 3 },
-				locations: AssignmentParams { m: 9, s1: 8, s2: 7 },
-			},
+			AssignmentCount { bootstrappers: 6, reputables: 20, endorsees: 5, newbies: 20 },
 		);
 
-		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p1).unwrap(), 2);
-		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p2), None);
-		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p3), None);
-		assert_eq!(EncointerCeremonies::get_meetup_index((cid, cindex), &p4), None);
+		run_to_next_phase();
+		// Assigning
+		assert!(event_deposited::<TestRuntime>(
+			Event::ParticipantsNotAssigned {
+				cid,
+				cindex,
+				unassigned: AssignmentCount {
+					bootstrappers: 0,
+					reputables: 16,
+					endorsees: 5,
+					newbies: 20
+				},
+			}
+			.into()
+		));
 	});
 }
 
@@ -2705,6 +4950,8 @@ fn unregister_participant_fails_with_reputables_and_wrong_reputation() {
 			Reputation::VerifiedLinked
 		);
 
+		// registered before the linked reputation has been recorded
+		LinkedReputations::<TestRuntime>::remove((cid, cindex), &alice);
 		assert_err!(
 			EncointerCeremonies::unregister_participant(
 				RuntimeOrigin::signed(alice.clone()),
@@ -2731,6 +4978,54 @@ fn unregister_participant_fails_with_reputables_and_wrong_reputation() {
 	})
 }
 
+#[test]
+fn unregister_participant_unlinks_reputation_of_endorsees() {
+	new_test_ext().execute_with(|| {
+		let cid = perform_bootstrapping_ceremony(None, 1);
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let cindex = EncointerScheduler::current_ceremony_index();
+
+		let zoran = sr25519::Pair::from_seed_slice(&[9u8; 32]).unwrap();
+		assert!(
+			EncointerBalances::issue(cid, &account_id(&zoran), NominalIncome::from_num(1)).is_ok()
+		);
+		EncointerCeremonies::fake_reputation(
+			(cid2, cindex - 1),
+			&account_id(&zoran),
+			Reputation::VerifiedUnlinked,
+		);
+		// reputation from other communities only grants endorsee status
+		let proof = prove_attendance(account_id(&zoran), cid2, cindex - 1, &zoran);
+		assert_ok!(register(account_id(&zoran), cid, Some(proof)));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&zoran)),
+			Some(ParticipantType::Endorsee)
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid2, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedLinked
+		);
+
+		assert_ok!(EncointerCeremonies::unregister_participant(
+			RuntimeOrigin::signed(account_id(&zoran)),
+			cid,
+			None
+		));
+		assert_eq!(
+			EncointerCeremonies::get_participant_type((cid, cindex), &account_id(&zoran)),
+			None
+		);
+		assert_eq!(
+			EncointerCeremonies::participant_reputation((cid2, cindex - 1), account_id(&zoran)),
+			Reputation::VerifiedUnlinked
+		);
+		assert_eq!(
+			EncointerCeremonies::linked_reputations((cid, cindex), account_id(&zoran)),
+			None
+		);
+	})
+}
+
 #[test]
 fn unregister_participant_works_with_newbies() {
 	new_test_ext().execute_with(|| {
@@ -3289,6 +5584,47 @@ fn has_reputation_works() {
 	});
 }
 
+#[test]
+fn reputation_score_works() {
+	new_test_ext().execute_with(|| {
+		let cid = register_test_community::<TestRuntime>(None, 0.0, 0.0);
+		let alice = account_id(&AccountKeyring::Alice.pair());
+		EncointerCeremonies::do_set_reputation_lifetime(3).unwrap();
+
+		for _ in 0..12 {
+			run_to_next_phase();
+		}
+		assert_eq!(EncointerScheduler::current_ceremony_index(), 5);
+		assert_eq!(EncointerCeremonies::reputation_score(&alice, &cid), ReputationScore::default());
+
+		// outside of the reputation lifetime
+		EncointerCeremonies::fake_reputation((cid, 1), &alice, Reputation::VerifiedUnlinked);
+		assert_eq!(EncointerCeremonies::reputation_score(&alice, &cid), ReputationScore::default());
+
+		EncointerCeremonies::fake_reputation((cid, 2), &alice, Reputation::VerifiedLinked);
+		assert_eq!(
+			EncointerCeremonies::reputation_score(&alice, &cid),
+			ReputationScore { score: 1, streak: 0, verified_count: 1 }
+		);
+
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedUnlinked);
+		assert_eq!(
+			EncointerCeremonies::reputation_score(&alice, &cid),
+			ReputationScore { score: 5, streak: 1, verified_count: 2 }
+		);
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		assert_eq!(
+			EncointerCeremonies::reputation_score(&alice, &cid),
+			ReputationScore { score: 9, streak: 3, verified_count: 3 }
+		);
+		assert_eq!(
+			EncointerCeremonies::get_reputation_score(cid, &alice),
+			EncointerCeremonies::reputation_score(&alice, &cid)
+		);
+	});
+}
+
 #[test]
 fn is_endorsed_works() {
 	new_test_ext().execute_with(|| {
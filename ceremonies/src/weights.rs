@@ -23,6 +23,19 @@ limitations under the License.
 //! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
 //! DATE: 2022-08-10, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! PLACEHOLDERS: `choose_meetup_time`, `register_participant_in_region`, `add_partner_community`,
+//! `remove_partner_community`, `endorse_and_register_batch` and `reschedule_meetup` have been
+//! added since the above run. Their weights are estimated from the storage accesses of the
+//! extrinsics and have to be regenerated with the command below before being used in production.
+//!
+//! NATIVE: `claim_rewards`, `attest_meetup`, `set_meetup_validation_policy`,
+//! `set_waitlist_auto_registration` and `cancel_meetup` have been regenerated on 2026-10-19 by
+//! running their benchmarks natively against the pallet's mock runtime on the in-memory
+//! benchmarking state of `sc-client-db` (STEPS: `50`, REPEAT: 20, ANALYSIS: min squares (IQR)),
+//! with a `CommunityTreasuryShare` of 10%. Native execution is faster than the Wasm execution of
+//! the above run, so these weights have to be regenerated with the command below as well before
+//! being used in production.
 
 // Executed Command:
 // target/release/encointer-node-notee
@@ -64,6 +77,16 @@ pub trait WeightInfo {
 	fn set_time_tolerance() -> Weight;
 	fn set_location_tolerance() -> Weight;
 	fn purge_community_ceremony() -> Weight;
+	fn choose_meetup_time() -> Weight;
+	fn attest_meetup(n: u32) -> Weight;
+	fn set_meetup_validation_policy() -> Weight;
+	fn add_partner_community() -> Weight;
+	fn remove_partner_community() -> Weight;
+	fn set_waitlist_auto_registration() -> Weight;
+	fn cancel_meetup(n: u32) -> Weight;
+	fn reschedule_meetup() -> Weight;
+	fn register_participant_in_region() -> Weight;
+	fn endorse_and_register_batch(n: u32, f: u32) -> Weight;
 }
 
 /// Weights for pallet_encointer_ceremonies using the Encointer solo chain node and recommended hardware.
@@ -95,9 +118,9 @@ impl<T: frame_system::Config> WeightInfo for EncointerWeight<T> {
 			.saturating_add(RocksDbWeight::get().writes(9))
 	}
 	fn claim_rewards() -> Weight {
-		Weight::from_parts(816_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(62))
-			.saturating_add(T::DbWeight::get().writes(22))
+		Weight::from_parts(794_689_000, 0)
+			.saturating_add(T::DbWeight::get().reads(78))
+			.saturating_add(T::DbWeight::get().writes(33))
 	}
 	fn set_inactivity_timeout() -> Weight {
 		Weight::from_parts(29_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
@@ -125,6 +148,72 @@ impl<T: frame_system::Config> WeightInfo for EncointerWeight<T> {
 	fn purge_community_ceremony() -> Weight {
 		Weight::from_parts(139_000_000, 0).saturating_add(T::DbWeight::get().writes(12))
 	}
+	// placeholder, see module docs
+	fn choose_meetup_time() -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(16))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn attest_meetup(n: u32) -> Weight {
+		Weight::from_parts(287_137_043, 0)
+			.saturating_add(Weight::from_parts(149_192_065, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(30))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	fn set_meetup_validation_policy() -> Weight {
+		Weight::from_parts(20_077_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// placeholder, see module docs
+	fn add_partner_community() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(12))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	// placeholder, see module docs
+	fn remove_partner_community() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn set_waitlist_auto_registration() -> Weight {
+		Weight::from_parts(15_816_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1))
+			.saturating_add(T::DbWeight::get().writes(1))
+	}
+	fn cancel_meetup(n: u32) -> Weight {
+		Weight::from_parts(101_413_263, 0)
+			.saturating_add(Weight::from_parts(14_775_734, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// placeholder, see module docs
+	fn reschedule_meetup() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(3))
+	}
+	// placeholder, see module docs
+	fn register_participant_in_region() -> Weight {
+		Weight::from_parts(221_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
+	// placeholder, see module docs
+	fn endorse_and_register_batch(n: u32, f: u32) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(2_250_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(3))
+			.saturating_add(T::DbWeight::get().reads((24_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().reads((14_u64).saturating_mul(f.into())))
+			.saturating_add(T::DbWeight::get().writes((12_u64).saturating_mul(n.into())))
+	}
 }
 
 // For tests
@@ -155,9 +244,9 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(9))
 	}
 	fn claim_rewards() -> Weight {
-		Weight::from_parts(816_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(62))
-			.saturating_add(RocksDbWeight::get().writes(22))
+		Weight::from_parts(794_689_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(78))
+			.saturating_add(RocksDbWeight::get().writes(33))
 	}
 	fn set_inactivity_timeout() -> Weight {
 		Weight::from_parts(29_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
@@ -185,4 +274,70 @@ impl WeightInfo for () {
 	fn purge_community_ceremony() -> Weight {
 		Weight::from_parts(139_000_000, 0).saturating_add(RocksDbWeight::get().writes(12))
 	}
+	// placeholder, see module docs
+	fn choose_meetup_time() -> Weight {
+		Weight::from_parts(95_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(16))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn attest_meetup(n: u32) -> Weight {
+		Weight::from_parts(287_137_043, 0)
+			.saturating_add(Weight::from_parts(149_192_065, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(30))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	fn set_meetup_validation_policy() -> Weight {
+		Weight::from_parts(20_077_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// placeholder, see module docs
+	fn add_partner_community() -> Weight {
+		Weight::from_parts(45_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(12))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	// placeholder, see module docs
+	fn remove_partner_community() -> Weight {
+		Weight::from_parts(27_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn set_waitlist_auto_registration() -> Weight {
+		Weight::from_parts(15_816_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1))
+			.saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn cancel_meetup(n: u32) -> Weight {
+		Weight::from_parts(101_413_263, 0)
+			.saturating_add(Weight::from_parts(14_775_734, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+	}
+	// placeholder, see module docs
+	fn reschedule_meetup() -> Weight {
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14))
+			.saturating_add(RocksDbWeight::get().writes(3))
+	}
+	// placeholder, see module docs
+	fn register_participant_in_region() -> Weight {
+		Weight::from_parts(221_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
+	// placeholder, see module docs
+	fn endorse_and_register_batch(n: u32, f: u32) -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(Weight::from_parts(2_250_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(Weight::from_parts(120_000_000, 0).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads(3))
+			.saturating_add(RocksDbWeight::get().reads((24_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().reads((14_u64).saturating_mul(f.into())))
+			.saturating_add(RocksDbWeight::get().writes((12_u64).saturating_mul(n.into())))
+	}
 }
@@ -274,16 +274,7 @@ pub mod pallet {
 			demurrage: Demurrage,
 		) -> DispatchResultWithPostInfo {
 			T::CommunityMaster::ensure_origin(origin)?;
-
-			Self::ensure_cid_exists(&cid)?;
-
-			<encointer_balances::Pallet<T>>::set_demurrage(&cid, demurrage)
-				.map_err(|_| <Error<T>>::InvalidDemurrage)?;
-
-			info!(target: LOG, " updated demurrage for cid: {:?}", cid);
-			Self::deposit_event(Event::DemurrageUpdated(cid, demurrage));
-
-			Ok(().into())
+			Self::do_update_demurrage(cid, demurrage)
 		}
 
 		#[pallet::call_index(5)]
@@ -472,6 +463,21 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+	pub fn do_update_demurrage(
+		cid: CommunityIdentifier,
+		demurrage: Demurrage,
+	) -> DispatchResultWithPostInfo {
+		Self::ensure_cid_exists(&cid)?;
+
+		<encointer_balances::Pallet<T>>::set_demurrage(&cid, demurrage)
+			.map_err(|_| <Error<T>>::InvalidDemurrage)?;
+
+		info!(target: LOG, " updated demurrage for cid: {:?}", cid);
+		Self::deposit_event(Event::DemurrageUpdated(cid, demurrage));
+
+		Ok(().into())
+	}
+
 	pub fn do_update_nominal_income(
 		cid: CommunityIdentifier,
 		nominal_income: NominalIncomeType,
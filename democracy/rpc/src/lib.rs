@@ -0,0 +1,121 @@
+// Copyright (c) 2019 Alain Brenzikofer
+// This file is part of Encointer
+//
+// Encointer is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// Encointer is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with Encointer.  If not, see <http://www.gnu.org/licenses/>.
+
+use encointer_rpc::Error;
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_api::{Decode, Encode, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+use encointer_democracy_rpc_runtime_api::DemocracyApi as DemocracyRuntimeApi;
+use encointer_primitives::{
+	ceremonies::CommunityCeremony,
+	democracy::{ProposalIdType, ProposalInfo, VoteEntry},
+};
+
+#[rpc(client, server)]
+pub trait DemocracyApi<BlockHash, AccountId, BlockNumber, Moment, Hash>
+where
+	AccountId: 'static + Encode + Decode + Send + Sync,
+	BlockNumber: 'static + Encode + Decode + Send + Sync,
+	Moment: 'static + Encode + Decode + Send + Sync,
+	Hash: 'static + Encode + Decode + Send + Sync,
+{
+	#[method(name = "encointer_getProposals", blocking)]
+	fn get_proposals(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<ProposalInfo<AccountId, BlockNumber, Moment, Hash>>>;
+
+	#[method(name = "encointer_getEligibleReputations", blocking)]
+	fn get_eligible_reputations(
+		&self,
+		proposal_id: ProposalIdType,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<CommunityCeremony>>;
+
+	#[method(name = "encointer_getDelegatedReputations", blocking)]
+	fn get_delegated_reputations(
+		&self,
+		proposal_id: ProposalIdType,
+		delegate: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<VoteEntry<AccountId>>>;
+}
+
+pub struct DemocracyRpc<Client, Block, AccountId, BlockNumber, Moment, Hash> {
+	client: Arc<Client>,
+	_marker: std::marker::PhantomData<(Block, AccountId, BlockNumber, Moment, Hash)>,
+}
+
+impl<Client, Block, AccountId, BlockNumber, Moment, Hash>
+	DemocracyRpc<Client, Block, AccountId, BlockNumber, Moment, Hash>
+{
+	/// Create new `Democracy` instance with the given reference to the client.
+	pub fn new(client: Arc<Client>) -> Self {
+		DemocracyRpc { client, _marker: Default::default() }
+	}
+}
+
+impl<Client, Block, AccountId, BlockNumber, Moment, Hash>
+	DemocracyApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Moment, Hash>
+	for DemocracyRpc<Client, Block, AccountId, BlockNumber, Moment, Hash>
+where
+	AccountId: 'static + Clone + Encode + Decode + Send + Sync,
+	BlockNumber: 'static + Clone + Encode + Decode + Send + Sync,
+	Moment: 'static + Clone + Encode + Decode + Send + Sync,
+	Hash: 'static + Clone + Encode + Decode + Send + Sync,
+	Block: BlockT,
+	Client: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	Client::Api: DemocracyRuntimeApi<Block, AccountId, BlockNumber, Moment, Hash>,
+{
+	fn get_proposals(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<ProposalInfo<AccountId, BlockNumber, Moment, Hash>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(api.get_proposals(at).map_err(|e| Error::Runtime(e.into()))?)
+	}
+
+	fn get_eligible_reputations(
+		&self,
+		proposal_id: ProposalIdType,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<CommunityCeremony>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(api
+			.get_eligible_reputations(at, proposal_id, &account)
+			.map_err(|e| Error::Runtime(e.into()))?)
+	}
+
+	fn get_delegated_reputations(
+		&self,
+		proposal_id: ProposalIdType,
+		delegate: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<VoteEntry<AccountId>>> {
+		let api = self.client.runtime_api();
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		Ok(api
+			.get_delegated_reputations(at, proposal_id, &delegate)
+			.map_err(|e| Error::Runtime(e.into()))?)
+	}
+}
@@ -1,42 +1,139 @@
 use crate::{Pallet as EncointerDemocracy, *};
 use codec::Encode;
 use encointer_primitives::{
-	ceremonies::Reputation,
-	communities::CommunityIdentifier,
-	democracy::{ProposalState, Tally, Vote},
-	storage::{current_ceremony_index_key, global_reputation_count, participant_reputation},
+	ceremonies::{CommunityCeremony, Reputation},
+	common::{FromStr, PalletString},
+	communities::{CommunityIdentifier, Location},
+	democracy::{ProposalAccessPolicy, ProposalState, Tally, Vote},
+	storage::{
+		community_identifiers, current_ceremony_index_key, participant_reputation, storage_map_key,
+	},
 };
 use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite};
-use frame_support::{assert_ok, traits::OnInitialize, BoundedVec};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, QueryPreimage, ReservableCurrency, StorePreimage},
+	BoundedVec,
+};
 use frame_system::RawOrigin;
+use sp_runtime::{traits::Hash, Saturating};
 #[cfg(not(feature = "std"))]
 use sp_std::vec;
 
-fn advance_n_blocks<T: Config>(n: u64) {
-	for _ in 0..n {
-		frame_system::Pallet::<T>::set_block_number(
-			frame_system::Pallet::<T>::block_number() + 1u32.into(),
+/// Fills the active proposals up to the maximum, leaving room for a single proposal
+fn fill_active_proposals<T: Config>() {
+	let other_proposals = (1000..)
+		.take(T::MaxActiveProposals::get().saturating_sub(1) as usize)
+		.collect::<Vec<ProposalIdType>>();
+	<ActiveProposals<T>>::put(BoundedVec::truncate_from(other_proposals));
+}
+
+/// Registers `n` communities, up to the maximum number of communities
+fn fake_communities<T: Config>(n: u32) -> Vec<CommunityIdentifier> {
+	let cids = (0..n.min(<T as encointer_communities::Config>::MaxCommunityIdentifiers::get()))
+		.map(|i| CommunityIdentifier::new(Location::default(), vec![i]).unwrap())
+		.collect::<Vec<CommunityIdentifier>>();
+	frame_support::storage::unhashed::put_raw(&community_identifiers(), &cids.encode());
+	cids
+}
+
+/// Gives `account` the oldest reputation which is still valid to delegate in the global scope
+fn fake_global_reputation<T: Config>(account: &T::AccountId) -> CommunityCeremony {
+	let cid = *fake_communities::<T>(1).last().unwrap();
+	let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index()
+		.saturating_sub(encointer_ceremonies::Pallet::<T>::reputation_lifetime());
+	frame_support::storage::unhashed::put_raw(
+		&participant_reputation((cid, cindex), account),
+		&Reputation::VerifiedUnlinked.encode(),
+	);
+	(cid, cindex)
+}
+
+/// Gives `voter` `r` reputations from different communities which are eligible to vote for a
+/// global proposal submitted in cycle 7
+fn fake_reputations<T: Config>(voter: &T::AccountId, r: u32) -> ReputationVecOf<T> {
+	let reputations = (0..r)
+		.map(|i| (CommunityIdentifier::new(Location::default(), vec![i]).unwrap(), 5))
+		.collect::<Vec<CommunityCeremony>>();
+	for community_ceremony in reputations.iter() {
+		frame_support::storage::unhashed::put_raw(
+			&participant_reputation(*community_ceremony, voter),
+			&Reputation::VerifiedUnlinked.encode(),
 		);
-		frame_system::Pallet::<T>::on_initialize(frame_system::Pallet::<T>::block_number());
 	}
+	BoundedVec::try_from(reputations).unwrap()
+}
+
+/// Lets `d` accounts, up to the maximum number of delegators, delegate to `voter` and gives
+/// them `d` reputations in total which are eligible to vote for a global proposal submitted in
+/// cycle 7
+fn fake_delegated_reputations<T: Config>(
+	voter: &T::AccountId,
+	d: u32,
+) -> DelegatedReputationVecOf<T> {
+	let num_delegators = T::MaxDelegatorsPerDelegate::get().max(1);
+	fake_communities::<T>(d);
+	let delegated_reputations = (0..d)
+		.map(|i| {
+			let delegator = account::<T::AccountId>("delegator", i % num_delegators, 1);
+			(delegator, (CommunityIdentifier::new(Location::default(), vec![i]).unwrap(), 5))
+		})
+		.collect::<Vec<VoteEntry<T::AccountId>>>();
+	for (delegator, community_ceremony) in delegated_reputations.iter() {
+		frame_support::storage::unhashed::put_raw(
+			&participant_reputation(*community_ceremony, delegator),
+			&Reputation::VerifiedUnlinked.encode(),
+		);
+		if !EncointerDemocracy::<T>::is_delegation_active(
+			delegator,
+			ProposalAccessPolicy::Global,
+			voter,
+		) {
+			assert_ok!(EncointerDemocracy::<T>::delegate(
+				RawOrigin::Signed(delegator.clone()).into(),
+				ProposalAccessPolicy::Global,
+				voter.clone(),
+				*community_ceremony
+			));
+		}
+	}
+	BoundedVec::try_from(delegated_reputations).unwrap()
+}
+
+fn fund_proposer<T: Config>(proposer: &T::AccountId) {
+	<T as Config>::Currency::make_free_balance_be(
+		proposer,
+		T::ProposalDeposit::get()
+			.saturating_mul(2u32.into())
+			.saturating_add(<T as Config>::Currency::minimum_balance()),
+	);
 }
 
 benchmarks! {
 
 	submit_proposal {
 		let zoran = account("zoran", 1, 1);
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		fund_proposer::<T>(&zoran);
+		fill_active_proposals::<T>();
+		// worst case: a deposit is reserved and the preimage of the call is held
+		let proposal_action = ProposalAction::Call(Bounded::Lookup {
+			hash: <T as frame_system::Config>::Hashing::hash(b"call"),
+			len: 100,
+		});
 		assert!(<Proposals<T>>::iter().next().is_none());
 	}: _(RawOrigin::Signed(zoran), proposal_action)
 	verify {
 		assert!(<Proposals<T>>::iter().next().is_some());
+		assert_eq!(EncointerDemocracy::<T>::active_proposals().len() as u32, T::MaxActiveProposals::get());
 	}
 
 	vote {
+		let r in 1 .. T::MaxReputationVecLength::get();
+		let d in 0 .. T::MaxReputationVecLength::get();
 		frame_support::storage::unhashed::put_raw(&current_ceremony_index_key(), &7u32.encode());
 
 		let zoran = account::<T::AccountId>("zoran", 1, 1);
-		let cid = CommunityIdentifier::default();
+		fund_proposer::<T>(&zoran);
 
 		let proposal_action = ProposalAction::SetInactivityTimeout(8);
 		assert_ok!(EncointerDemocracy::<T>::submit_proposal(
@@ -44,28 +141,26 @@ benchmarks! {
 			proposal_action
 		));
 
-		frame_support::storage::unhashed::put_raw(&participant_reputation((cid, 3), &zoran), &Reputation::VerifiedUnlinked.encode());
-		frame_support::storage::unhashed::put_raw(&participant_reputation((cid, 4), &zoran), &Reputation::VerifiedUnlinked.encode());
-		frame_support::storage::unhashed::put_raw(&participant_reputation((cid, 5), &zoran), &Reputation::VerifiedUnlinked.encode());
-		let reputation_vec: ReputationVecOf<T> = BoundedVec::try_from(vec![
-			(cid, 3),
-			(cid, 4),
-			(cid, 5),
-		]).unwrap();
+		let reputation_vec = fake_reputations::<T>(&zoran, r);
+		let delegated_reputation_vec = fake_delegated_reputations::<T>(&zoran, d);
 
 		assert!(<VoteEntries<T>>::iter().next().is_none());
-	}: _(RawOrigin::Signed(zoran.clone()),
+	}: vote_with_delegations(RawOrigin::Signed(zoran.clone()),
 	1,
 	Vote::Aye,
-	reputation_vec)
+	reputation_vec,
+	delegated_reputation_vec)
 	verify {
-		assert!(<VoteEntries<T>>::iter().next().is_some());
+		assert_eq!(EncointerDemocracy::<T>::tallies(1).unwrap().turnout, (r + d) as u128);
 	}
 
-	update_proposal_state {
+	change_vote {
+		let r in 1 .. T::MaxReputationVecLength::get();
+		let d in 0 .. T::MaxReputationVecLength::get();
 		frame_support::storage::unhashed::put_raw(&current_ceremony_index_key(), &7u32.encode());
+
 		let zoran = account::<T::AccountId>("zoran", 1, 1);
-		let cid = CommunityIdentifier::default();
+		fund_proposer::<T>(&zoran);
 
 		let proposal_action = ProposalAction::SetInactivityTimeout(8);
 		assert_ok!(EncointerDemocracy::<T>::submit_proposal(
@@ -73,18 +168,196 @@ benchmarks! {
 			proposal_action
 		));
 
+		let reputation_vec = fake_reputations::<T>(&zoran, r);
+		let delegated_reputation_vec = fake_delegated_reputations::<T>(&zoran, d);
 
-		Tallies::<T>::insert(1, Tally { turnout: 3, ayes: 3 });
+		assert_ok!(EncointerDemocracy::<T>::vote_with_delegations(
+			RawOrigin::Signed(zoran.clone()).into(),
+			1,
+			Vote::Aye,
+			reputation_vec.clone(),
+			delegated_reputation_vec.clone()
+		));
+	}: _(RawOrigin::Signed(zoran.clone()),
+	1,
+	Vote::Nay,
+	reputation_vec,
+	delegated_reputation_vec)
+	verify {
+		assert_eq!(EncointerDemocracy::<T>::tallies(1).unwrap().ayes, 0);
+	}
+
+	remove_vote {
+		let r in 1 .. T::MaxReputationVecLength::get();
+		let d in 0 .. T::MaxReputationVecLength::get();
+		frame_support::storage::unhashed::put_raw(&current_ceremony_index_key(), &7u32.encode());
+
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		fund_proposer::<T>(&zoran);
 
-		frame_support::storage::unhashed::put_raw(&global_reputation_count(5), &3u128.encode());
-		assert_eq!(EncointerDemocracy::<T>::proposals(1).unwrap().state, ProposalState::Ongoing);
-		EncointerDemocracy::<T>::update_proposal_state(RawOrigin::Signed(zoran.clone()).into(), 1).ok();
-		assert!(<EnactmentQueue<T>>::iter().next().is_none());
-		advance_n_blocks::<T>(21);
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		assert_ok!(EncointerDemocracy::<T>::submit_proposal(
+			RawOrigin::Signed(zoran.clone()).into(),
+			proposal_action
+		));
 
+		let reputation_vec = fake_reputations::<T>(&zoran, r);
+		let delegated_reputation_vec = fake_delegated_reputations::<T>(&zoran, d);
+
+		assert_ok!(EncointerDemocracy::<T>::vote_with_delegations(
+			RawOrigin::Signed(zoran.clone()).into(),
+			1,
+			Vote::Aye,
+			reputation_vec.clone(),
+			delegated_reputation_vec.clone()
+		));
+	}: _(RawOrigin::Signed(zoran.clone()),
+	1,
+	reputation_vec,
+	delegated_reputation_vec)
+	verify {
+		assert!(<VoteEntries<T>>::iter().next().is_none());
+	}
+
+	delegate {
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		let yuri = account::<T::AccountId>("yuri", 2, 2);
+		let scope = ProposalAccessPolicy::Global;
+		let reputation = fake_global_reputation::<T>(&zoran);
+		// worst case: zoran replaces an existing delegation and yuri has the maximum number of
+		// other active delegators
+		assert_ok!(EncointerDemocracy::<T>::delegate(
+			RawOrigin::Signed(zoran.clone()).into(),
+			scope,
+			account::<T::AccountId>("xenia", 3, 3),
+			reputation
+		));
+		let cindex = encointer_scheduler::Pallet::<T>::current_ceremony_index();
+		let delegators = (0..T::MaxDelegatorsPerDelegate::get().saturating_sub(1))
+			.map(|i| account::<T::AccountId>("delegator", i, 1))
+			.collect::<Vec<_>>();
+		for delegator in delegators.iter() {
+			<Delegations<T>>::insert(delegator, scope, Delegation { delegate: yuri.clone(), cindex });
+		}
+		<Delegators<T>>::insert(&yuri, scope, BoundedVec::truncate_from(delegators.clone()));
+	}: _(RawOrigin::Signed(zoran.clone()), scope, yuri.clone(), reputation)
+	verify {
+		assert_eq!(
+			EncointerDemocracy::<T>::delegators(yuri, scope).into_inner(),
+			[delegators, vec![zoran]].concat()
+		);
+	}
+
+	undelegate {
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		let yuri = account::<T::AccountId>("yuri", 2, 2);
+		let scope = ProposalAccessPolicy::Global;
+		let reputation = fake_global_reputation::<T>(&zoran);
+		assert_ok!(EncointerDemocracy::<T>::delegate(
+			RawOrigin::Signed(zoran.clone()).into(),
+			scope,
+			yuri.clone(),
+			reputation
+		));
+	}: _(RawOrigin::Signed(zoran.clone()), scope)
+	verify {
+		assert!(EncointerDemocracy::<T>::delegations(zoran, scope).is_none());
+	}
+
+	update_proposal_state {
+		// worst case: a community petition is approved, its deposit is refunded to a new account
+		// and it is removed from the full active proposals
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		let cid = CommunityIdentifier::default();
+		let proposal_action = ProposalAction::Petition(
+			Some(cid),
+			PalletString::from_str("QmPK1s3pNYLi9ERiq3BDxKa4XosgWwFRQUydHUtz4YgpqB").unwrap(),
+		);
+		let deposit = T::CommunityProposalDeposit::get();
+		assert_ok!(encointer_balances::Pallet::<T>::issue(
+			cid,
+			&EncointerDemocracy::<T>::deposit_escrow_account(1),
+			deposit
+		));
+		<ProposalDeposits<T>>::insert(1, ProposalDeposit::Community(cid, deposit));
+		frame_system::Pallet::<T>::set_block_number(T::ConfirmationPeriod::get() + 1u32.into());
+		<Proposals<T>>::insert(1, Proposal {
+			proposer: zoran.clone(),
+			start: frame_system::Pallet::<T>::block_number(),
+			start_cindex: 7,
+			action: proposal_action,
+			state: ProposalState::Confirming { since: 0u32.into() },
+		});
+		fill_active_proposals::<T>();
+		assert_ok!(<ActiveProposals<T>>::try_append(1));
+		Tallies::<T>::insert(1, Tally { turnout: 3, ayes: 3 });
+		frame_support::storage::unhashed::put_raw(
+			&storage_map_key("EncointerCeremonies", "ReputationCount", (cid, 5)),
+			&3u128.encode(),
+		);
 	}: _(RawOrigin::Signed(zoran), 1)
 	verify {
-		assert!(<EnactmentQueue<T>>::iter().next().is_some());
+		assert_eq!(EncointerDemocracy::<T>::proposals(1).unwrap().state, ProposalState::Approved);
+		assert!(EncointerDemocracy::<T>::proposal_deposits(1).is_none());
+		assert!(!EncointerDemocracy::<T>::active_proposals().contains(&1));
+	}
+
+	cancel_proposal {
+		// worst case: a proposal to dispatch a call whose preimage is held expires, the
+		// preimage is dropped, the deposit is slashed and the proposal is removed from the full
+		// active proposals
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		fund_proposer::<T>(&zoran);
+		fill_active_proposals::<T>();
+		let len = 256;
+		let hash = T::Preimages::note(vec![0; len as usize].into()).unwrap();
+		let bounded_call = Bounded::Lookup { hash, len };
+		assert_ok!(EncointerDemocracy::<T>::submit_proposal(
+			RawOrigin::Signed(zoran.clone()).into(),
+			ProposalAction::Call(bounded_call)
+		));
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + T::ProposalLifetime::get() + 1u32.into(),
+		);
+	}: update_proposal_state(RawOrigin::Signed(zoran), 1)
+	verify {
+		assert_eq!(EncointerDemocracy::<T>::proposals(1).unwrap().state, ProposalState::Cancelled);
+		assert!(EncointerDemocracy::<T>::proposal_deposits(1).is_none());
+		assert!(!EncointerDemocracy::<T>::active_proposals().contains(&1));
+	}
+
+	lookup_call {
+		// a remark of `l` bytes, whose preimage is noted unless it can be inlined
+		let l in 0 .. T::Preimages::MAX_LENGTH as u32 - 8;
+		let call: <T as Config>::RuntimeCall =
+			frame_system::Call::<T>::remark { remark: vec![0; l as usize] }.into();
+		let bounded_call = T::Preimages::bound(call).unwrap();
+	}: { assert_ok!(T::Preimages::peek(&bounded_call)); }
+
+	enact_proposal {
+		let zoran = account::<T::AccountId>("zoran", 1, 1);
+		fund_proposer::<T>(&zoran);
+		// worst case: the treasury pays out to a new account and the deposit is refunded
+		let cid = CommunityIdentifier::default();
+		let treasury = encointer_balances::Pallet::<T>::get_community_treasury_account(cid);
+		assert_ok!(encointer_balances::Pallet::<T>::issue(cid, &treasury, BalanceType::from_num(100)));
+		let proposal_action = ProposalAction::SpendCommunityTreasury(
+			cid,
+			account::<T::AccountId>("yuri", 2, 2),
+			BalanceType::from_num(60),
+		);
+		assert_ok!(<T as Config>::Currency::reserve(&zoran, T::ProposalDeposit::get()));
+		<ProposalDeposits<T>>::insert(1, ProposalDeposit::Native(T::ProposalDeposit::get()));
+		<Proposals<T>>::insert(1, Proposal {
+			proposer: zoran,
+			start: frame_system::Pallet::<T>::block_number(),
+			start_cindex: 7,
+			action: proposal_action,
+			state: ProposalState::Approved,
+		});
+	}: { assert_ok!(EncointerDemocracy::<T>::enact_proposal(1)); }
+	verify {
+		assert_eq!(EncointerDemocracy::<T>::proposals(1).unwrap().state, ProposalState::Enacted);
 	}
 }
 
@@ -20,20 +20,35 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use encointer_primitives::{
-	ceremonies::{CommunityCeremony, ReputationCountType},
-	democracy::{Proposal, ProposalAction, ProposalIdType, ReputationVec},
-	fixed::{transcendental::sqrt, types::U64F64},
+	balances::BalanceType,
+	ceremonies::{CommunityCeremony, ReputationCountType, ReputationScoreType},
+	common::validate_ipfs_cid,
+	communities::CommunityIdentifier,
+	democracy::{
+		DelegatedReputationVec, Delegation, Proposal, ProposalAction, ProposalActionIdentifier,
+		ProposalDeposit, ProposalIdType, ProposalInfo, ReputationVec, VoteEntry, VoteThreshold,
+	},
 	scheduler::{CeremonyIndexType, CeremonyPhaseType},
 };
 use encointer_scheduler::OnCeremonyPhaseChange;
-use frame_support::traits::Get;
+use frame_support::{
+	dispatch::{DispatchResult, GetDispatchInfo, PostDispatchInfo},
+	storage::with_storage_layer,
+	traits::{
+		Bounded, Currency, EnsureOrigin, Get, QueryPreimage, ReservableCurrency, StorePreimage,
+	},
+	PalletId,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use log::{info, warn};
+use sp_runtime::traits::{AccountIdConversion, Convert, Dispatchable, One, Saturating, Zero};
 pub use weights::WeightInfo;
 
 #[cfg(not(feature = "std"))]
 use sp_std::vec::Vec;
 
 // Logger target
-//const LOG: &str = "encointer";
+const LOG: &str = "encointer";
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -41,6 +56,40 @@ mod benchmarking;
 pub use pallet::*;
 
 type ReputationVecOf<T> = ReputationVec<<T as pallet::Config>::MaxReputationVecLength>;
+type DelegatedReputationVecOf<T> = DelegatedReputationVec<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet::Config>::MaxReputationVecLength,
+>;
+pub type BoundedCallOf<T> =
+	Bounded<<T as Config>::RuntimeCall, <T as frame_system::Config>::Hashing>;
+type ProposalActionOf<T> = ProposalAction<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_timestamp::Config>::Moment,
+	BoundedCallOf<T>,
+>;
+type ProposalOf<T> = Proposal<
+	<T as frame_system::Config>::AccountId,
+	BlockNumberFor<T>,
+	<T as pallet_timestamp::Config>::Moment,
+	BoundedCallOf<T>,
+>;
+pub type ProposalInfoOf<T> = ProposalInfo<
+	<T as frame_system::Config>::AccountId,
+	BlockNumberFor<T>,
+	<T as pallet_timestamp::Config>::Moment,
+	<T as frame_system::Config>::Hash,
+>;
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// Evaluates the tallies of all proposals with adaptive quorum biasing in favour of rejection
+pub struct PositiveTurnoutBiasForAll;
+impl Convert<ProposalActionIdentifier, VoteThreshold> for PositiveTurnoutBiasForAll {
+	fn convert(_: ProposalActionIdentifier) -> VoteThreshold {
+		VoteThreshold::PositiveTurnoutBias
+	}
+}
+
 #[allow(clippy::unused_unit)]
 #[frame_support::pallet]
 pub mod pallet {
@@ -49,7 +98,9 @@ pub mod pallet {
 	use frame_support::pallet_prelude::*;
 	use frame_system::pallet_prelude::*;
 
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::config]
@@ -61,6 +112,20 @@ pub mod pallet {
 	{
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
+		/// The runtime origin type, which must be able to represent the `EncointerDemocracy` origin
+		type RuntimeOrigin: From<RawOrigin>;
+
+		/// The runtime call type which can be proposed and is dispatched upon enactment
+		type RuntimeCall: Parameter
+			+ Dispatchable<
+				RuntimeOrigin = <Self as Config>::RuntimeOrigin,
+				PostInfo = PostDispatchInfo,
+			> + GetDispatchInfo
+			+ From<frame_system::Call<Self>>;
+
+		/// The preimage provider used to look up proposed calls that are too large to be inlined
+		type Preimages: QueryPreimage<H = Self::Hashing> + StorePreimage;
+
 		#[pallet::constant]
 		type MaxReputationVecLength: Get<u32>;
 		#[pallet::constant]
@@ -71,14 +136,86 @@ pub mod pallet {
 		type ProposalLifetimeCycles: Get<u32>; // ceil of the proposal lifetime in cycles
 		#[pallet::constant]
 		type MinTurnout: Get<u128>; // in permill
+		/// Selects the strategy by which the tally of a proposal is evaluated, depending on
+		/// its action
+		type TallyStrategies: Convert<ProposalActionIdentifier, VoteThreshold>;
+		/// Maximum number of proposals which can be `Ongoing` or `Confirming` at the same time
+		#[pallet::constant]
+		type MaxActiveProposals: Get<u32>;
+		/// Weight budget per block for advancing active proposals in `on_initialize`
+		#[pallet::constant]
+		type ProposalUpdateWeightBudget: Get<Weight>;
+		/// Weight budget per block for enacting approved proposals in `on_initialize`
+		#[pallet::constant]
+		type EnactmentWeightBudget: Get<Weight>;
+		/// The currency in which deposits for globally scoped proposals are reserved
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Deposit for globally scoped proposals, in native currency
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self>>;
+		/// Deposit for community scoped proposals, in the currency of that community
+		#[pallet::constant]
+		type CommunityProposalDeposit: Get<BalanceType>;
+		/// Id from which the escrow accounts holding the community currency deposits of proposals
+		/// are derived
+		#[pallet::constant]
+		type DepositEscrowPalletId: Get<PalletId>;
+		/// If true, community scoped proposals can only be submitted by accounts with reputation
+		/// in that community
+		#[pallet::constant]
+		type RequireReputationForCommunityProposals: Get<bool>;
+		/// Minimum recency weighted reputation score in the community required to submit community
+		/// scoped proposals if `RequireReputationForCommunityProposals` is set
+		#[pallet::constant]
+		type MinReputationScoreForCommunityProposals: Get<ReputationScoreType>;
+		/// Maximum number of accounts which can delegate to the same account for the same scope
+		#[pallet::constant]
+		type MaxDelegatorsPerDelegate: Get<u32>;
 		type WeightInfo: WeightInfo;
 	}
 
+	/// Origin with which approved `ProposalAction::Call` proposals are dispatched.
+	#[pallet::origin]
+	pub type Origin = RawOrigin;
+
+	#[derive(PartialEq, Eq, Clone, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+	pub enum RawOrigin {
+		/// the call has been approved by an encointer democracy proposal
+		EncointerDemocracy,
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
+		/// proposal submitted
+		ProposalSubmitted {
+			proposal_id: ProposalIdType,
+			proposal_action: ProposalActionOf<T>,
+			proposer: T::AccountId,
+		},
+		/// vote placed with `num_reputations` reputations, including delegated ones
+		VotePlaced {
+			proposal_id: ProposalIdType,
+			voter: T::AccountId,
+			vote: Vote,
+			num_reputations: u32,
+		},
+		/// proposal state changed
+		ProposalStateChanged {
+			proposal_id: ProposalIdType,
+			old_state: ProposalState<BlockNumberFor<T>>,
+			new_state: ProposalState<BlockNumberFor<T>>,
+		},
+		/// proposal cancelled, either because it timed out or because it was superseded
+		ProposalCancelled { proposal_id: ProposalIdType },
 		///  proposal enacted
 		ProposalEnacted { proposal_id: ProposalIdType },
+		/// the action of an approved proposal failed upon enactment
+		EnactmentFailed { proposal_id: ProposalIdType, reason: DispatchError },
+		/// voting power delegated
+		Delegated { delegator: T::AccountId, delegate: T::AccountId, scope: ProposalAccessPolicy },
+		/// delegation revoked
+		Undelegated { delegator: T::AccountId, scope: ProposalAccessPolicy },
 	}
 
 	#[pallet::error]
@@ -98,12 +235,33 @@ pub mod pallet {
 		AQBError,
 		/// cannot submit new proposal as a proposal of the same type is waiting for enactment
 		ProposalWaitingForEnactment,
+		/// none of the supplied reputations has been used to vote for this proposal
+		InexistentVote,
+		/// cannot submit new proposal as the maximum number of active proposals is reached
+		TooManyActiveProposals,
+		/// community scoped proposals require reputation in that community
+		NoReputationInCommunity,
+		/// the reputation score in the community is below the minimum for community scoped
+		/// proposals
+		ReputationScoreTooLow,
+		/// an account cannot delegate to itself
+		CannotDelegateToSelf,
+		/// only accounts with reputation in the scope can delegate
+		NoReputationToDelegate,
+		/// the delegate has reached the maximum number of delegators for this scope
+		TooManyDelegators,
+		/// no delegation exists for this scope
+		InexistentDelegation,
+		/// the text of a petition must be referenced by a valid IPFS cid
+		InvalidIpfsCid,
+		/// the weight of the proposal action exceeds the enactment weight budget of a block
+		EnactmentWeightExceeded,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn proposals)]
 	pub(super) type Proposals<T: Config> =
-		StorageMap<_, Blake2_128Concat, ProposalIdType, Proposal<BlockNumberFor<T>>, OptionQuery>;
+		StorageMap<_, Blake2_128Concat, ProposalIdType, ProposalOf<T>, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn proposal_count)]
@@ -125,6 +283,20 @@ pub mod pallet {
 		(),
 		ValueQuery,
 	>;
+
+	/// Direction of the votes in `VoteEntries`. Votes cast before directions were recorded have
+	/// no entry here and can neither be changed nor removed.
+	#[pallet::storage]
+	#[pallet::getter(fn vote_directions)]
+	pub(super) type VoteDirections<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIdType,
+		Blake2_128Concat,
+		VoteEntry<T::AccountId>,
+		Vote,
+		OptionQuery,
+	>;
 	// TODO set default value
 	#[pallet::storage]
 	#[pallet::getter(fn cancelled_at_block)]
@@ -136,6 +308,66 @@ pub mod pallet {
 	pub(super) type EnactmentQueue<T: Config> =
 		StorageMap<_, Blake2_128Concat, ProposalActionIdentifier, ProposalIdType, OptionQuery>;
 
+	/// Proposals in the `EnactmentQueue` which have been approved before this block are due for
+	/// enactment. Set when a registering phase starts and removed once they are all enacted.
+	#[pallet::storage]
+	#[pallet::getter(fn enactment_cutoff)]
+	pub(super) type EnactmentCutoff<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
+	/// Vote entries which have been cast by the stored delegate on behalf of the delegator
+	#[pallet::storage]
+	#[pallet::getter(fn delegated_vote_entries)]
+	pub(super) type DelegatedVoteEntries<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ProposalIdType,
+		Blake2_128Concat,
+		VoteEntry<T::AccountId>,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// Delegation per delegator and scope
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub(super) type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProposalAccessPolicy,
+		Delegation<T::AccountId>,
+		OptionQuery,
+	>;
+
+	/// Delegators per delegate and scope
+	#[pallet::storage]
+	#[pallet::getter(fn delegators)]
+	pub(super) type Delegators<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProposalAccessPolicy,
+		BoundedVec<T::AccountId, T::MaxDelegatorsPerDelegate>,
+		ValueQuery,
+	>;
+
+	/// Deposits held from proposers until their proposal is concluded.
+	///
+	/// The deposit is refunded once the proposal is approved, even if its enactment fails, or
+	/// superseded by another proposal, and slashed if it times out.
+	#[pallet::storage]
+	#[pallet::getter(fn proposal_deposits)]
+	pub(super) type ProposalDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, ProposalIdType, ProposalDeposit<BalanceOf<T>>, OptionQuery>;
+
+	/// Proposals which are `Ongoing` or `Confirming`, advanced automatically in `on_initialize`
+	#[pallet::storage]
+	#[pallet::getter(fn active_proposals)]
+	pub(super) type ActiveProposals<T: Config> =
+		StorageValue<_, BoundedVec<ProposalIdType, T::MaxActiveProposals>, ValueQuery>;
+
 	#[derive(frame_support::DefaultNoBound)]
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
@@ -151,38 +383,77 @@ pub mod pallet {
 		}
 	}
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: BlockNumberFor<T>) -> Weight {
+			Self::advance_active_proposals(T::ProposalUpdateWeightBudget::get())
+				.saturating_add(Self::enact_queued_proposals(T::EnactmentWeightBudget::get()))
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
 		#[pallet::weight((<T as Config>::WeightInfo::submit_proposal(), DispatchClass::Normal, Pays::Yes))]
 		pub fn submit_proposal(
 			origin: OriginFor<T>,
-			proposal_action: ProposalAction,
+			proposal_action: ProposalActionOf<T>,
 		) -> DispatchResultWithPostInfo {
-			if Self::enactment_queue(proposal_action.get_identifier()).is_some() {
+			if let ProposalAction::Petition(_, text) = &proposal_action {
+				validate_ipfs_cid(text).map_err(|_| Error::<T>::InvalidIpfsCid)?;
+			} else if Self::enactment_queue(proposal_action.get_identifier()).is_some() {
 				return Err(Error::<T>::ProposalWaitingForEnactment.into())
 			}
-			let _sender = ensure_signed(origin)?;
+			let sender = ensure_signed(origin)?;
+			if let ProposalAccessPolicy::Community(cid) = proposal_action.get_access_policy() {
+				if T::RequireReputationForCommunityProposals::get() {
+					let reputation_score =
+						<encointer_ceremonies::Pallet<T>>::reputation_score(&sender, &cid);
+					ensure!(
+						reputation_score.verified_count > 0,
+						Error::<T>::NoReputationInCommunity
+					);
+					ensure!(
+						reputation_score.score >= T::MinReputationScoreForCommunityProposals::get(),
+						Error::<T>::ReputationScoreTooLow
+					);
+				}
+			}
 			let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
 			let current_proposal_id = Self::proposal_count();
 			let next_proposal_id = current_proposal_id
 				.checked_add(1u128)
 				.ok_or(Error::<T>::ProposalIdOutOfBounds)?;
 			let current_block = frame_system::Pallet::<T>::block_number();
+			<ActiveProposals<T>>::try_append(next_proposal_id)
+				.map_err(|_| Error::<T>::TooManyActiveProposals)?;
+			Self::take_deposit(next_proposal_id, &sender, &proposal_action)?;
+			if let ProposalAction::Call(bounded_call) = &proposal_action {
+				// make sure the preimage is kept around until the proposal is enacted
+				T::Preimages::hold(bounded_call);
+			}
 			let proposal = Proposal {
+				proposer: sender.clone(),
 				start: current_block,
 				start_cindex: cindex,
 				state: ProposalState::Ongoing,
-				action: proposal_action,
+				action: proposal_action.clone(),
 			};
 			<Proposals<T>>::insert(next_proposal_id, proposal);
 			<ProposalCount<T>>::put(next_proposal_id);
 			<Tallies<T>>::insert(next_proposal_id, Tally { turnout: 0, ayes: 0 });
+
+			info!(target: LOG, "proposal {} submitted by {:?}", next_proposal_id, sender);
+			Self::deposit_event(Event::ProposalSubmitted {
+				proposal_id: next_proposal_id,
+				proposal_action,
+				proposer: sender,
+			});
 			Ok(().into())
 		}
 
 		#[pallet::call_index(1)]
-		#[pallet::weight((<T as Config>::WeightInfo::vote(), DispatchClass::Normal, Pays::Yes))]
+		#[pallet::weight((<T as Config>::WeightInfo::vote(T::MaxReputationVecLength::get(), 0), DispatchClass::Normal, Pays::Yes))]
 		pub fn vote(
 			origin: OriginFor<T>,
 			proposal_id: ProposalIdType,
@@ -190,10 +461,233 @@ pub mod pallet {
 			reputations: ReputationVecOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
-			let tally = <Tallies<T>>::get(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			Self::do_vote(sender, proposal_id, vote, reputations, Default::default())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight((Pallet::<T>::update_proposal_state_weight(), DispatchClass::Normal, Pays::Yes))]
+		pub fn update_proposal_state(
+			origin: OriginFor<T>,
+			proposal_id: ProposalIdType,
+		) -> DispatchResultWithPostInfo {
+			let _sender = ensure_signed(origin)?;
+			Self::do_update_proposal_state(proposal_id)?;
+			Ok(().into())
+		}
+
+		/// Change the direction of votes previously cast with `reputations` for `proposal_id`.
+		///
+		/// Votes cast on behalf of delegators are changed for the `delegated_reputations` which
+		/// the sender has used as their delegate.
+		/// Only possible while the proposal is `Ongoing` or `Confirming`.
+		#[pallet::call_index(3)]
+		#[pallet::weight((<T as Config>::WeightInfo::change_vote(T::MaxReputationVecLength::get(), T::MaxReputationVecLength::get()), DispatchClass::Normal, Pays::Yes))]
+		pub fn change_vote(
+			origin: OriginFor<T>,
+			proposal_id: ProposalIdType,
+			vote: Vote,
+			reputations: ReputationVecOf<T>,
+			delegated_reputations: DelegatedReputationVecOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let mut tally = Self::ensure_tally_can_be_updated(proposal_id)?;
+			let num_reputations = reputations.len() as u32;
+			let num_delegated_reputations = delegated_reputations.len() as u32;
+
+			let mut has_voted = false;
+			for vote_entry in Self::own_and_delegated_vote_entries(
+				proposal_id,
+				&sender,
+				reputations,
+				delegated_reputations,
+			)? {
+				match Self::vote_directions(proposal_id, &vote_entry) {
+					Some(previous_vote) => {
+						has_voted = true;
+						if vote_entry.0 == sender {
+							<DelegatedVoteEntries<T>>::remove(proposal_id, &vote_entry);
+						}
+						if previous_vote == vote {
+							continue
+						}
+						tally.ayes = match vote {
+							Vote::Aye => tally.ayes.checked_add(1),
+							Vote::Nay => tally.ayes.checked_sub(1),
+						}
+						.ok_or(Error::<T>::VoteCountOverflow)?;
+						<VoteDirections<T>>::insert(proposal_id, &vote_entry, vote);
+					},
+					None => continue,
+				}
+			}
+			ensure!(has_voted, Error::<T>::InexistentVote);
+
+			<Tallies<T>>::insert(proposal_id, tally);
+			Self::try_update_proposal_state(proposal_id)?;
+
+			Ok(Some(<T as Config>::WeightInfo::change_vote(
+				num_reputations,
+				num_delegated_reputations,
+			))
+			.into())
+		}
+
+		/// Retract votes previously cast with `reputations` for `proposal_id`.
+		///
+		/// Votes cast on behalf of delegators are retracted for the `delegated_reputations` which
+		/// the sender has used as their delegate.
+		/// The retracted reputations may be used to vote again later on.
+		/// Only possible while the proposal is `Ongoing` or `Confirming`.
+		#[pallet::call_index(4)]
+		#[pallet::weight((<T as Config>::WeightInfo::remove_vote(T::MaxReputationVecLength::get(), T::MaxReputationVecLength::get()), DispatchClass::Normal, Pays::Yes))]
+		pub fn remove_vote(
+			origin: OriginFor<T>,
+			proposal_id: ProposalIdType,
+			reputations: ReputationVecOf<T>,
+			delegated_reputations: DelegatedReputationVecOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			let mut tally = Self::ensure_tally_can_be_updated(proposal_id)?;
+			let num_reputations = reputations.len() as u32;
+			let num_delegated_reputations = delegated_reputations.len() as u32;
+
+			let mut has_voted = false;
+			for vote_entry in Self::own_and_delegated_vote_entries(
+				proposal_id,
+				&sender,
+				reputations,
+				delegated_reputations,
+			)? {
+				if let Some(previous_vote) = <VoteDirections<T>>::take(proposal_id, &vote_entry) {
+					has_voted = true;
+					<VoteEntries<T>>::remove(proposal_id, &vote_entry);
+					<DelegatedVoteEntries<T>>::remove(proposal_id, &vote_entry);
+					Self::remove_from_tally(&mut tally, previous_vote)?;
+				}
+			}
+			ensure!(has_voted, Error::<T>::InexistentVote);
+
+			<Tallies<T>>::insert(proposal_id, tally);
+			Self::try_update_proposal_state(proposal_id)?;
+
+			Ok(Some(<T as Config>::WeightInfo::remove_vote(
+				num_reputations,
+				num_delegated_reputations,
+			))
+			.into())
+		}
+
+		/// Delegate the voting power for proposals of `scope` to `delegate`.
+		///
+		/// Replaces any previous delegation for `scope`. The delegation expires together with the
+		/// reputation gained in the current cycle.
+		/// Requires `reputation` to be a verified reputation of the sender within the reputation
+		/// lifetime, earned in the community of `scope` or in any community for the global scope.
+		#[pallet::call_index(5)]
+		#[pallet::weight((<T as Config>::WeightInfo::delegate(), DispatchClass::Normal, Pays::Yes))]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			scope: ProposalAccessPolicy,
+			delegate: T::AccountId,
+			reputation: CommunityCeremony,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender != delegate, Error::<T>::CannotDelegateToSelf);
+			ensure!(
+				Self::has_reputation_in_scope(&sender, scope, reputation),
+				Error::<T>::NoReputationToDelegate
+			);
+			Self::remove_delegation(&sender, scope);
+
+			<Delegators<T>>::try_mutate(&delegate, scope, |delegators| {
+				// make room by dropping delegators whose delegation has expired
+				delegators.retain(|d| Self::is_delegation_active(d, scope, &delegate));
+				delegators.try_push(sender.clone())
+			})
+			.map_err(|_| Error::<T>::TooManyDelegators)?;
+			let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+			<Delegations<T>>::insert(
+				&sender,
+				scope,
+				Delegation { delegate: delegate.clone(), cindex },
+			);
+
+			info!(target: LOG, "{:?} delegated {:?} to {:?}", sender, scope, delegate);
+			Self::deposit_event(Event::Delegated { delegator: sender, delegate, scope });
+			Ok(().into())
+		}
+
+		/// Revoke the delegation of the voting power for proposals of `scope`.
+		///
+		/// Votes which have already been cast by the delegate remain valid.
+		#[pallet::call_index(6)]
+		#[pallet::weight((<T as Config>::WeightInfo::undelegate(), DispatchClass::Normal, Pays::Yes))]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			scope: ProposalAccessPolicy,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			ensure!(Self::remove_delegation(&sender, scope), Error::<T>::InexistentDelegation);
+
+			info!(target: LOG, "{:?} revoked delegation of {:?}", sender, scope);
+			Self::deposit_event(Event::Undelegated { delegator: sender, scope });
+			Ok(().into())
+		}
+
+		/// Vote with `reputations` and, as their delegate, with the `delegated_reputations` of
+		/// accounts which have delegated the scope of `proposal_id` to the sender.
+		///
+		/// Delegated reputations which are not eligible are ignored, see
+		/// [`Pallet::delegated_reputations`].
+		#[pallet::call_index(7)]
+		#[pallet::weight((<T as Config>::WeightInfo::vote(T::MaxReputationVecLength::get(), T::MaxReputationVecLength::get()), DispatchClass::Normal, Pays::Yes))]
+		pub fn vote_with_delegations(
+			origin: OriginFor<T>,
+			proposal_id: ProposalIdType,
+			vote: Vote,
+			reputations: ReputationVecOf<T>,
+			delegated_reputations: DelegatedReputationVecOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+			Self::do_vote(sender, proposal_id, vote, reputations, delegated_reputations)
+		}
+	}
+	impl<T: Config> Pallet<T> {
+		fn do_vote(
+			sender: T::AccountId,
+			proposal_id: ProposalIdType,
+			vote: Vote,
+			reputations: ReputationVecOf<T>,
+			delegated_reputations: DelegatedReputationVecOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let mut tally = <Tallies<T>>::get(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
 			let eligible_reputations =
 				Self::eligible_reputations(proposal_id, &sender, &reputations)?;
-			let num_votes = eligible_reputations.len() as u128;
+
+			// a direct vote overrides the vote cast by a delegate on behalf of the sender
+			for community_ceremony in eligible_reputations.iter() {
+				if <DelegatedVoteEntries<T>>::take(proposal_id, (&sender, community_ceremony))
+					.is_some()
+				{
+					if let Some(previous_vote) =
+						Self::vote_directions(proposal_id, (&sender, community_ceremony))
+					{
+						Self::remove_from_tally(&mut tally, previous_vote)?;
+					}
+				}
+			}
+
+			let mut vote_entries: Vec<VoteEntry<T::AccountId>> = eligible_reputations
+				.into_iter()
+				.map(|community_ceremony| (sender.clone(), community_ceremony))
+				.collect();
+			for vote_entry in
+				Self::eligible_delegated_reputations(proposal_id, &sender, &delegated_reputations)?
+			{
+				<DelegatedVoteEntries<T>>::insert(proposal_id, &vote_entry, &sender);
+				vote_entries.push(vote_entry);
+			}
+			let num_votes = vote_entries.len() as u128;
 
 			let ayes = match vote {
 				Vote::Aye => num_votes,
@@ -209,33 +703,26 @@ pub mod pallet {
 			};
 
 			<Tallies<T>>::insert(proposal_id, new_tally);
-			for community_ceremony in eligible_reputations {
-				<VoteEntries<T>>::insert(proposal_id, (&sender, community_ceremony), ());
+			for vote_entry in vote_entries {
+				<VoteEntries<T>>::insert(proposal_id, &vote_entry, ());
+				<VoteDirections<T>>::insert(proposal_id, vote_entry, vote);
 			}
+			Self::deposit_event(Event::VotePlaced {
+				proposal_id,
+				voter: sender,
+				vote,
+				num_reputations: num_votes as u32,
+			});
 
-			match Self::do_update_proposal_state(proposal_id) {
-				Ok(_) => Ok(()),
-				Err(error) => match error {
-					Error::<T>::ProposalCannotBeUpdated => Ok(()),
-					other_error => Err(other_error),
-				},
-			}?;
+			Self::try_update_proposal_state(proposal_id)?;
 
-			Ok(().into())
+			Ok(Some(<T as Config>::WeightInfo::vote(
+				reputations.len() as u32,
+				delegated_reputations.len() as u32,
+			))
+			.into())
 		}
 
-		#[pallet::call_index(2)]
-		#[pallet::weight((<T as Config>::WeightInfo::update_proposal_state(), DispatchClass::Normal, Pays::Yes))]
-		pub fn update_proposal_state(
-			origin: OriginFor<T>,
-			proposal_id: ProposalIdType,
-		) -> DispatchResultWithPostInfo {
-			let _sender = ensure_signed(origin)?;
-			Self::do_update_proposal_state(proposal_id)?;
-			Ok(().into())
-		}
-	}
-	impl<T: Config> Pallet<T> {
 		fn relevant_cindexes(
 			proposal_id: ProposalIdType,
 		) -> Result<Vec<CeremonyIndexType>, Error<T>> {
@@ -279,18 +766,273 @@ pub mod pallet {
 						continue
 					}
 				}
-				if <VoteEntries<T>>::contains_key(proposal_id, (account_id, community_ceremony)) {
+				if Self::is_unused_valid_reputation(proposal_id, account_id, community_ceremony) {
+					eligible_reputations.push(*community_ceremony);
+				}
+			}
+			BoundedVec::try_from(eligible_reputations).map_err(|_e| Error::<T>::BoundedVecError)
+		}
+
+		/// Returns all reputations of `account_id` which are eligible to vote for `proposal_id`,
+		/// see [`Self::eligible_reputations`].
+		pub fn get_eligible_reputations(
+			proposal_id: ProposalIdType,
+			account_id: &T::AccountId,
+		) -> Result<Vec<CommunityCeremony>, Error<T>> {
+			Ok(Self::relevant_community_ceremonies(proposal_id)?
+				.into_iter()
+				.filter(|community_ceremony| {
+					Self::is_unused_valid_reputation(proposal_id, account_id, community_ceremony)
+				})
+				.collect())
+		}
+
+		fn is_unused_valid_reputation(
+			proposal_id: ProposalIdType,
+			account_id: &T::AccountId,
+			community_ceremony: &CommunityCeremony,
+		) -> bool {
+			// reputations used by a delegate can be overridden by a direct vote
+			if <VoteEntries<T>>::contains_key(proposal_id, (account_id, community_ceremony)) &&
+				!<DelegatedVoteEntries<T>>::contains_key(
+					proposal_id,
+					(account_id, community_ceremony),
+				) {
+				return false
+			}
+			<encointer_ceremonies::Pallet<T>>::validate_reputation(
+				account_id,
+				&community_ceremony.0,
+				community_ceremony.1,
+			)
+		}
+
+		/// Returns all proposals together with their current tally and evaluation.
+		pub fn get_proposals() -> Vec<ProposalInfoOf<T>> {
+			<Proposals<T>>::iter_keys().filter_map(Self::get_proposal_info).collect()
+		}
+
+		pub fn get_proposal_info(proposal_id: ProposalIdType) -> Option<ProposalInfoOf<T>> {
+			let proposal = Self::proposals(proposal_id)?;
+			let remaining_confirmation_blocks = match proposal.state {
+				ProposalState::Confirming { since } => Some(
+					(since + T::ConfirmationPeriod::get() + One::one())
+						.saturating_sub(frame_system::Pallet::<T>::block_number()),
+				),
+				_ => None,
+			};
+			Some(ProposalInfo {
+				proposal_id,
+				proposal: proposal.map_call(|call| call.hash()),
+				tally: Self::tallies(proposal_id).unwrap_or_default(),
+				electorate: Self::get_electorate(proposal_id).unwrap_or_default(),
+				is_passing: Self::is_passing(proposal_id).unwrap_or(false),
+				remaining_confirmation_blocks,
+			})
+		}
+
+		/// Returns the vote entries of the accounts delegating to `delegate` for the scope of
+		/// `proposal_id`, whose reputations
+		/// 1. are valid
+		/// 2. have not been used to vote for proposal_id
+		/// 3. originate in the correct community (for Community AccessPolicy)
+		/// 4. are within the relevant cindexes of the proposal
+		///
+		/// Iterates over all relevant community ceremonies of all delegators and is therefore
+		/// meant to be queried off-chain, e.g. to assemble the `delegated_reputations` of
+		/// [`Pallet::vote_with_delegations`].
+		pub fn delegated_reputations(
+			proposal_id: ProposalIdType,
+			delegate: &T::AccountId,
+		) -> Result<Vec<VoteEntry<T::AccountId>>, Error<T>> {
+			let scope = Self::proposals(proposal_id)
+				.ok_or(Error::<T>::InexistentProposal)?
+				.action
+				.get_access_policy();
+			let community_ceremonies = Self::relevant_community_ceremonies(proposal_id)?;
+
+			let mut delegated_reputations = Vec::new();
+			for delegator in Self::delegators(delegate, scope) {
+				if !Self::is_delegation_active(&delegator, scope, delegate) {
+					continue
+				}
+				for community_ceremony in community_ceremonies.iter() {
+					if <VoteEntries<T>>::contains_key(proposal_id, (&delegator, community_ceremony))
+					{
+						continue
+					}
+					if <encointer_ceremonies::Pallet<T>>::validate_reputation(
+						&delegator,
+						&community_ceremony.0,
+						community_ceremony.1,
+					) {
+						delegated_reputations.push((delegator.clone(), *community_ceremony));
+					}
+				}
+			}
+			Ok(delegated_reputations)
+		}
+
+		/// Returns the subset of `delegated_reputations` which `delegate` may use to vote for
+		/// `proposal_id`, see [`Self::delegated_reputations`].
+		fn eligible_delegated_reputations(
+			proposal_id: ProposalIdType,
+			delegate: &T::AccountId,
+			delegated_reputations: &DelegatedReputationVecOf<T>,
+		) -> Result<Vec<VoteEntry<T::AccountId>>, Error<T>> {
+			let scope = Self::proposals(proposal_id)
+				.ok_or(Error::<T>::InexistentProposal)?
+				.action
+				.get_access_policy();
+			let relevant_cindexes = Self::relevant_cindexes(proposal_id)?;
+
+			let mut eligible = Vec::<VoteEntry<T::AccountId>>::new();
+			for (delegator, community_ceremony) in delegated_reputations.iter() {
+				if !relevant_cindexes.contains(&community_ceremony.1) {
+					continue
+				}
+				if let ProposalAccessPolicy::Community(cid) = scope {
+					if community_ceremony.0 != cid {
+						continue
+					}
+				}
+				if eligible.iter().any(|(d, cc)| d == delegator && cc == community_ceremony) {
+					continue
+				}
+				if !Self::is_delegation_active(delegator, scope, delegate) ||
+					<VoteEntries<T>>::contains_key(proposal_id, (delegator, community_ceremony))
+				{
 					continue
 				}
 				if <encointer_ceremonies::Pallet<T>>::validate_reputation(
-					account_id,
+					delegator,
 					&community_ceremony.0,
 					community_ceremony.1,
 				) {
-					eligible_reputations.push(*community_ceremony);
+					eligible.push((delegator.clone(), *community_ceremony));
 				}
 			}
-			BoundedVec::try_from(eligible_reputations).map_err(|_e| Error::<T>::BoundedVecError)
+			Ok(eligible)
+		}
+
+		/// Returns true if `delegator` has delegated `scope` to `delegate` and the delegation has
+		/// not yet expired.
+		pub fn is_delegation_active(
+			delegator: &T::AccountId,
+			scope: ProposalAccessPolicy,
+			delegate: &T::AccountId,
+		) -> bool {
+			let reputation_lifetime = <encointer_ceremonies::Pallet<T>>::reputation_lifetime();
+			let cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+			Self::delegations(delegator, scope).map_or(false, |delegation| {
+				&delegation.delegate == delegate &&
+					delegation.cindex.saturating_add(reputation_lifetime) >= cindex
+			})
+		}
+
+		/// Returns true if `reputation` is a verified reputation of `account_id` in `scope` which
+		/// is still within the reputation lifetime.
+		fn has_reputation_in_scope(
+			account_id: &T::AccountId,
+			scope: ProposalAccessPolicy,
+			reputation: CommunityCeremony,
+		) -> bool {
+			let (cid, cindex) = reputation;
+			if let ProposalAccessPolicy::Community(scope_cid) = scope {
+				if cid != scope_cid {
+					return false
+				}
+			}
+			let current_cindex = <encointer_scheduler::Pallet<T>>::current_ceremony_index();
+			let reputation_lifetime = <encointer_ceremonies::Pallet<T>>::reputation_lifetime();
+			cindex <= current_cindex &&
+				cindex.saturating_add(reputation_lifetime) >= current_cindex &&
+				<encointer_ceremonies::Pallet<T>>::participant_reputation(reputation, account_id)
+					.is_verified()
+		}
+
+		/// Removes the delegation of `delegator` for `scope`. Returns false if there was none.
+		fn remove_delegation(delegator: &T::AccountId, scope: ProposalAccessPolicy) -> bool {
+			match <Delegations<T>>::take(delegator, scope) {
+				Some(delegation) => {
+					<Delegators<T>>::mutate(&delegation.delegate, scope, |delegators| {
+						delegators.retain(|d| d != delegator)
+					});
+					true
+				},
+				None => false,
+			}
+		}
+
+		/// Returns all community ceremonies whose reputation is relevant for `proposal_id`.
+		fn relevant_community_ceremonies(
+			proposal_id: ProposalIdType,
+		) -> Result<Vec<CommunityCeremony>, Error<T>> {
+			let cids = match Self::proposals(proposal_id)
+				.ok_or(Error::<T>::InexistentProposal)?
+				.action
+				.get_access_policy()
+			{
+				ProposalAccessPolicy::Community(cid) => sp_std::vec![cid],
+				ProposalAccessPolicy::Global =>
+					<encointer_communities::Pallet<T>>::community_identifiers().into_inner(),
+			};
+			let cindexes = Self::relevant_cindexes(proposal_id)?;
+			Ok(cids
+				.into_iter()
+				.flat_map(|cid| cindexes.iter().map(move |cindex| (cid, *cindex)))
+				.collect())
+		}
+
+		/// Returns the vote entries of `account_id` for `reputations` together with those of
+		/// `delegated_reputations` which `account_id` has cast on behalf of its current delegators.
+		fn own_and_delegated_vote_entries(
+			proposal_id: ProposalIdType,
+			account_id: &T::AccountId,
+			reputations: ReputationVecOf<T>,
+			delegated_reputations: DelegatedReputationVecOf<T>,
+		) -> Result<Vec<VoteEntry<T::AccountId>>, Error<T>> {
+			let scope = Self::proposals(proposal_id)
+				.ok_or(Error::<T>::InexistentProposal)?
+				.action
+				.get_access_policy();
+			let delegators = Self::delegators(account_id, scope);
+
+			let mut vote_entries: Vec<VoteEntry<T::AccountId>> = reputations
+				.into_iter()
+				.map(|community_ceremony| (account_id.clone(), community_ceremony))
+				.collect();
+			for vote_entry in delegated_reputations {
+				if delegators.contains(&vote_entry.0) &&
+					Self::delegated_vote_entries(proposal_id, &vote_entry).as_ref() ==
+						Some(account_id)
+				{
+					vote_entries.push(vote_entry);
+				}
+			}
+			Ok(vote_entries)
+		}
+
+		fn deposit_state_changed(
+			proposal_id: ProposalIdType,
+			old_state: ProposalState<BlockNumberFor<T>>,
+			new_state: ProposalState<BlockNumberFor<T>>,
+		) {
+			if old_state != new_state {
+				Self::deposit_event(Event::ProposalStateChanged {
+					proposal_id,
+					old_state,
+					new_state,
+				});
+			}
+		}
+
+		fn remove_from_tally(tally: &mut Tally, vote: Vote) -> Result<(), Error<T>> {
+			tally.turnout = tally.turnout.checked_sub(1).ok_or(Error::<T>::VoteCountOverflow)?;
+			if vote == Vote::Aye {
+				tally.ayes = tally.ayes.checked_sub(1).ok_or(Error::<T>::VoteCountOverflow)?;
+			}
+			Ok(())
 		}
 
 		/// Updates the proposal state
@@ -300,6 +1042,7 @@ pub mod pallet {
 			let mut proposal =
 				Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
 			ensure!(proposal.state.can_update(), Error::<T>::ProposalCannotBeUpdated);
+			let old_state = proposal.state;
 			let mut approved = false;
 			let current_block = frame_system::Pallet::<T>::block_number();
 			let proposal_action_identifier = proposal.action.get_identifier();
@@ -308,6 +1051,16 @@ pub mod pallet {
 			let proposal_too_old = current_block - proposal.start > T::ProposalLifetime::get();
 			if proposal_cancelled || proposal_too_old {
 				proposal.state = ProposalState::Cancelled;
+				if let ProposalAction::Call(bounded_call) = &proposal.action {
+					T::Preimages::drop(bounded_call);
+				}
+				// superseded proposals are not the proposer's fault
+				if proposal_cancelled {
+					Self::refund_deposit(proposal_id, &proposal.proposer);
+				} else {
+					Self::slash_deposit(proposal_id, &proposal.proposer);
+				}
+				Self::deposit_event(Event::ProposalCancelled { proposal_id });
 			} else {
 				// passing
 				if Self::is_passing(proposal_id)? {
@@ -316,12 +1069,20 @@ pub mod pallet {
 						// confirmed longer than period
 						if current_block - since > T::ConfirmationPeriod::get() {
 							proposal.state = ProposalState::Approved;
-							<EnactmentQueue<T>>::insert(proposal_action_identifier, proposal_id);
-							<CancelledAtBlock<T>>::insert(
-								proposal_action_identifier,
-								current_block,
-							);
-							approved = true;
+							if proposal.action.is_petition() {
+								// there is nothing to enact
+								Self::refund_deposit(proposal_id, &proposal.proposer);
+							} else {
+								<EnactmentQueue<T>>::insert(
+									proposal_action_identifier,
+									proposal_id,
+								);
+								<CancelledAtBlock<T>>::insert(
+									proposal_action_identifier,
+									current_block,
+								);
+								approved = true;
+							}
 						}
 					// not confirming
 					} else {
@@ -335,10 +1096,276 @@ pub mod pallet {
 					}
 				}
 			}
+			if !proposal.state.can_update() {
+				Self::remove_active_proposal(proposal_id);
+			}
+			Self::deposit_state_changed(proposal_id, old_state, proposal.state);
 			<Proposals<T>>::insert(proposal_id, proposal);
 			Ok(approved)
 		}
 
+		/// The weight of updating the state of a proposal, which is the heavier of approving and
+		/// cancelling it.
+		pub fn update_proposal_state_weight() -> Weight {
+			<T as Config>::WeightInfo::update_proposal_state()
+				.max(<T as Config>::WeightInfo::cancel_proposal())
+		}
+
+		/// Advances the state of active proposals as far as `weight_budget` allows.
+		///
+		/// Proposals which could not be advanced in this call are considered first in the next one.
+		pub fn advance_active_proposals(weight_budget: Weight) -> Weight {
+			let update_weight = Self::update_proposal_state_weight();
+			let mut used_weight = T::DbWeight::get().reads_writes(1, 1);
+			if used_weight.any_gt(weight_budget) {
+				return Weight::zero()
+			}
+
+			let active_proposals = Self::active_proposals();
+			let mut num_processed = 0;
+			for proposal_id in active_proposals.iter() {
+				if used_weight.saturating_add(update_weight).any_gt(weight_budget) {
+					break
+				}
+				used_weight.saturating_accrue(update_weight);
+				num_processed += 1;
+				match Self::do_update_proposal_state(*proposal_id) {
+					Ok(_) => (),
+					Err(Error::<T>::InexistentProposal) |
+					Err(Error::<T>::ProposalCannotBeUpdated) => Self::remove_active_proposal(*proposal_id),
+					Err(e) => warn!(
+						target: LOG,
+						"failed to update state of proposal {}: {:?}",
+						proposal_id,
+						e
+					),
+				}
+			}
+
+			// move the processed proposals to the back to avoid starving the others
+			let processed = &active_proposals[..num_processed];
+			<ActiveProposals<T>>::mutate(|ids| ids.sort_by_key(|id| processed.contains(id)));
+			used_weight
+		}
+
+		/// Enacts the queued proposals which are due as far as `weight_budget` allows.
+		///
+		/// Proposals which do not fit into the remaining budget are postponed to the next block.
+		/// Proposals which do not even fit into the whole budget fail to be enacted.
+		pub fn enact_queued_proposals(weight_budget: Weight) -> Weight {
+			let mut used_weight = T::DbWeight::get().reads(1);
+			let cutoff = match Self::enactment_cutoff() {
+				Some(cutoff) => cutoff,
+				None => return used_weight,
+			};
+
+			let mut enacted = Vec::new();
+			let mut postponed = false;
+			for (identifier, proposal_id) in <EnactmentQueue<T>>::iter() {
+				// the queue entry, its cancellation block and the proposal
+				let read_weight = T::DbWeight::get().reads(3);
+				if used_weight.saturating_add(read_weight).any_gt(weight_budget) {
+					postponed = true;
+					break
+				}
+				used_weight.saturating_accrue(read_weight);
+				// approved after the start of the registering phase, enacted in the next cycle
+				if Self::cancelled_at_block(identifier) >= cutoff {
+					continue
+				}
+
+				let proposal_action = Self::proposals(proposal_id).map(|proposal| proposal.action);
+				// looking up a proposed call to find its weight
+				let inspection_weight = match &proposal_action {
+					Some(ProposalAction::Call(bounded_call)) =>
+						Self::call_lookup_weight(bounded_call),
+					_ => Weight::zero(),
+				};
+				if used_weight.saturating_add(inspection_weight).any_gt(weight_budget) {
+					postponed = true;
+					break
+				}
+				used_weight.saturating_accrue(inspection_weight);
+
+				let enactment_weight = Self::enactment_weight(proposal_action);
+				// proposals which can never be enacted are rejected at the cost of an enactment
+				let too_heavy = enactment_weight.any_gt(weight_budget);
+				let required_weight = if too_heavy {
+					<T as Config>::WeightInfo::enact_proposal()
+				} else {
+					enactment_weight
+				};
+				if used_weight.saturating_add(required_weight).any_gt(weight_budget) {
+					postponed = true;
+					break
+				}
+				used_weight.saturating_accrue(required_weight);
+
+				let result = if too_heavy {
+					Self::reject_enactment(proposal_id, Error::<T>::EnactmentWeightExceeded.into())
+				} else {
+					Self::enact_proposal(proposal_id)
+				};
+				if let Err(e) = result {
+					warn!(target: LOG, "failed to enact proposal {}: {:?}", proposal_id, e);
+				}
+				enacted.push(identifier);
+			}
+
+			for identifier in enacted.iter() {
+				<EnactmentQueue<T>>::remove(identifier);
+			}
+			if !postponed {
+				<EnactmentCutoff<T>>::kill();
+			}
+			used_weight.saturating_add(
+				T::DbWeight::get().writes(enacted.len() as u64 + u64::from(!postponed)),
+			)
+		}
+
+		/// Returns the weight of enacting `proposal_action`, including the dispatch of a proposed
+		/// call and the lookup of its preimage.
+		fn enactment_weight(proposal_action: Option<ProposalActionOf<T>>) -> Weight {
+			let base_weight = <T as Config>::WeightInfo::enact_proposal();
+			match proposal_action {
+				Some(ProposalAction::Call(bounded_call)) => {
+					let call_weight = T::Preimages::peek(&bounded_call)
+						.map(|(call, _)| call.get_dispatch_info().weight)
+						.unwrap_or_default();
+					base_weight
+						.saturating_add(Self::call_lookup_weight(&bounded_call))
+						.saturating_add(call_weight)
+				},
+				_ => base_weight,
+			}
+		}
+
+		/// Returns the weight of reading and decoding the preimage of a proposed call.
+		fn call_lookup_weight(bounded_call: &BoundedCallOf<T>) -> Weight {
+			let len = bounded_call.len().unwrap_or(T::Preimages::MAX_LENGTH as u32);
+			<T as Config>::WeightInfo::lookup_call(len)
+		}
+
+		/// Returns the account which holds the community currency deposit of `proposal_id`.
+		pub fn deposit_escrow_account(proposal_id: ProposalIdType) -> T::AccountId {
+			T::DepositEscrowPalletId::get().into_sub_account_truncating(proposal_id)
+		}
+
+		/// Reserves (native) or moves to an escrow account (community currency) the deposit for a
+		/// new proposal.
+		fn take_deposit(
+			proposal_id: ProposalIdType,
+			proposer: &T::AccountId,
+			proposal_action: &ProposalActionOf<T>,
+		) -> DispatchResult {
+			let deposit = match proposal_action.get_access_policy() {
+				ProposalAccessPolicy::Global => {
+					let amount = T::ProposalDeposit::get();
+					if amount.is_zero() {
+						return Ok(())
+					}
+					<T as Config>::Currency::reserve(proposer, amount)?;
+					ProposalDeposit::Native(amount)
+				},
+				ProposalAccessPolicy::Community(cid) => {
+					let amount = T::CommunityProposalDeposit::get();
+					if amount == 0 {
+						return Ok(())
+					}
+					<encointer_balances::Pallet<T>>::do_transfer(
+						cid,
+						proposer.clone(),
+						Self::deposit_escrow_account(proposal_id),
+						amount,
+					)?;
+					ProposalDeposit::Community(cid, amount)
+				},
+			};
+			<ProposalDeposits<T>>::insert(proposal_id, deposit);
+			Ok(())
+		}
+
+		fn refund_deposit(proposal_id: ProposalIdType, proposer: &T::AccountId) {
+			let result = match Self::proposal_deposits(proposal_id) {
+				Some(ProposalDeposit::Native(amount)) => {
+					<T as Config>::Currency::unreserve(proposer, amount);
+					Ok(())
+				},
+				Some(ProposalDeposit::Community(cid, _)) =>
+					Self::release_community_deposit(proposal_id, cid, proposer.clone()),
+				None => return,
+			};
+			Self::conclude_deposit(proposal_id, result, "refunded");
+		}
+
+		/// Burns a native deposit and moves a community currency deposit to the community
+		/// treasury.
+		fn slash_deposit(proposal_id: ProposalIdType, proposer: &T::AccountId) {
+			let result = match Self::proposal_deposits(proposal_id) {
+				Some(ProposalDeposit::Native(amount)) => {
+					// the imbalance is dropped, i.e. burned
+					let _ = <T as Config>::Currency::slash_reserved(proposer, amount);
+					Ok(())
+				},
+				Some(ProposalDeposit::Community(cid, _)) => Self::release_community_deposit(
+					proposal_id,
+					cid,
+					<encointer_balances::Pallet<T>>::get_community_treasury_account(cid),
+				),
+				None => return,
+			};
+			Self::conclude_deposit(proposal_id, result, "slashed");
+		}
+
+		/// Transfers the community currency deposit of `proposal_id` from its escrow account to
+		/// `dest`. The deposit is subject to demurrage while it is held.
+		fn release_community_deposit(
+			proposal_id: ProposalIdType,
+			cid: CommunityIdentifier,
+			dest: T::AccountId,
+		) -> DispatchResult {
+			let escrow = Self::deposit_escrow_account(proposal_id);
+			let amount = <encointer_balances::Pallet<T>>::balance(cid, &escrow);
+			<encointer_balances::Pallet<T>>::do_transfer(cid, escrow, dest, amount).map(|_| ())
+		}
+
+		/// Forgets the deposit of `proposal_id` once it has been released. A deposit which could
+		/// not be released is kept in `ProposalDeposits` and remains in its escrow account.
+		fn conclude_deposit(proposal_id: ProposalIdType, result: DispatchResult, action: &str) {
+			match result {
+				Ok(()) => {
+					<ProposalDeposits<T>>::remove(proposal_id);
+					info!(target: LOG, "{} deposit of proposal {}", action, proposal_id);
+				},
+				Err(e) => warn!(
+					target: LOG,
+					"deposit of proposal {} could not be {}: {:?}", proposal_id, action, e
+				),
+			}
+		}
+
+		fn remove_active_proposal(proposal_id: ProposalIdType) {
+			<ActiveProposals<T>>::mutate(|ids| ids.retain(|id| *id != proposal_id));
+		}
+
+		/// Updates the proposal state, ignoring proposals that cannot be updated anymore.
+		fn try_update_proposal_state(proposal_id: ProposalIdType) -> Result<(), Error<T>> {
+			match Self::do_update_proposal_state(proposal_id) {
+				Ok(_) => Ok(()),
+				Err(error) => match error {
+					Error::<T>::ProposalCannotBeUpdated => Ok(()),
+					other_error => Err(other_error),
+				},
+			}
+		}
+
+		/// Returns the tally of the proposal if its votes may still be changed.
+		fn ensure_tally_can_be_updated(proposal_id: ProposalIdType) -> Result<Tally, Error<T>> {
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			ensure!(proposal.state.can_update(), Error::<T>::ProposalCannotBeUpdated);
+			Self::tallies(proposal_id).ok_or(Error::<T>::InexistentProposal)
+		}
+
 		pub fn get_electorate(
 			proposal_id: ProposalIdType,
 		) -> Result<ReputationCountType, Error<T>> {
@@ -363,36 +1390,6 @@ pub mod pallet {
 			}
 		}
 
-		fn positive_turnout_bias(e: u128, t: u128, a: u128) -> Result<bool, Error<T>> {
-			// electorate e
-			// turnout t
-			// approval a
-
-			// let nays n = t - a
-			// approved if n / sqrt(t) < a / sqrt(e)
-			// <==>
-			// a > sqrt(e) * sqrt(t) / (sqrt(e) / sqrt(t) + 1)
-
-			let sqrt_e =
-				sqrt::<U64F64, U64F64>(U64F64::from_num(e)).map_err(|_| <Error<T>>::AQBError)?;
-			let sqrt_t =
-				sqrt::<U64F64, U64F64>(U64F64::from_num(t)).map_err(|_| <Error<T>>::AQBError)?;
-			let one = U64F64::from_num(1);
-
-			Ok(U64F64::from_num(a) >
-				sqrt_e
-					.checked_mul(sqrt_t)
-					.ok_or(<Error<T>>::AQBError)?
-					.checked_div(
-						sqrt_e
-							.checked_div(sqrt_t)
-							.ok_or(<Error<T>>::AQBError)?
-							.checked_add(one)
-							.ok_or(<Error<T>>::AQBError)?,
-					)
-					.ok_or(<Error<T>>::AQBError)?)
-		}
-
 		pub fn is_passing(proposal_id: ProposalIdType) -> Result<bool, Error<T>> {
 			let tally = Self::tallies(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
 			let electorate = Self::get_electorate(proposal_id)?;
@@ -401,59 +1398,149 @@ pub mod pallet {
 			if turnout_permill < T::MinTurnout::get() {
 				return Ok(false)
 			}
-			let positive_turnout_bias =
-				Self::positive_turnout_bias(electorate, tally.turnout, tally.ayes);
-			if let Ok(passing) = positive_turnout_bias {
-				if passing {
-					return Ok(true)
-				}
-			}
-			Ok(false)
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			let strategy = T::TallyStrategies::convert(proposal.action.get_identifier());
+			Ok(strategy.is_passing(electorate, &tally).unwrap_or(false))
 		}
+		/// Enacts an approved proposal.
+		///
+		/// If the proposal action fails, its storage changes are reverted and the proposal ends
+		/// up in the `EnactmentFailed` state. The deposit is refunded in either case, as the
+		/// proposal has been approved.
 		pub fn enact_proposal(proposal_id: ProposalIdType) -> Result<(), Error<T>> {
-			let mut proposal =
-				Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			let result =
+				with_storage_layer(|| Self::execute_proposal_action(proposal.action.clone()));
+			Self::conclude_enactment(proposal_id, proposal, result);
+			Ok(())
+		}
 
-			match proposal.action {
-				ProposalAction::UpdateNominalIncome(cid, nominal_income) => {
-					let _ = <encointer_communities::Pallet<T>>::do_update_nominal_income(
-						cid,
-						nominal_income,
-					);
-				},
+		/// Lets an approved proposal fail without executing its action.
+		fn reject_enactment(
+			proposal_id: ProposalIdType,
+			reason: DispatchError,
+		) -> Result<(), Error<T>> {
+			let proposal = Self::proposals(proposal_id).ok_or(Error::<T>::InexistentProposal)?;
+			Self::conclude_enactment(proposal_id, proposal, Err(reason));
+			Ok(())
+		}
 
-				ProposalAction::SetInactivityTimeout(inactivity_timeout) => {
-					let _ = <encointer_ceremonies::Pallet<T>>::do_set_inactivity_timeout(
-						inactivity_timeout,
-					);
+		fn conclude_enactment(
+			proposal_id: ProposalIdType,
+			mut proposal: ProposalOf<T>,
+			result: DispatchResult,
+		) {
+			let old_state = proposal.state;
+			match result {
+				Ok(()) => {
+					proposal.state = ProposalState::Enacted;
+					info!(target: LOG, "proposal {} enacted", proposal_id);
+					Self::deposit_event(Event::ProposalEnacted { proposal_id });
 				},
-			};
+				Err(reason) => {
+					proposal.state = ProposalState::EnactmentFailed;
+					warn!(target: LOG, "enactment of proposal {} failed: {:?}", proposal_id, reason);
+					Self::deposit_event(Event::EnactmentFailed { proposal_id, reason });
+				},
+			}
+			if let ProposalAction::Call(bounded_call) = &proposal.action {
+				T::Preimages::drop(bounded_call);
+			}
 
-			proposal.state = ProposalState::Enacted;
+			Self::refund_deposit(proposal_id, &proposal.proposer);
+			Self::deposit_state_changed(proposal_id, old_state, proposal.state);
 			<Proposals<T>>::insert(proposal_id, proposal);
-			Self::deposit_event(Event::ProposalEnacted { proposal_id });
-			Ok(())
+		}
+
+		fn execute_proposal_action(proposal_action: ProposalActionOf<T>) -> DispatchResult {
+			match proposal_action {
+				ProposalAction::UpdateNominalIncome(cid, nominal_income) =>
+					<encointer_communities::Pallet<T>>::do_update_nominal_income(
+						cid,
+						nominal_income,
+					),
+				ProposalAction::SetInactivityTimeout(inactivity_timeout) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_inactivity_timeout(inactivity_timeout),
+				ProposalAction::UpdateDemurrage(cid, demurrage) =>
+					<encointer_communities::Pallet<T>>::do_update_demurrage(cid, demurrage),
+				ProposalAction::SetReputationLifetime(reputation_lifetime) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_reputation_lifetime(
+						reputation_lifetime,
+					),
+				ProposalAction::SetEndorsementTicketsPerBootstrapper(endorsement_tickets) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_endorsement_tickets_per_bootstrapper(
+						endorsement_tickets,
+					),
+				ProposalAction::SetEndorsementTicketsPerReputable(endorsement_tickets) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_endorsement_tickets_per_reputable(
+						endorsement_tickets,
+					),
+				ProposalAction::SetMeetupTimeOffset(meetup_time_offset) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_meetup_time_offset(meetup_time_offset),
+				ProposalAction::SetTimeTolerance(time_tolerance) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_time_tolerance(time_tolerance),
+				ProposalAction::SetLocationTolerance(location_tolerance) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_location_tolerance(location_tolerance),
+				ProposalAction::SetFeeConversionFactor(fee_conversion_factor) =>
+					<encointer_balances::Pallet<T>>::do_set_fee_conversion_factor(
+						fee_conversion_factor,
+					),
+				ProposalAction::Call(bounded_call) => {
+					let (call, _) = T::Preimages::realize(&bounded_call)?;
+					call.dispatch(RawOrigin::EncointerDemocracy.into())
+				},
+				ProposalAction::Petition(_, _) => Ok(().into()),
+				ProposalAction::SpendCommunityTreasury(cid, beneficiary, amount) => {
+					let treasury =
+						<encointer_balances::Pallet<T>>::get_community_treasury_account(cid);
+					<encointer_balances::Pallet<T>>::do_transfer(cid, treasury, beneficiary, amount)
+						.map(|_| ().into())
+						.map_err(|e| e.into())
+				},
+				ProposalAction::SetMeetupValidationPolicy(cid, policy) =>
+					<encointer_ceremonies::Pallet<T>>::do_set_meetup_validation_policy(cid, policy),
+			}
+			.map(|_| ())
+			.map_err(|e| e.error)
 		}
 	}
 }
 
+/// Ensures that the origin is the `EncointerDemocracy` origin, i.e. that the call has been
+/// approved by a democracy proposal.
+///
+/// Can be wired into `CeremonyMaster`/`CommunityMaster` to let communities govern themselves.
+pub struct EnsureEncointerDemocracy;
+impl<O: Into<Result<RawOrigin, O>> + From<RawOrigin>> EnsureOrigin<O> for EnsureEncointerDemocracy {
+	type Success = ();
+	fn try_origin(o: O) -> Result<Self::Success, O> {
+		o.into().map(|o| match o {
+			RawOrigin::EncointerDemocracy => (),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<O, ()> {
+		Ok(O::from(RawOrigin::EncointerDemocracy))
+	}
+}
+
 impl<T: Config> OnCeremonyPhaseChange for Pallet<T> {
 	fn on_ceremony_phase_change(new_phase: CeremonyPhaseType) {
 		match new_phase {
 			CeremonyPhaseType::Assigning => {},
 			CeremonyPhaseType::Attesting => {},
 			CeremonyPhaseType::Registering => {
-				// safe as EnactmentQueue has one key per ProposalActionType and those are bounded
-				<EnactmentQueue<T>>::iter().for_each(|p| {
-					let _ = Self::enact_proposal(p.1);
-				});
-				// remove all keys from the map
-				<EnactmentQueue<T>>::translate::<ProposalIdType, _>(|_, _| None);
+				// the queue is not bounded, as it is keyed by the hash of proposed calls. Its
+				// proposals are enacted in `on_initialize` of the following blocks as far as the
+				// `EnactmentWeightBudget` allows.
+				<EnactmentCutoff<T>>::put(frame_system::Pallet::<T>::block_number());
 			},
 		}
 	}
 }
 
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
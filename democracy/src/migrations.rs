@@ -0,0 +1,212 @@
+use super::*;
+use codec::{Decode, Encode};
+use encointer_primitives::democracy::ProposalState;
+use frame_support::{pallet_prelude::*, traits::OnRuntimeUpgrade};
+use sp_runtime::traits::TrailingZeroInput;
+
+/// The log target.
+const TARGET: &str = "democracy::migration::v1";
+
+mod v0 {
+	use super::*;
+
+	/// Proposal as stored before the proposer has been recorded
+	#[derive(Encode, Decode)]
+	pub struct Proposal<BlockNumber, ProposalAction> {
+		pub start: BlockNumber,
+		pub start_cindex: CeremonyIndexType,
+		pub action: ProposalAction,
+		pub state: ProposalState<BlockNumber>,
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// Records a proposer for every stored proposal and seeds `ActiveProposals` with the
+	/// proposals which are still `Ongoing` or `Confirming`.
+	///
+	/// The proposer of proposals submitted before v1 is unknown. As no deposit has been taken for
+	/// them, they get the all-zero account, which is never refunded nor slashed anything.
+	///
+	/// `MaxActiveProposals` must be large enough to hold all proposals which are still `Ongoing`
+	/// or `Confirming`, otherwise `pre_upgrade` fails.
+	pub struct MigrateToV1<T>(sp_std::marker::PhantomData<T>);
+
+	impl<T: Config + frame_system::Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::DispatchError> {
+			assert_eq!(StorageVersion::get::<Pallet<T>>(), 0, "can only upgrade from version 0");
+
+			let proposal_count = Proposals::<T>::iter_keys().count() as u32;
+			log::info!(target: TARGET, "{} proposals will be migrated.", proposal_count);
+
+			let active_proposal_count = Proposals::<T>::iter_keys()
+				.filter(|id| {
+					frame_support::storage::unhashed::get::<
+						v0::Proposal<BlockNumberFor<T>, ProposalActionOf<T>>,
+					>(&Proposals::<T>::hashed_key_for(id))
+					.map_or(false, |p| p.state.can_update())
+				})
+				.count() as u32;
+			ensure!(
+				active_proposal_count <= T::MaxActiveProposals::get(),
+				"more active proposals than MaxActiveProposals, which must be raised"
+			);
+
+			Ok(proposal_count.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let weight = T::DbWeight::get().reads(1);
+			if StorageVersion::get::<Pallet<T>>() != 0 {
+				log::warn!(
+					target: TARGET,
+					"skipping on_runtime_upgrade: executed on wrong storage version.\
+				Expected version 0"
+				);
+				return weight
+			}
+
+			let unknown_proposer = T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+				.expect("infinite length input; no invalid inputs for type; qed");
+			let mut translated = 0u64;
+			let mut active_proposals = Vec::new();
+			Proposals::<T>::translate::<v0::Proposal<BlockNumberFor<T>, ProposalActionOf<T>>, _>(
+				|proposal_id, proposal| {
+					translated.saturating_inc();
+					if proposal.state.can_update() {
+						active_proposals.push(proposal_id);
+					}
+					Some(Proposal {
+						proposer: unknown_proposer.clone(),
+						start: proposal.start,
+						start_cindex: proposal.start_cindex,
+						action: proposal.action,
+						state: proposal.state,
+					})
+				},
+			);
+			log::info!(target: TARGET, "{} proposals migrated", translated);
+
+			// oldest first, as they are closest to timing out
+			active_proposals.sort();
+			let max_active_proposals = T::MaxActiveProposals::get() as usize;
+			if active_proposals.len() > max_active_proposals {
+				log::warn!(
+					target: TARGET,
+					"{} active proposals exceed the maximum of {}. The newest ones have to be \
+					updated manually.",
+					active_proposals.len(),
+					max_active_proposals
+				);
+			}
+			ActiveProposals::<T>::put(BoundedVec::truncate_from(active_proposals));
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			weight.saturating_add(T::DbWeight::get().reads_writes(translated, translated + 2))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::DispatchError> {
+			assert_eq!(StorageVersion::get::<Pallet<T>>(), 1, "must upgrade");
+
+			let old_proposal_count: u32 =
+				Decode::decode(&mut &state[..]).expect("pre_upgrade provides a valid state; qed");
+			let new_proposal_count = Proposals::<T>::iter().count() as u32;
+			assert_eq!(old_proposal_count, new_proposal_count, "must migrate all proposals");
+			ensure!(
+				ActiveProposals::<T>::get()
+					.iter()
+					.all(|id| Proposals::<T>::get(id).map_or(false, |p| p.state.can_update())),
+				"active proposals must be ongoing or confirming"
+			);
+			ensure!(
+				Proposals::<T>::iter()
+					.all(|(id, p)| !p.state.can_update() ||
+						ActiveProposals::<T>::get().contains(&id)),
+				"all ongoing or confirming proposals must be active"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use mock::{new_test_ext, EncointerDemocracy, TestRuntime};
+
+	#[test]
+	fn migration_v0_to_v1_works() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+
+			let old_proposal = v0::Proposal {
+				start: 3u64,
+				start_cindex: 2,
+				action: ProposalAction::<u64, u64, u64>::SetInactivityTimeout(8),
+				state: ProposalState::<u64>::Confirming { since: 5 },
+			};
+			frame_support::storage::unhashed::put_raw(
+				&Proposals::<TestRuntime>::hashed_key_for(1),
+				&old_proposal.encode(),
+			);
+			let approved_proposal = v0::Proposal {
+				start: 1u64,
+				start_cindex: 1,
+				action: ProposalAction::<u64, u64, u64>::SetInactivityTimeout(7),
+				state: ProposalState::<u64>::Approved,
+			};
+			frame_support::storage::unhashed::put_raw(
+				&Proposals::<TestRuntime>::hashed_key_for(2),
+				&approved_proposal.encode(),
+			);
+
+			let _weight = v1::MigrateToV1::<TestRuntime>::on_runtime_upgrade();
+
+			assert_eq!(StorageVersion::get::<Pallet<TestRuntime>>(), 1);
+			let proposal = EncointerDemocracy::proposals(1).unwrap();
+			assert_eq!(
+				proposal.proposer,
+				<TestRuntime as frame_system::Config>::AccountId::decode(
+					&mut TrailingZeroInput::zeroes()
+				)
+				.unwrap()
+			);
+			assert_eq!(proposal.start, 3);
+			assert_eq!(proposal.start_cindex, 2);
+			assert_eq!(proposal.action, ProposalAction::SetInactivityTimeout(8));
+			assert_eq!(proposal.state, ProposalState::Confirming { since: 5 });
+			assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::Approved);
+
+			assert_eq!(EncointerDemocracy::active_proposals().into_inner(), vec![1]);
+		});
+	}
+
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn migration_v0_to_v1_fails_with_too_many_active_proposals() {
+		new_test_ext().execute_with(|| {
+			StorageVersion::new(0).put::<Pallet<TestRuntime>>();
+
+			let max_active_proposals =
+				<<TestRuntime as Config>::MaxActiveProposals as Get<u32>>::get();
+			for proposal_id in 1..=max_active_proposals as u128 + 1 {
+				let old_proposal = v0::Proposal {
+					start: 3u64,
+					start_cindex: 2,
+					action: ProposalAction::<u64, u64, u64>::SetInactivityTimeout(8),
+					state: ProposalState::<u64>::Ongoing,
+				};
+				frame_support::storage::unhashed::put_raw(
+					&Proposals::<TestRuntime>::hashed_key_for(proposal_id),
+					&old_proposal.encode(),
+				);
+			}
+
+			assert!(v1::MigrateToV1::<TestRuntime>::pre_upgrade().is_err());
+		});
+	}
+}
@@ -15,9 +15,15 @@
 // along with Encointer.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate as dut;
-use encointer_primitives::{balances::BalanceType, scheduler::CeremonyPhaseType};
+use encointer_primitives::{
+	balances::BalanceType,
+	ceremonies::ReputationScoreType,
+	democracy::{ProposalActionIdentifier, VoteThreshold},
+	scheduler::CeremonyPhaseType,
+};
+use frame_support::{parameter_types, weights::Weight, PalletId};
 use sp_runtime::{
-	traits::{ConstU128, ConstU64},
+	traits::{ConstU128, ConstU64, Convert},
 	BuildStorage,
 };
 use test_utils::*;
@@ -28,12 +34,14 @@ frame_support::construct_runtime!(
 	pub enum TestRuntime
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		EncointerScheduler: encointer_scheduler::{Pallet, Call, Storage, Config<T>, Event},
 		EncointerCommunities: encointer_communities::{Pallet, Call, Storage, Event<T>},
 		EncointerCeremonies: encointer_ceremonies::{Pallet, Call, Storage, Event<T>},
 		EncointerBalances: encointer_balances::{Pallet, Call, Storage, Event<T>},
-		EncointerDemocracy: dut::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
+		EncointerDemocracy: dut::{Pallet, Call, Storage, Config<T>, Event<T>, Origin},
 	}
 );
 
@@ -41,23 +49,73 @@ frame_support::construct_runtime!(
 // 	pub const MaxReputationVecLength: u32 = 10;
 // }
 
+parameter_types! {
+	// enough to advance two proposals per block
+	pub ProposalUpdateWeightBudget: Weight =
+		EncointerDemocracy::update_proposal_state_weight().saturating_mul(2);
+	pub static EnactmentWeightBudget: Weight = Weight::from_parts(1_000_000_000_000, 0);
+	// deposits and the reputation requirement are disabled unless a test enables them
+	pub static ProposalDepositAmount: Balance = 0;
+	pub static CommunityProposalDepositAmount: BalanceType = BalanceType::from_num(0);
+	pub static RequireReputationForCommunityProposals: bool = false;
+	pub static MinReputationScoreForCommunityProposals: ReputationScoreType = 0;
+	pub static DemurrageVoteThreshold: VoteThreshold = VoteThreshold::PositiveTurnoutBias;
+	pub const DepositEscrowPalletId: PalletId = PalletId(*b"ecr/demo");
+}
+
+pub struct TestTallyStrategies;
+impl Convert<ProposalActionIdentifier, VoteThreshold> for TestTallyStrategies {
+	fn convert(identifier: ProposalActionIdentifier) -> VoteThreshold {
+		match identifier {
+			ProposalActionIdentifier::UpdateDemurrage(_) => DemurrageVoteThreshold::get(),
+			_ => VoteThreshold::PositiveTurnoutBias,
+		}
+	}
+}
+
 impl dut::Config for TestRuntime {
 	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Preimages = Preimage;
 	type MaxReputationVecLength = ConstU32<10>;
 	type ConfirmationPeriod = ConstU64<10>;
 	type ProposalLifetime = ConstU64<40>;
 	type ProposalLifetimeCycles = ConstU32<1>;
 	type MinTurnout = ConstU128<20>; // 2%
+	type TallyStrategies = TestTallyStrategies;
+	type MaxActiveProposals = ConstU32<10>;
+	type ProposalUpdateWeightBudget = ProposalUpdateWeightBudget;
+	type EnactmentWeightBudget = EnactmentWeightBudget;
+	type Currency = pallet_balances::Pallet<TestRuntime>;
+	type ProposalDeposit = ProposalDepositAmount;
+	type CommunityProposalDeposit = CommunityProposalDepositAmount;
+	type DepositEscrowPalletId = DepositEscrowPalletId;
+	type RequireReputationForCommunityProposals = RequireReputationForCommunityProposals;
+	type MinReputationScoreForCommunityProposals = MinReputationScoreForCommunityProposals;
+	type MaxDelegatorsPerDelegate = ConstU32<2>;
 	type WeightInfo = (); // 2%
 }
 
 // boilerplate
+impl pallet_preimage::Config for TestRuntime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type ManagerOrigin = EnsureAlice;
+	type Consideration = ();
+}
+
 impl_frame_system!(TestRuntime);
+impl_balances!(TestRuntime, System);
 impl_timestamp!(TestRuntime, EncointerScheduler);
 impl_encointer_balances!(TestRuntime);
 impl_encointer_communities!(TestRuntime);
 impl_encointer_scheduler!(TestRuntime, EncointerDemocracy);
-impl_encointer_ceremonies!(TestRuntime);
+impl_encointer_ceremonies!(
+	TestRuntime,
+	frame_support::traits::EitherOfDiverse<EnsureAlice, dut::EnsureEncointerDemocracy>
+);
 
 // genesis values
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -17,21 +17,33 @@
 //! Unit tests for the tokens module.
 
 use super::*;
-use crate::mock::{EncointerCeremonies, EncointerCommunities, EncointerScheduler, Timestamp};
+use crate::mock::{
+	Balances, CommunityProposalDepositAmount, DemurrageVoteThreshold, EnactmentWeightBudget,
+	EncointerBalances, EncointerCeremonies, EncointerCommunities, EncointerScheduler,
+	MinReputationScoreForCommunityProposals, ProposalDepositAmount,
+	RequireReputationForCommunityProposals, Timestamp,
+};
 use encointer_primitives::{
-	ceremonies::{InactivityTimeoutType, Reputation},
+	balances::{BalanceType, Demurrage},
+	ceremonies::{InactivityTimeoutType, MeetupValidationPolicy, Reputation, VoteMajority},
+	common::{BoundedIpfsCid, FromStr},
 	communities::{CommunityIdentifier, NominalIncome as NominalIncomeType},
-	democracy::{ProposalAction, ProposalActionIdentifier, ProposalState, Tally, Vote},
+	democracy::{
+		Delegation, ProposalAccessPolicy, ProposalAction, ProposalActionIdentifier,
+		ProposalDeposit, ProposalState, Tally, Vote, VoteThreshold,
+	},
 };
 use frame_support::{
 	assert_err, assert_ok,
-	traits::{OnFinalize, OnInitialize},
+	dispatch::GetDispatchInfo,
+	traits::{Currency, OnFinalize, OnInitialize},
+	weights::{RuntimeDbWeight, Weight},
 };
 use frame_system::pallet_prelude::BlockNumberFor;
-use mock::{new_test_ext, EncointerDemocracy, RuntimeOrigin, System, TestRuntime};
-use sp_runtime::BoundedVec;
+use mock::{new_test_ext, EncointerDemocracy, RuntimeCall, RuntimeOrigin, System, TestRuntime};
+use sp_runtime::{BoundedVec, DispatchError, Perbill};
 use test_utils::{
-	helpers::{account_id, add_population, register_test_community},
+	helpers::{account_id, add_population, event_deposited, last_event, register_test_community},
 	*,
 };
 
@@ -47,6 +59,10 @@ fn bob() -> AccountId {
 	AccountKeyring::Bob.into()
 }
 
+fn charlie() -> AccountId {
+	AccountKeyring::Charlie.into()
+}
+
 type BlockNumber = BlockNumberFor<TestRuntime>;
 
 fn advance_n_blocks(n: u64) {
@@ -70,6 +86,7 @@ fn run_to_block(n: u64) {
 		Timestamp::on_finalize(System::block_number());
 		System::set_block_number(System::block_number() + 1);
 		System::on_initialize(System::block_number());
+		EncointerDemocracy::on_initialize(System::block_number());
 	}
 }
 
@@ -100,6 +117,7 @@ fn proposal_submission_works() {
 		assert_eq!(proposal.state, ProposalState::Ongoing);
 		assert_eq!(proposal.action, proposal_action);
 		assert_eq!(proposal.start, block);
+		assert_eq!(proposal.proposer, alice());
 		assert!(EncointerDemocracy::tallies(1).is_some());
 	});
 }
@@ -123,6 +141,246 @@ fn proposal_submission_fails_if_proposal_in_enactment_queue() {
 	});
 }
 
+#[test]
+fn native_deposit_is_reserved_and_refunded_upon_enactment() {
+	new_test_ext().execute_with(|| {
+		ProposalDepositAmount::set(10);
+		let alice = alice();
+		Balances::make_free_balance_be(&alice, 100);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_eq!(Balances::reserved_balance(&alice), 10);
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), Some(ProposalDeposit::Native(10)));
+
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+		assert_ok!(EncointerDemocracy::enact_proposal(1));
+
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(Balances::free_balance(&alice), 100);
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), None);
+	});
+}
+
+#[test]
+fn native_deposit_is_slashed_upon_timeout() {
+	new_test_ext().execute_with(|| {
+		ProposalDepositAmount::set(10);
+		let alice = alice();
+		Balances::make_free_balance_be(&alice, 100);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+
+		advance_n_blocks(41);
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(Balances::free_balance(&alice), 90);
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), None);
+	});
+}
+
+#[test]
+fn native_deposit_is_refunded_if_proposal_is_superseded() {
+	new_test_ext().execute_with(|| {
+		ProposalDepositAmount::set(10);
+		let alice = alice();
+		Balances::make_free_balance_be(&alice, 100);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+
+		CancelledAtBlock::<TestRuntime>::insert(ProposalActionIdentifier::SetInactivityTimeout, 3);
+		advance_n_blocks(5);
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+
+		assert_eq!(Balances::reserved_balance(&alice), 0);
+		assert_eq!(Balances::free_balance(&alice), 100);
+	});
+}
+
+#[test]
+fn submit_proposal_fails_with_insufficient_deposit() {
+	new_test_ext().execute_with(|| {
+		ProposalDepositAmount::set(10);
+		let cid = create_cid();
+		CommunityProposalDepositAmount::set(BalanceType::from_num(1));
+
+		assert!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(bob()),
+			ProposalAction::SetInactivityTimeout(8)
+		)
+		.is_err());
+		assert!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(bob()),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32))
+		)
+		.is_err());
+		assert_eq!(EncointerDemocracy::proposal_count(), 0);
+	});
+}
+
+#[test]
+fn community_deposit_is_held_in_escrow_and_refunded_upon_enactment() {
+	new_test_ext().execute_with(|| {
+		CommunityProposalDepositAmount::set(BalanceType::from_num(1));
+		let cid = create_cid();
+		let alice = alice();
+		assert_ok!(EncointerBalances::issue(cid, &alice, BalanceType::from_num(10)));
+		let total_issuance = EncointerBalances::total_issuance(cid);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32))
+		));
+		let escrow = EncointerDemocracy::deposit_escrow_account(1);
+		assert_eq!(EncointerBalances::balance(cid, &alice), BalanceType::from_num(9));
+		assert_eq!(EncointerBalances::balance(cid, &escrow), BalanceType::from_num(1));
+		assert_eq!(EncointerBalances::total_issuance(cid), total_issuance);
+		assert_eq!(
+			EncointerDemocracy::proposal_deposits(1),
+			Some(ProposalDeposit::Community(cid, BalanceType::from_num(1)))
+		);
+
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+		assert_ok!(EncointerDemocracy::enact_proposal(1));
+
+		assert_eq!(EncointerBalances::balance(cid, &alice), BalanceType::from_num(10));
+		assert_eq!(EncointerBalances::balance(cid, &escrow), BalanceType::from_num(0));
+		assert_eq!(EncointerBalances::total_issuance(cid), total_issuance);
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), None);
+	});
+}
+
+#[test]
+fn community_deposit_is_moved_to_treasury_upon_timeout() {
+	new_test_ext().execute_with(|| {
+		CommunityProposalDepositAmount::set(BalanceType::from_num(1));
+		let cid = create_cid();
+		let alice = alice();
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		// no demurrage to keep the numbers simple
+		encointer_balances::DemurragePerBlock::<TestRuntime>::insert(cid, Demurrage::from_num(0));
+		assert_ok!(EncointerBalances::issue(cid, &alice, BalanceType::from_num(10)));
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32))
+		));
+
+		advance_n_blocks(41);
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+
+		assert_eq!(EncointerBalances::balance(cid, &alice), BalanceType::from_num(9));
+		assert_eq!(EncointerBalances::balance(cid, &treasury), BalanceType::from_num(1));
+		assert_eq!(
+			EncointerBalances::balance(cid, &EncointerDemocracy::deposit_escrow_account(1)),
+			BalanceType::from_num(0)
+		);
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), None);
+	});
+}
+
+#[test]
+fn community_deposit_is_refunded_upon_failed_enactment() {
+	new_test_ext().execute_with(|| {
+		CommunityProposalDepositAmount::set(BalanceType::from_num(1));
+		let cid = create_cid();
+		let alice = alice();
+		assert_ok!(EncointerBalances::issue(cid, &alice, BalanceType::from_num(10)));
+
+		// the treasury is empty
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SpendCommunityTreasury(cid, bob(), BalanceType::from_num(5))
+		));
+		assert_eq!(EncointerBalances::balance(cid, &alice), BalanceType::from_num(9));
+
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+		assert_ok!(EncointerDemocracy::enact_proposal(1));
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::EnactmentFailed);
+		assert_eq!(EncointerBalances::balance(cid, &alice), BalanceType::from_num(10));
+		assert_eq!(EncointerDemocracy::proposal_deposits(1), None);
+	});
+}
+
+#[test]
+fn community_proposals_require_reputation_if_configured() {
+	new_test_ext().execute_with(|| {
+		RequireReputationForCommunityProposals::set(true);
+		let cid = create_cid();
+		let alice = alice();
+		let proposal_action =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
+
+		assert_err!(
+			EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_action.clone()
+			),
+			Error::<TestRuntime>::NoReputationInCommunity
+		);
+		// global proposals are not affected
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+
+		EncointerCeremonies::fake_reputation((cid, 6), &alice, Reputation::VerifiedUnlinked);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice),
+			proposal_action
+		));
+	});
+}
+
+#[test]
+fn community_proposals_require_min_reputation_score_if_configured() {
+	new_test_ext().execute_with(|| {
+		RequireReputationForCommunityProposals::set(true);
+		let cid = create_cid();
+		let alice = alice();
+		let proposal_action =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
+
+		EncointerCeremonies::fake_reputation((cid, 6), &alice, Reputation::VerifiedUnlinked);
+		let score = EncointerCeremonies::reputation_score(&alice, &cid).score;
+		MinReputationScoreForCommunityProposals::set(score + 1);
+		assert_err!(
+			EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_action.clone()
+			),
+			Error::<TestRuntime>::ReputationScoreTooLow
+		);
+
+		// attending the ceremony before extends the streak
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedUnlinked);
+		assert!(EncointerCeremonies::reputation_score(&alice, &cid).score > score);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice),
+			proposal_action
+		));
+	});
+}
+
 #[test]
 fn eligible_reputations_works_with_different_reputations() {
 	new_test_ext().execute_with(|| {
@@ -368,388 +626,1942 @@ fn voting_works() {
 }
 
 #[test]
-fn do_update_proposal_state_fails_with_inexistent_proposal() {
+fn change_vote_works() {
 	new_test_ext().execute_with(|| {
-		assert_err!(
-			EncointerDemocracy::do_update_proposal_state(1),
-			Error::<TestRuntime>::InexistentProposal
-		);
+		let cid = create_cid();
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4)]).unwrap()
+		));
+
+		assert_ok!(EncointerDemocracy::change_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+			Default::default()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 2, ayes: 1 });
+		assert_eq!(EncointerDemocracy::vote_directions(1, (&alice, (cid, 3))), Some(Vote::Nay));
+
+		// changing to the same direction is a no-op
+		assert_ok!(EncointerDemocracy::change_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4)]).unwrap(),
+			Default::default()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 2, ayes: 0 });
+
+		assert_ok!(EncointerDemocracy::change_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4)]).unwrap(),
+			Default::default()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 2, ayes: 2 });
 	});
 }
 
 #[test]
-fn do_update_proposal_state_fails_with_wrong_state() {
+fn change_vote_fails_without_previous_vote() {
 	new_test_ext().execute_with(|| {
 		let cid = create_cid();
-		let proposal: Proposal<BlockNumber> = Proposal {
-			start: BlockNumber::from(1u64),
-			start_cindex: 1,
-			action: ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32)),
-			state: ProposalState::Cancelled,
-		};
-		Proposals::<TestRuntime>::insert(1, proposal);
+		let alice = alice();
 
-		let proposal2: Proposal<BlockNumber> = Proposal {
-			start: BlockNumber::from(1u64),
-			start_cindex: 1,
-			action: ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32)),
-			state: ProposalState::Approved,
-		};
-		Proposals::<TestRuntime>::insert(2, proposal2);
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 3), &bob(), Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(bob()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
 
 		assert_err!(
-			EncointerDemocracy::do_update_proposal_state(1),
-			Error::<TestRuntime>::ProposalCannotBeUpdated
+			EncointerDemocracy::change_vote(
+				RuntimeOrigin::signed(alice.clone()),
+				1,
+				Vote::Nay,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::InexistentVote
 		);
-
 		assert_err!(
-			EncointerDemocracy::do_update_proposal_state(2),
-			Error::<TestRuntime>::ProposalCannotBeUpdated
+			EncointerDemocracy::remove_vote(
+				RuntimeOrigin::signed(alice),
+				1,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::InexistentVote
 		);
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 1 });
 	});
 }
 
 #[test]
-fn do_update_proposal_state_works_with_cancelled_proposal() {
+fn votes_without_direction_can_neither_be_recast_nor_changed() {
 	new_test_ext().execute_with(|| {
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		let cid = create_cid();
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
 
 		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice()),
-			proposal_action
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
 		));
+		// vote cast before vote directions were recorded
+		VoteEntries::<TestRuntime>::insert(1, (alice.clone(), (cid, 3)), ());
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 1, ayes: 1 });
 
-		CancelledAtBlock::<TestRuntime>::insert(ProposalActionIdentifier::SetInactivityTimeout, 3);
-
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
-
-		advance_n_blocks(5);
-
-		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 1 });
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+		assert_err!(
+			EncointerDemocracy::change_vote(
+				RuntimeOrigin::signed(alice.clone()),
+				1,
+				Vote::Nay,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::InexistentVote
+		);
+		assert_err!(
+			EncointerDemocracy::remove_vote(
+				RuntimeOrigin::signed(alice),
+				1,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::InexistentVote
+		);
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 1 });
 	});
 }
 
 #[test]
-fn do_update_proposal_state_works_with_too_old_proposal() {
+fn remove_vote_works() {
 	new_test_ext().execute_with(|| {
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		let cid = create_cid();
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
 
 		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice()),
-			proposal_action
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
 		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 4)]).unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 2, ayes: 1 });
+
+		assert_ok!(EncointerDemocracy::remove_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+			Default::default()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 0 });
+		assert_eq!(EncointerDemocracy::vote_directions(1, (&alice, (cid, 3))), None);
+		assert!(!VoteEntries::<TestRuntime>::contains_key(1, (&alice, (cid, 3))));
+
+		assert_ok!(EncointerDemocracy::remove_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			BoundedVec::try_from(vec![(cid, 4)]).unwrap(),
+			Default::default()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 0, ayes: 0 });
+
+		// retracted reputations can be used again
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4)]).unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 2, ayes: 2 });
+	});
+}
+
+#[test]
+fn change_and_remove_vote_fail_for_finished_proposal() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+
+		assert_err!(
+			EncointerDemocracy::change_vote(
+				RuntimeOrigin::signed(alice.clone()),
+				1,
+				Vote::Nay,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::ProposalCannotBeUpdated
+		);
+		assert_err!(
+			EncointerDemocracy::remove_vote(
+				RuntimeOrigin::signed(alice),
+				1,
+				BoundedVec::try_from(vec![(cid, 3)]).unwrap(),
+				Default::default()
+			),
+			Error::<TestRuntime>::ProposalCannotBeUpdated
+		);
+	});
+}
+
+#[test]
+fn delegate_and_undelegate_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		let scope = ProposalAccessPolicy::Global;
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedUnlinked);
+
+		assert_err!(
+			EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(alice.clone()),
+				scope,
+				alice.clone(),
+				(cid, 5)
+			),
+			Error::<TestRuntime>::CannotDelegateToSelf
+		);
+
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			scope,
+			bob(),
+			(cid, 5)
+		));
+		assert_eq!(
+			EncointerDemocracy::delegations(&alice, scope),
+			Some(Delegation { delegate: bob(), cindex: 7 })
+		);
+		assert_eq!(EncointerDemocracy::delegators(bob(), scope).into_inner(), vec![alice.clone()]);
+
+		// re-delegating replaces the previous delegation
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			scope,
+			charlie(),
+			(cid, 5)
+		));
+		assert!(EncointerDemocracy::delegators(bob(), scope).is_empty());
+		assert_eq!(
+			EncointerDemocracy::delegators(charlie(), scope).into_inner(),
+			vec![alice.clone()]
+		);
+
+		assert_ok!(EncointerDemocracy::undelegate(RuntimeOrigin::signed(alice.clone()), scope));
+		assert_eq!(EncointerDemocracy::delegations(&alice, scope), None);
+		assert!(EncointerDemocracy::delegators(charlie(), scope).is_empty());
+		assert_err!(
+			EncointerDemocracy::undelegate(RuntimeOrigin::signed(alice), scope),
+			Error::<TestRuntime>::InexistentDelegation
+		);
+	});
+}
+
+#[test]
+fn delegate_fails_without_reputation_in_scope() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let alice = alice();
+
+		for scope in [ProposalAccessPolicy::Global, ProposalAccessPolicy::Community(cid)] {
+			assert_err!(
+				EncointerDemocracy::delegate(
+					RuntimeOrigin::signed(alice.clone()),
+					scope,
+					bob(),
+					(cid, 5)
+				),
+				Error::<TestRuntime>::NoReputationToDelegate
+			);
+		}
+
+		// reputation in another community only suffices for the global scope
+		EncointerCeremonies::fake_reputation((cid2, 5), &alice, Reputation::VerifiedUnlinked);
+		assert_err!(
+			EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(alice.clone()),
+				ProposalAccessPolicy::Community(cid),
+				bob(),
+				(cid2, 5)
+			),
+			Error::<TestRuntime>::NoReputationToDelegate
+		);
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAccessPolicy::Global,
+			bob(),
+			(cid2, 5)
+		));
+
+		// the reputation has to be the sender's
+		assert_err!(
+			EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(alice.clone()),
+				ProposalAccessPolicy::Global,
+				bob(),
+				(cid2, 6)
+			),
+			Error::<TestRuntime>::NoReputationToDelegate
+		);
+
+		// expired reputation does not count
+		EncointerCeremonies::fake_reputation((cid, 1), &alice, Reputation::VerifiedUnlinked);
+		assert_err!(
+			EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(alice),
+				ProposalAccessPolicy::Community(cid),
+				bob(),
+				(cid, 1)
+			),
+			Error::<TestRuntime>::NoReputationToDelegate
+		);
+	});
+}
+
+#[test]
+fn delegate_fails_with_too_many_delegators_unless_expired() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let scope = ProposalAccessPolicy::Global;
+		let dave: AccountId = AccountKeyring::Dave.into();
+		for delegator in [alice(), charlie(), dave.clone()] {
+			EncointerCeremonies::fake_reputation(
+				(cid, 5),
+				&delegator,
+				Reputation::VerifiedUnlinked,
+			);
+		}
+
+		for delegator in [alice(), charlie()] {
+			assert_ok!(EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(delegator),
+				scope,
+				bob(),
+				(cid, 5)
+			));
+		}
+		assert_err!(
+			EncointerDemocracy::delegate(
+				RuntimeOrigin::signed(dave.clone()),
+				scope,
+				bob(),
+				(cid, 5)
+			),
+			Error::<TestRuntime>::TooManyDelegators
+		);
+
+		// alice's delegation expires with the reputation lifetime
+		Delegations::<TestRuntime>::insert(
+			alice(),
+			scope,
+			Delegation { delegate: bob(), cindex: 1 },
+		);
+		assert!(!EncointerDemocracy::is_delegation_active(&alice(), scope, &bob()));
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(dave.clone()),
+			scope,
+			bob(),
+			(cid, 5)
+		));
+		assert_eq!(
+			EncointerDemocracy::delegators(bob(), scope).into_inner(),
+			vec![charlie(), dave]
+		);
+	});
+}
+
+#[test]
+fn delegated_reputations_are_counted_for_delegate() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		let bob = bob();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &bob, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAccessPolicy::Global,
+			bob.clone(),
+			(cid, 3)
+		));
+		assert_eq!(
+			EncointerDemocracy::delegated_reputations(1, &bob).unwrap(),
+			vec![(alice.clone(), (cid, 3)), (alice.clone(), (cid, 4))]
+		);
+
+		let delegated_reputations: DelegatedReputationVecOf<TestRuntime> =
+			BoundedVec::try_from(vec![
+				(alice.clone(), (cid, 3)),
+				(alice.clone(), (cid, 4)),
+				(alice.clone(), (cid, 3)),
+			])
+			.unwrap();
+		// a plain vote does not use delegated reputations
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(bob.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 5)]).unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 1 });
+		// duplicates are counted once
+		assert_ok!(EncointerDemocracy::vote_with_delegations(
+			RuntimeOrigin::signed(bob.clone()),
+			1,
+			Vote::Aye,
+			Default::default(),
+			delegated_reputations.clone()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 3, ayes: 3 });
+		assert_eq!(
+			EncointerDemocracy::delegated_vote_entries(1, (alice.clone(), (cid, 3))),
+			Some(bob.clone())
+		);
+		assert!(EncointerDemocracy::delegated_reputations(1, &bob).unwrap().is_empty());
+
+		// the delegate changes the delegated votes along with its own
+		assert_ok!(EncointerDemocracy::change_vote(
+			RuntimeOrigin::signed(bob.clone()),
+			1,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 5)]).unwrap(),
+			delegated_reputations.clone()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 3, ayes: 0 });
+
+		// a direct vote of the delegator overrides the delegated vote
+		assert_eq!(
+			EncointerDemocracy::eligible_reputations(
+				1,
+				&alice,
+				&BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+			)
+			.unwrap()
+			.into_inner(),
+			vec![(cid, 3)]
+		);
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 3, ayes: 1 });
+		assert_eq!(EncointerDemocracy::delegated_vote_entries(1, (alice.clone(), (cid, 3))), None);
+
+		// removing the delegate's votes leaves the direct vote untouched
+		assert_ok!(EncointerDemocracy::remove_vote(
+			RuntimeOrigin::signed(bob),
+			1,
+			BoundedVec::try_from(vec![(cid, 5)]).unwrap(),
+			delegated_reputations
+		));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 1, ayes: 1 });
+		assert_eq!(EncointerDemocracy::vote_directions(1, (alice, (cid, 3))), Some(Vote::Aye));
+	});
+}
+
+#[test]
+fn delegated_reputations_respect_scope_direct_votes_and_expiry() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		let bob = bob();
+		let dave: AccountId = AccountKeyring::Dave.into();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &charlie(), Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &dave, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32))
+		));
+
+		// community delegations do not apply to global proposals
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAccessPolicy::Community(cid),
+			bob.clone(),
+			(cid, 4)
+		));
+		assert!(EncointerDemocracy::delegated_reputations(1, &bob).unwrap().is_empty());
+
+		// reputations the delegator has used directly are not delegated
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			2,
+			Vote::Nay,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+		assert_eq!(
+			EncointerDemocracy::delegated_reputations(2, &bob).unwrap(),
+			vec![(alice.clone(), (cid, 4))]
+		);
+
+		// expired delegations are not counted
+		Delegations::<TestRuntime>::insert(
+			charlie(),
+			ProposalAccessPolicy::Community(cid),
+			Delegation { delegate: bob.clone(), cindex: 1 },
+		);
+		Delegators::<TestRuntime>::mutate(&bob, ProposalAccessPolicy::Community(cid), |d| {
+			d.try_push(charlie()).unwrap()
+		});
+		assert_eq!(
+			EncointerDemocracy::delegated_reputations(2, &bob).unwrap(),
+			vec![(alice.clone(), (cid, 4))]
+		);
+
+		// ineligible delegated reputations are ignored when voting
+		assert_ok!(EncointerDemocracy::vote_with_delegations(
+			RuntimeOrigin::signed(bob.clone()),
+			2,
+			Vote::Aye,
+			Default::default(),
+			BoundedVec::try_from(vec![
+				(alice.clone(), (cid, 3)), // used directly
+				(alice.clone(), (cid, 4)), // valid
+				(charlie(), (cid, 5)),     // expired delegation
+				(dave, (cid, 5)),          // no delegation
+			])
+			.unwrap()
+		));
+		assert_eq!(EncointerDemocracy::tallies(2).unwrap(), Tally { turnout: 2, ayes: 1 });
+		assert_eq!(EncointerDemocracy::delegated_vote_entries(2, (alice, (cid, 4))), Some(bob));
+	});
+}
+
+#[test]
+fn do_update_proposal_state_fails_with_inexistent_proposal() {
+	new_test_ext().execute_with(|| {
+		assert_err!(
+			EncointerDemocracy::do_update_proposal_state(1),
+			Error::<TestRuntime>::InexistentProposal
+		);
+	});
+}
+
+#[test]
+fn do_update_proposal_state_fails_with_wrong_state() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let proposal: ProposalOf<TestRuntime> = Proposal {
+			proposer: alice(),
+			start: BlockNumber::from(1u64),
+			start_cindex: 1,
+			action: ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32)),
+			state: ProposalState::Cancelled,
+		};
+		Proposals::<TestRuntime>::insert(1, proposal);
+
+		let proposal2: ProposalOf<TestRuntime> = Proposal {
+			proposer: alice(),
+			start: BlockNumber::from(1u64),
+			start_cindex: 1,
+			action: ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32)),
+			state: ProposalState::Approved,
+		};
+		Proposals::<TestRuntime>::insert(2, proposal2);
+
+		assert_err!(
+			EncointerDemocracy::do_update_proposal_state(1),
+			Error::<TestRuntime>::ProposalCannotBeUpdated
+		);
+
+		assert_err!(
+			EncointerDemocracy::do_update_proposal_state(2),
+			Error::<TestRuntime>::ProposalCannotBeUpdated
+		);
+	});
+}
+
+#[test]
+fn do_update_proposal_state_works_with_cancelled_proposal() {
+	new_test_ext().execute_with(|| {
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			proposal_action
+		));
+
+		CancelledAtBlock::<TestRuntime>::insert(ProposalActionIdentifier::SetInactivityTimeout, 3);
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		advance_n_blocks(5);
+
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+	});
+}
+
+#[test]
+fn do_update_proposal_state_works_with_too_old_proposal() {
+	new_test_ext().execute_with(|| {
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			proposal_action
+		));
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		advance_n_blocks(40);
+
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		advance_n_blocks(1);
+
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+	});
+}
+
+#[test]
+fn do_update_proposal_state_works() {
+	new_test_ext().execute_with(|| {
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+
+		let alice = alice();
+		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+
+		// make sure electorate is 100
+		let pairs = add_population(100, 0);
+		for p in pairs {
+			EncointerCeremonies::fake_reputation(
+				(cid, 5),
+				&account_id(&p),
+				Reputation::VerifiedLinked,
+			);
+		}
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice),
+			proposal_action.clone()
+		));
+
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		// propsal is passing
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 100 });
+
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+		assert_eq!(
+			EncointerDemocracy::proposals(1).unwrap().state,
+			ProposalState::Confirming { since: 0 }
+		);
+
+		// not passing anymore
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 0 });
+
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		// nothing changes if repeated
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		// passing
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 100 });
+
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+		assert_eq!(
+			EncointerDemocracy::proposals(1).unwrap().state,
+			ProposalState::Confirming { since: 0 }
+		);
+
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		advance_n_blocks(11);
+		// proposal is enacted
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), true);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Approved);
+		assert_eq!(
+			EncointerDemocracy::enactment_queue(proposal_action.get_identifier()).unwrap(),
+			1
+		);
+	});
+}
+
+#[test]
+fn update_proposal_state_extrinsic_works() {
+	new_test_ext().execute_with(|| {
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+
+		let alice = alice();
+		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action
+		));
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		// propsal is passing
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 3, ayes: 3 });
+		EncointerDemocracy::update_proposal_state(RuntimeOrigin::signed(alice), 1).unwrap();
+		assert_eq!(
+			EncointerDemocracy::proposals(1).unwrap().state,
+			ProposalState::Confirming { since: 0 }
+		);
+	});
+}
+
+#[test]
+fn active_proposals_are_advanced_in_on_initialize() {
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+
+		for proposal_action in [
+			ProposalAction::SetInactivityTimeout(8),
+			ProposalAction::SetReputationLifetime(8),
+			ProposalAction::SetLocationTolerance(8),
+		] {
+			assert_ok!(EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_action
+			));
+		}
+		assert_eq!(EncointerDemocracy::active_proposals().into_inner(), vec![1, 2, 3]);
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+		// proposals 1 and 3 are passing
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 3, ayes: 3 });
+		Tallies::<TestRuntime>::insert(3, Tally { turnout: 3, ayes: 3 });
+
+		// the weight budget suffices for two proposals per block
+		EncointerDemocracy::on_initialize(System::block_number());
+		assert_eq!(
+			EncointerDemocracy::proposals(1).unwrap().state,
+			ProposalState::Confirming { since: 0 }
+		);
+		assert_eq!(EncointerDemocracy::proposals(3).unwrap().state, ProposalState::Ongoing);
+		assert_eq!(EncointerDemocracy::active_proposals().into_inner(), vec![3, 1, 2]);
+
+		EncointerDemocracy::on_initialize(System::block_number());
+		assert_eq!(
+			EncointerDemocracy::proposals(3).unwrap().state,
+			ProposalState::Confirming { since: 0 }
+		);
+		assert_eq!(EncointerDemocracy::active_proposals().into_inner(), vec![2, 3, 1]);
+
+		advance_n_blocks(11);
+		EncointerDemocracy::on_initialize(System::block_number());
+		EncointerDemocracy::on_initialize(System::block_number());
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Approved);
+		assert_eq!(EncointerDemocracy::proposals(3).unwrap().state, ProposalState::Approved);
+		assert_eq!(
+			EncointerDemocracy::enactment_queue(ProposalActionIdentifier::SetInactivityTimeout),
+			Some(1)
+		);
+		assert_eq!(EncointerDemocracy::active_proposals().into_inner(), vec![2]);
+
+		// too old proposals are cancelled
+		advance_n_blocks(30);
+		EncointerDemocracy::on_initialize(System::block_number());
+		assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::Cancelled);
+		assert!(EncointerDemocracy::active_proposals().is_empty());
+	});
+}
+
+#[test]
+fn advance_active_proposals_respects_weight_budget() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		advance_n_blocks(41);
+
+		assert_eq!(EncointerDemocracy::advance_active_proposals(Weight::zero()), Weight::zero());
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+
+		let used_weight = EncointerDemocracy::advance_active_proposals(Weight::MAX);
+		assert_eq!(used_weight, EncointerDemocracy::update_proposal_state_weight());
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+		assert!(EncointerDemocracy::active_proposals().is_empty());
+	});
+}
+
+#[test]
+fn submit_proposal_fails_with_too_many_active_proposals() {
+	new_test_ext().execute_with(|| {
+		for i in 0..10 {
+			assert_ok!(EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice()),
+				ProposalAction::SetInactivityTimeout(i)
+			));
+		}
+		assert_err!(
+			EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice()),
+				ProposalAction::SetInactivityTimeout(10)
+			),
+			Error::<TestRuntime>::TooManyActiveProposals
+		);
+	});
+}
+
+#[test]
+fn get_proposals_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5)]).unwrap()
+		));
+
+		advance_n_blocks(4);
+		let proposals = EncointerDemocracy::get_proposals();
+		assert_eq!(proposals.len(), 1);
+		let info = &proposals[0];
+		assert_eq!(info.proposal_id, 1);
+		assert_eq!(info.proposal.proposer, alice);
+		assert_eq!(info.proposal.action, ProposalAction::SetInactivityTimeout(8));
+		assert_eq!(info.proposal.state, ProposalState::Confirming { since: 0 });
+		assert_eq!(info.tally, Tally { turnout: 3, ayes: 3 });
+		assert_eq!(info.electorate, 3);
+		assert!(info.is_passing);
+		// approval is possible once the confirmation period of 10 blocks has been exceeded
+		assert_eq!(info.remaining_confirmation_blocks, Some(7));
+
+		assert_eq!(EncointerDemocracy::get_proposal_info(2), None);
+	});
+}
+
+#[test]
+fn get_proposals_represents_calls_by_their_hash() {
+	new_test_ext().execute_with(|| {
+		let call =
+			RuntimeCall::EncointerCeremonies(encointer_ceremonies::Call::set_location_tolerance {
+				location_tolerance: 123,
+			});
+		let bounded_call = <TestRuntime as Config>::Preimages::bound(call).unwrap();
+		let hash = bounded_call.hash();
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			ProposalAction::Call(bounded_call)
+		));
+
+		let info = EncointerDemocracy::get_proposal_info(1).unwrap();
+		assert_eq!(info.proposal.action, ProposalAction::Call(hash));
+		assert!(!info.is_passing);
+		assert_eq!(info.remaining_confirmation_blocks, None);
+	});
+}
+
+#[test]
+fn get_eligible_reputations_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let alice = alice();
+
+		EncointerCeremonies::fake_reputation((cid, 2), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::Unverified);
+		EncointerCeremonies::fake_reputation((cid2, 5), &alice, Reputation::VerifiedUnlinked);
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32))
+		));
+
+		assert_eq!(
+			EncointerDemocracy::get_eligible_reputations(1, &alice).unwrap(),
+			vec![(cid, 3), (cid2, 5)]
+		);
+		assert_eq!(
+			EncointerDemocracy::get_eligible_reputations(2, &alice).unwrap(),
+			vec![(cid, 3)]
+		);
+
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3)]).unwrap()
+		));
+		assert_eq!(
+			EncointerDemocracy::get_eligible_reputations(1, &alice).unwrap(),
+			vec![(cid2, 5)]
+		);
+		assert_err!(
+			EncointerDemocracy::get_eligible_reputations(3, &alice),
+			Error::<TestRuntime>::InexistentProposal
+		);
+	});
+}
+
+#[test]
+fn test_get_electorate_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let alice = alice();
+		let bob = bob();
+
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid2, 3), &bob, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid2, 4), &bob, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid2, 5), &bob, Reputation::VerifiedLinked);
+
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action
+		));
+
+		let proposal_action =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action
+		));
+
+		assert_eq!(EncointerDemocracy::get_electorate(1).unwrap(), 5);
+		assert_eq!(EncointerDemocracy::get_electorate(2).unwrap(), 2);
+	});
+}
+
+#[test]
+fn is_passing_works() {
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+
+		// electorate is 100
+		let pairs = add_population(100, 0);
+		for p in pairs {
+			EncointerCeremonies::fake_reputation(
+				(cid, 5),
+				&account_id(&p),
+				Reputation::VerifiedLinked,
+			);
+		}
+
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action
+		));
+
+		// turnout below threshold
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 1, ayes: 1 });
+		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), false);
+
+		// low turnout, 60 % approval
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 10, ayes: 6 });
+		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), false);
+
+		// low turnout 90 % approval
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 10, ayes: 9 });
+		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+
+		// high turnout, 60 % approval
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 60 });
+		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+
+		// high turnout 90 % approval
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 90 });
+		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+	});
+}
+
+#[test]
+fn is_passing_uses_tally_strategy_of_proposal_action() {
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+
+		// electorate is 100
+		let pairs = add_population(100, 0);
+		for p in pairs {
+			EncointerCeremonies::fake_reputation(
+				(cid, 5),
+				&account_id(&p),
+				Reputation::VerifiedLinked,
+			);
+		}
+		DemurrageVoteThreshold::set(VoteThreshold::Supermajority(Perbill::from_percent(66)));
+
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::UpdateDemurrage(cid, Demurrage::from_num(0.5))
+		));
+
+		// high turnout, 60 % approval
+		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 60 });
+		Tallies::<TestRuntime>::insert(2, Tally { turnout: 100, ayes: 60 });
+		assert!(EncointerDemocracy::is_passing(1).unwrap());
+		assert!(!EncointerDemocracy::is_passing(2).unwrap());
+
+		// high turnout, 70 % approval
+		Tallies::<TestRuntime>::insert(2, Tally { turnout: 100, ayes: 70 });
+		assert!(EncointerDemocracy::is_passing(2).unwrap());
+
+		// the minimum turnout still applies
+		Tallies::<TestRuntime>::insert(2, Tally { turnout: 1, ayes: 1 });
+		assert!(!EncointerDemocracy::is_passing(2).unwrap());
+	});
+}
+
+#[test]
+fn enactment_updates_proposal_metadata_and_enactment_queue() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
+
+		let proposal_action2 =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action2.clone()
+		));
+
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
+		EnactmentQueue::<TestRuntime>::insert(proposal_action2.get_identifier(), 2);
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		advance_n_blocks(40);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
 
-		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::Enacted);
 
-		advance_n_blocks(1);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
 
-		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Cancelled);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action2.get_identifier()), None);
 	});
 }
 
 #[test]
-fn do_update_proposal_state_works() {
+fn proposal_happy_flow() {
 	new_test_ext().execute_with(|| {
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		let cid = create_cid();
+		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let alice = alice();
+		let proposal_action =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(13037u32));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
+
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid2, 3), &alice, Reputation::VerifiedLinked);
+
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5),]).unwrap()
+		));
+
+		advance_n_blocks(40);
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid2, 3)]).unwrap()
+		));
 
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		assert_eq!(EncointerCommunities::nominal_income(cid), NominalIncomeType::from(13037u32));
+	});
+}
+
+#[test]
+fn enact_update_nominal_income_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
 		let alice = alice();
-		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
+		let proposal_action =
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(13037u32));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
 
-		// make sure electorate is 100
-		let pairs = add_population(100, 0);
-		for p in pairs {
-			EncointerCeremonies::fake_reputation(
-				(cid, 5),
-				&account_id(&p),
-				Reputation::VerifiedLinked,
-			);
-		}
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		assert_eq!(EncointerCommunities::nominal_income(cid), NominalIncomeType::from(13037u32));
+	});
+}
 
+#[test]
+fn enact_set_inactivity_timeout_works() {
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let proposal_action =
+			ProposalAction::SetInactivityTimeout(InactivityTimeoutType::from(13037u32));
 		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice),
-			proposal_action
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
 		));
 
-		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
 
-		// propsal is passing
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 100 });
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
 		assert_eq!(
-			EncointerDemocracy::proposals(1).unwrap().state,
-			ProposalState::Confirming { since: 0 }
+			EncointerCeremonies::inactivity_timeout(),
+			InactivityTimeoutType::from(13037u32)
 		);
+	});
+}
 
-		// not passing anymore
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 0 });
+#[test]
+fn enact_update_demurrage_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		let demurrage = Demurrage::from_num(0.0001);
+		let proposal_action = ProposalAction::UpdateDemurrage(cid, demurrage);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
 
-		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
 
-		// nothing changes if repeated
-		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		// passing
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 100 });
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		assert_eq!(EncointerBalances::demurrage_per_block(cid), demurrage);
+	});
+}
 
-		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), false);
+#[test]
+fn enact_set_meetup_validation_policy_works() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		let policy = MeetupValidationPolicy {
+			min_attestation_ratio: Some(Perbill::from_percent(50)),
+			vote_majority: VoteMajority::AbsoluteMajority,
+			early_rewards_allowed: false,
+		};
+		let proposal_action = ProposalAction::SetMeetupValidationPolicy(cid, policy);
+		assert_eq!(proposal_action.get_access_policy(), ProposalAccessPolicy::Community(cid));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
+
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerCeremonies::meetup_validation_policy(cid), policy);
+	});
+}
+
+#[test]
+fn enact_global_parameter_proposals_works() {
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let proposal_actions = vec![
+			ProposalAction::SetReputationLifetime(7),
+			ProposalAction::SetEndorsementTicketsPerBootstrapper(13),
+			ProposalAction::SetEndorsementTicketsPerReputable(3),
+			ProposalAction::SetMeetupTimeOffset(-3600 * 1000),
+			ProposalAction::SetTimeTolerance(1200000),
+			ProposalAction::SetLocationTolerance(500),
+			ProposalAction::SetFeeConversionFactor(12345),
+		];
+
+		for (i, proposal_action) in proposal_actions.iter().enumerate() {
+			assert_ok!(EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_action.clone()
+			));
+			EnactmentQueue::<TestRuntime>::insert(
+				proposal_action.get_identifier(),
+				(i + 1) as ProposalIdType,
+			);
+		}
+
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		for (i, proposal_action) in proposal_actions.iter().enumerate() {
+			assert_eq!(
+				EncointerDemocracy::proposals((i + 1) as ProposalIdType).unwrap().state,
+				ProposalState::Enacted
+			);
+			assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		}
+		assert_eq!(EncointerCeremonies::reputation_lifetime(), 7);
+		assert_eq!(EncointerCeremonies::endorsement_tickets_per_bootstrapper(), 13);
+		assert_eq!(EncointerCeremonies::endorsement_tickets_per_reputable(), 3);
+		assert_eq!(EncointerCeremonies::meetup_time_offset(), -3600 * 1000);
+		assert_eq!(EncointerCeremonies::time_tolerance(), 1200000);
+		assert_eq!(EncointerCeremonies::location_tolerance(), 500);
+		assert_eq!(EncointerBalances::fee_conversion_factor(), 12345);
+	});
+}
+
+#[test]
+fn proposal_action_access_policies_are_correct() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
 		assert_eq!(
-			EncointerDemocracy::proposals(1).unwrap().state,
-			ProposalState::Confirming { since: 0 }
+			ProposalActionOf::<TestRuntime>::UpdateDemurrage(cid, Demurrage::from_num(0.0001))
+				.get_access_policy(),
+			ProposalAccessPolicy::Community(cid)
 		);
-
-		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
-		advance_n_blocks(11);
-		// proposal is enacted
-		assert_eq!(EncointerDemocracy::do_update_proposal_state(1).unwrap(), true);
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Approved);
 		assert_eq!(
-			EncointerDemocracy::enactment_queue(proposal_action.get_identifier()).unwrap(),
-			1
+			ProposalActionOf::<TestRuntime>::SetReputationLifetime(7).get_access_policy(),
+			ProposalAccessPolicy::Global
+		);
+		assert_eq!(
+			ProposalActionOf::<TestRuntime>::SetLocationTolerance(500).get_identifier(),
+			ProposalActionIdentifier::SetLocationTolerance
 		);
 	});
 }
 
 #[test]
-fn update_proposal_state_extrinsic_works() {
+fn enact_call_proposal_dispatches_with_democracy_origin() {
 	new_test_ext().execute_with(|| {
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
-
 		let alice = alice();
-		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
-
+		let call = RuntimeCall::EncointerCeremonies(
+			encointer_ceremonies::Call::<TestRuntime>::set_location_tolerance {
+				location_tolerance: 123,
+			},
+		);
+		let bounded_call = <TestRuntime as Config>::Preimages::bound(call).unwrap();
+		let proposal_action = ProposalAction::Call(bounded_call);
 		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			proposal_action.clone()
 		));
 
-		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
-		// propsal is passing
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 3, ayes: 3 });
-		EncointerDemocracy::update_proposal_state(RuntimeOrigin::signed(alice), 1).unwrap();
-		assert_eq!(
-			EncointerDemocracy::proposals(1).unwrap().state,
-			ProposalState::Confirming { since: 0 }
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		assert_eq!(EncointerCeremonies::location_tolerance(), 123);
+	});
+}
+
+fn petition_text() -> BoundedIpfsCid {
+	BoundedIpfsCid::from_str("QmPK1s3pNYLi9ERiq3BDxKa4XosgWwFRQUydHUtz4YgpqB").unwrap()
+}
+
+#[test]
+fn submit_petition_fails_with_invalid_ipfs_cid() {
+	new_test_ext().execute_with(|| {
+		let text = BoundedIpfsCid::from_str("not a cid").unwrap();
+		assert_err!(
+			EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice()),
+				ProposalAction::Petition(None, text)
+			),
+			Error::<TestRuntime>::InvalidIpfsCid
 		);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			ProposalAction::Petition(None, petition_text())
+		));
 	});
 }
 
 #[test]
-fn test_get_electorate_works() {
+fn petition_is_approved_without_enactment() {
 	new_test_ext().execute_with(|| {
 		let cid = create_cid();
-		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
 		let alice = alice();
-		let bob = bob();
+		let proposal_action = ProposalAction::Petition(Some(cid), petition_text());
+		assert_eq!(proposal_action.get_access_policy(), ProposalAccessPolicy::Community(cid));
 
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
 		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
 		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid2, 3), &bob, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid2, 4), &bob, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid2, 5), &bob, Reputation::VerifiedLinked);
 
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
 		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			proposal_action.clone()
+		));
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			proposal_action.clone()
+		));
+
+		assert_ok!(EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5)]).unwrap()
 		));
 
-		let proposal_action =
-			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
+		advance_n_blocks(11);
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1), Ok(false));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Approved);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+		assert!(!EncointerDemocracy::active_proposals().contains(&1));
+
+		// the other petition is neither superseded nor blocked
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(2));
+		assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::Ongoing);
 		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice.clone()),
+			RuntimeOrigin::signed(alice),
 			proposal_action
 		));
 
-		assert_eq!(EncointerDemocracy::get_electorate(1).unwrap(), 5);
-		assert_eq!(EncointerDemocracy::get_electorate(2).unwrap(), 2);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Approved);
 	});
 }
 
 #[test]
-fn is_passing_works() {
+fn enact_spend_community_treasury_works() {
 	new_test_ext().execute_with(|| {
+		let cid = create_cid();
 		let alice = alice();
-		let cid = register_test_community::<TestRuntime>(None, 10.0, 10.0);
-
-		// electorate is 100
-		let pairs = add_population(100, 0);
-		for p in pairs {
-			EncointerCeremonies::fake_reputation(
-				(cid, 5),
-				&account_id(&p),
-				Reputation::VerifiedLinked,
-			);
-		}
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		// no demurrage to keep the numbers simple
+		encointer_balances::DemurragePerBlock::<TestRuntime>::insert(cid, Demurrage::from_num(0));
+		assert_ok!(EncointerBalances::issue(cid, &treasury, BalanceType::from_num(100)));
 
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
+		let proposal_action =
+			ProposalAction::SpendCommunityTreasury(cid, bob(), BalanceType::from_num(60));
+		assert_eq!(proposal_action.get_access_policy(), ProposalAccessPolicy::Community(cid));
 		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			proposal_action.clone()
 		));
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
 
-		// turnout below threshold
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 1, ayes: 1 });
-		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), false);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		// low turnout, 60 % approval
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 10, ayes: 6 });
-		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), false);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerBalances::balance(cid, &bob()), BalanceType::from_num(60));
+		assert_eq!(EncointerBalances::balance(cid, &treasury), BalanceType::from_num(40));
 
-		// low turnout 90 % approval
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 10, ayes: 9 });
-		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+		// the treasury cannot be overdrawn
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice),
+			proposal_action.clone()
+		));
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 2);
 
-		// high turnout, 60 % approval
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 60 });
-		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+		run_to_next_phase();
+		run_to_next_phase();
+		run_to_next_phase();
 
-		// high turnout 90 % approval
-		Tallies::<TestRuntime>::insert(1, Tally { turnout: 100, ayes: 90 });
-		assert_eq!(EncointerDemocracy::is_passing(1).unwrap(), true);
+		assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::EnactmentFailed);
+		assert_eq!(EncointerBalances::balance(cid, &bob()), BalanceType::from_num(60));
+		assert_eq!(EncointerBalances::balance(cid, &treasury), BalanceType::from_num(40));
 	});
 }
 
 #[test]
-fn enactment_updates_proposal_metadata_and_enactment_queue() {
+fn concurrent_community_treasury_spends_are_enacted() {
 	new_test_ext().execute_with(|| {
 		let cid = create_cid();
 		let alice = alice();
+		let treasury = EncointerBalances::get_community_treasury_account(cid);
+		encointer_balances::DemurragePerBlock::<TestRuntime>::insert(cid, Demurrage::from_num(0));
+		assert_ok!(EncointerBalances::issue(cid, &treasury, BalanceType::from_num(100)));
 
-		let proposal_action = ProposalAction::SetInactivityTimeout(8);
-		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
-		));
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
 
-		let proposal_action2 =
-			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32));
-		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice.clone()),
-			proposal_action2
-		));
+		let spends = [
+			ProposalAction::SpendCommunityTreasury(cid, bob(), BalanceType::from_num(30)),
+			ProposalAction::SpendCommunityTreasury(cid, charlie(), BalanceType::from_num(50)),
+		];
+		assert_ne!(spends[0].get_identifier(), spends[1].get_identifier());
+		for (proposal_id, proposal_action) in (1..).zip(spends.iter()) {
+			assert_ok!(EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_action.clone()
+			));
+			assert_ok!(EncointerDemocracy::vote(
+				RuntimeOrigin::signed(alice.clone()),
+				proposal_id,
+				Vote::Aye,
+				BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5)]).unwrap()
+			));
+		}
 
-		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
-		EnactmentQueue::<TestRuntime>::insert(proposal_action2.get_identifier(), 2);
+		advance_n_blocks(11);
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(1), Ok(true));
+		// approving one spend does not cancel the other
+		assert_eq!(EncointerDemocracy::do_update_proposal_state(2), Ok(true));
 
 		run_to_next_phase();
 		run_to_next_phase();
 		run_to_next_phase();
 
 		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
-
 		assert_eq!(EncointerDemocracy::proposals(2).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerBalances::balance(cid, &bob()), BalanceType::from_num(30));
+		assert_eq!(EncointerBalances::balance(cid, &charlie()), BalanceType::from_num(50));
+		assert_eq!(EncointerBalances::balance(cid, &treasury), BalanceType::from_num(20));
+	});
+}
 
-		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
+#[test]
+fn voting_refunds_weight_of_unused_reputations() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		let alice = alice();
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
+		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		let reputations: ReputationVecOf<TestRuntime> =
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4)]).unwrap();
 
-		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action2.get_identifier()), None);
+		let call = RuntimeCall::EncointerDemocracy(crate::Call::vote {
+			proposal_id: 1,
+			vote: Vote::Aye,
+			reputations: reputations.clone(),
+		});
+		assert_eq!(call.get_dispatch_info().weight, <() as WeightInfo>::vote(10, 0));
+
+		let post_info = EncointerDemocracy::vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Aye,
+			reputations.clone(),
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::vote(2, 0)));
+
+		let post_info = EncointerDemocracy::change_vote(
+			RuntimeOrigin::signed(alice.clone()),
+			1,
+			Vote::Nay,
+			reputations.clone(),
+			Default::default(),
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::change_vote(2, 0)));
+
+		let post_info = EncointerDemocracy::remove_vote(
+			RuntimeOrigin::signed(alice),
+			1,
+			reputations,
+			Default::default(),
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::remove_vote(2, 0)));
 	});
 }
 
 #[test]
-fn proposal_happy_flow() {
+fn voting_with_delegations_refunds_weight_of_unused_delegated_reputations() {
 	new_test_ext().execute_with(|| {
 		let cid = create_cid();
-		let cid2 = register_test_community::<TestRuntime>(None, 10.0, 10.0);
 		let alice = alice();
-		let proposal_action =
-			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(13037u32));
+		let bob = bob();
+		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
 		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			ProposalAction::SetInactivityTimeout(8)
+		));
+		assert_ok!(EncointerDemocracy::delegate(
+			RuntimeOrigin::signed(alice.clone()),
+			ProposalAccessPolicy::Global,
+			bob.clone(),
+			(cid, 3)
 		));
+		let delegated_reputations: DelegatedReputationVecOf<TestRuntime> =
+			BoundedVec::try_from(vec![(alice, (cid, 3))]).unwrap();
+
+		let call = RuntimeCall::EncointerDemocracy(crate::Call::vote_with_delegations {
+			proposal_id: 1,
+			vote: Vote::Aye,
+			reputations: Default::default(),
+			delegated_reputations: delegated_reputations.clone(),
+		});
+		assert_eq!(call.get_dispatch_info().weight, <() as WeightInfo>::vote(10, 10));
+
+		let post_info = EncointerDemocracy::vote_with_delegations(
+			RuntimeOrigin::signed(bob.clone()),
+			1,
+			Vote::Aye,
+			Default::default(),
+			delegated_reputations.clone(),
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::vote(0, 1)));
+
+		let post_info = EncointerDemocracy::remove_vote(
+			RuntimeOrigin::signed(bob),
+			1,
+			Default::default(),
+			delegated_reputations,
+		)
+		.unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::remove_vote(0, 1)));
+		assert_eq!(EncointerDemocracy::tallies(1).unwrap(), Tally { turnout: 0, ayes: 0 });
+	});
+}
+
+#[test]
+fn proposal_lifecycle_emits_events() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let cid = create_cid();
+		let alice = alice();
+		let proposal_action = ProposalAction::SetInactivityTimeout(8);
 
 		EncointerCeremonies::fake_reputation((cid, 3), &alice, Reputation::VerifiedLinked);
 		EncointerCeremonies::fake_reputation((cid, 4), &alice, Reputation::VerifiedLinked);
 		EncointerCeremonies::fake_reputation((cid, 5), &alice, Reputation::VerifiedLinked);
-		EncointerCeremonies::fake_reputation((cid2, 3), &alice, Reputation::VerifiedLinked);
 
-		assert_ok!(EncointerDemocracy::vote(
+		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			1,
-			Vote::Aye,
-			BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5),]).unwrap()
+			proposal_action.clone()
 		));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::ProposalSubmitted {
+					proposal_id: 1,
+					proposal_action,
+					proposer: alice.clone()
+				}
+				.into()
+			)
+		);
 
-		advance_n_blocks(40);
 		assert_ok!(EncointerDemocracy::vote(
 			RuntimeOrigin::signed(alice.clone()),
 			1,
 			Vote::Aye,
-			BoundedVec::try_from(vec![(cid2, 3)]).unwrap()
+			BoundedVec::try_from(vec![(cid, 3), (cid, 4), (cid, 5)]).unwrap()
 		));
+		assert!(event_deposited::<TestRuntime>(
+			Event::VotePlaced { proposal_id: 1, voter: alice, vote: Vote::Aye, num_reputations: 3 }
+				.into()
+		));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::ProposalStateChanged {
+					proposal_id: 1,
+					old_state: ProposalState::Ongoing,
+					new_state: ProposalState::Confirming { since: 1 }
+				}
+				.into()
+			)
+		);
 
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
+		advance_n_blocks(11);
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::ProposalStateChanged {
+					proposal_id: 1,
+					old_state: ProposalState::Confirming { since: 1 },
+					new_state: ProposalState::Approved
+				}
+				.into()
+			)
+		);
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
-		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
-		assert_eq!(EncointerCommunities::nominal_income(cid), NominalIncomeType::from(13037u32));
+		assert_ok!(EncointerDemocracy::enact_proposal(1));
+		assert!(event_deposited::<TestRuntime>(Event::ProposalEnacted { proposal_id: 1 }.into()));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::ProposalStateChanged {
+					proposal_id: 1,
+					old_state: ProposalState::Approved,
+					new_state: ProposalState::Enacted
+				}
+				.into()
+			)
+		);
 	});
 }
 
 #[test]
-fn enact_update_nominal_income_works() {
+fn proposal_cancellation_emits_events() {
 	new_test_ext().execute_with(|| {
-		let cid = create_cid();
-		let alice = alice();
-		let proposal_action =
-			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(13037u32));
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
 		assert_ok!(EncointerDemocracy::submit_proposal(
-			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			RuntimeOrigin::signed(alice()),
+			ProposalAction::SetInactivityTimeout(8)
 		));
 
+		advance_n_blocks(41);
+		assert_ok!(EncointerDemocracy::do_update_proposal_state(1));
+		assert!(event_deposited::<TestRuntime>(Event::ProposalCancelled { proposal_id: 1 }.into()));
+		assert_eq!(
+			last_event::<TestRuntime>(),
+			Some(
+				Event::ProposalStateChanged {
+					proposal_id: 1,
+					old_state: ProposalState::Ongoing,
+					new_state: ProposalState::Cancelled
+				}
+				.into()
+			)
+		);
+	});
+}
+
+/// Weight budget of enacting `n` proposals with built-in actions in a single block
+fn enactment_weight_budget(n: u64) -> Weight {
+	let db_weight: RuntimeDbWeight = <TestRuntime as frame_system::Config>::DbWeight::get();
+	db_weight.reads(1).saturating_add(
+		db_weight
+			.reads(3)
+			.saturating_add(<TestRuntime as Config>::WeightInfo::enact_proposal())
+			.saturating_mul(n),
+	)
+}
+
+#[test]
+fn queued_proposals_are_enacted_within_weight_budget() {
+	new_test_ext().execute_with(|| {
+		let cid = create_cid();
+		System::set_block_number(5);
+		for proposal_action in [
+			ProposalAction::SetInactivityTimeout(8),
+			ProposalAction::UpdateNominalIncome(cid, NominalIncomeType::from(100u32)),
+			ProposalAction::SetReputationLifetime(9),
+		] {
+			assert_ok!(EncointerDemocracy::submit_proposal(
+				RuntimeOrigin::signed(alice()),
+				proposal_action
+			));
+		}
+		EnactmentQueue::<TestRuntime>::insert(ProposalActionIdentifier::SetInactivityTimeout, 1);
+		EnactmentQueue::<TestRuntime>::insert(
+			ProposalActionIdentifier::UpdateNominalIncome(cid),
+			2,
+		);
+		EnactmentQueue::<TestRuntime>::insert(ProposalActionIdentifier::SetReputationLifetime, 3);
+		// approved after the start of the registering phase
+		CancelledAtBlock::<TestRuntime>::insert(ProposalActionIdentifier::SetReputationLifetime, 6);
+
+		System::set_block_number(6);
+		EncointerDemocracy::on_ceremony_phase_change(CeremonyPhaseType::Registering);
+		assert_eq!(EncointerDemocracy::enactment_cutoff(), Some(6));
+
+		let budget = enactment_weight_budget(1);
+		assert!(EncointerDemocracy::enact_queued_proposals(budget).all_lte(budget));
+		let enacted =
+			|id| EncointerDemocracy::proposals(id).unwrap().state == ProposalState::Enacted;
+		assert_eq!(enacted(1) as u32 + enacted(2) as u32, 1);
+		assert_eq!(EncointerDemocracy::enactment_cutoff(), Some(6));
+
+		EncointerDemocracy::enact_queued_proposals(budget);
+		assert!(enacted(1) && enacted(2));
+		assert!(!enacted(3));
+		assert_eq!(EncointerDemocracy::enactment_cutoff(), None);
+		assert_eq!(
+			EnactmentQueue::<TestRuntime>::iter().collect::<Vec<_>>(),
+			vec![(ProposalActionIdentifier::SetReputationLifetime, 3)]
+		);
+	});
+}
+
+/// Weight of looking up the call of `proposal_action`
+fn call_lookup_weight(proposal_action: &ProposalActionOf<TestRuntime>) -> Weight {
+	match proposal_action {
+		ProposalAction::Call(bounded_call) =>
+			<TestRuntime as Config>::WeightInfo::lookup_call(bounded_call.len().unwrap()),
+		_ => Weight::zero(),
+	}
+}
+
+#[test]
+fn call_enactment_is_postponed_if_call_lookup_exceeds_weight_budget() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1);
+		let call = RuntimeCall::EncointerCeremonies(
+			encointer_ceremonies::Call::<TestRuntime>::set_location_tolerance {
+				location_tolerance: 123,
+			},
+		);
+		let bounded_call = <TestRuntime as Config>::Preimages::bound(call).unwrap();
+		let proposal_action = ProposalAction::Call(bounded_call);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			proposal_action.clone()
+		));
 		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
+		EncointerDemocracy::on_ceremony_phase_change(CeremonyPhaseType::Registering);
 
-		run_to_next_phase();
-		run_to_next_phase();
-		run_to_next_phase();
+		let budget = enactment_weight_budget(1);
+		assert!(call_lookup_weight(&proposal_action).any_gt(Weight::zero()));
+		assert!(EncointerDemocracy::enact_queued_proposals(budget).all_lte(budget));
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Ongoing);
+		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), Some(1));
+		assert!(EncointerDemocracy::enactment_cutoff().is_some());
+	});
+}
+
+#[test]
+fn call_enactment_exceeding_weight_budget_is_rejected() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+		let call = RuntimeCall::EncointerCeremonies(
+			encointer_ceremonies::Call::<TestRuntime>::set_location_tolerance {
+				location_tolerance: 123,
+			},
+		);
+		assert!(call.get_dispatch_info().weight.any_gt(Weight::zero()));
+		let bounded_call = <TestRuntime as Config>::Preimages::bound(call).unwrap();
+		let proposal_action = ProposalAction::Call(bounded_call);
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			proposal_action.clone()
+		));
+		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
+		EncointerDemocracy::on_ceremony_phase_change(CeremonyPhaseType::Registering);
+
+		// the budget only suffices for built-in actions and looking up the call
+		EnactmentWeightBudget::set(
+			enactment_weight_budget(1).saturating_add(call_lookup_weight(&proposal_action)),
+		);
+		EncointerDemocracy::on_initialize(System::block_number());
+
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::EnactmentFailed);
+		assert!(event_deposited::<TestRuntime>(
+			Event::EnactmentFailed {
+				proposal_id: 1,
+				reason: Error::<TestRuntime>::EnactmentWeightExceeded.into()
+			}
+			.into()
+		));
+		assert_eq!(EncointerCeremonies::location_tolerance(), LOCATION_TOLERANCE);
 		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
-		assert_eq!(EncointerCommunities::nominal_income(cid), NominalIncomeType::from(13037u32));
+		assert_eq!(EncointerDemocracy::enactment_cutoff(), None);
 	});
 }
 
 #[test]
-fn enact_set_inactivity_timeout_works() {
+fn failing_enactment_is_reported() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
 		let alice = alice();
-		let proposal_action =
-			ProposalAction::SetInactivityTimeout(InactivityTimeoutType::from(13037u32));
+		ProposalDepositAmount::set(10);
+		Balances::make_free_balance_be(&alice, 100);
+
+		// meetup time offset must be within [-8h, 8h]
+		let proposal_action = ProposalAction::SetMeetupTimeOffset(9 * 3600 * 1000);
 		assert_ok!(EncointerDemocracy::submit_proposal(
 			RuntimeOrigin::signed(alice.clone()),
-			proposal_action
+			proposal_action.clone()
 		));
-
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+		ActiveProposals::<TestRuntime>::kill();
 		EnactmentQueue::<TestRuntime>::insert(proposal_action.get_identifier(), 1);
 
 		run_to_next_phase();
 		run_to_next_phase();
 		run_to_next_phase();
 
-		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::Enacted);
-		assert_eq!(EncointerDemocracy::enactment_queue(proposal_action.get_identifier()), None);
-		assert_eq!(
-			EncointerCeremonies::inactivity_timeout(),
-			InactivityTimeoutType::from(13037u32)
-		);
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::EnactmentFailed);
+		assert_eq!(EncointerCeremonies::meetup_time_offset(), 0);
+		assert!(event_deposited::<TestRuntime>(
+			Event::EnactmentFailed {
+				proposal_id: 1,
+				reason: encointer_ceremonies::Error::<TestRuntime>::InvalidMeetupTimeOffset.into()
+			}
+			.into()
+		));
+		assert!(!event_deposited::<TestRuntime>(Event::ProposalEnacted { proposal_id: 1 }.into()));
+		// the deposit is refunded nevertheless
+		assert_eq!(Balances::free_balance(&alice), 100);
+	});
+}
+
+#[test]
+fn failing_call_enactment_is_reported() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(System::block_number() + 1); // this is needed to assert events
+													  // requires root origin
+		let call = RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 10 });
+		let bounded_call = <TestRuntime as Config>::Preimages::bound(call).unwrap();
+		assert_ok!(EncointerDemocracy::submit_proposal(
+			RuntimeOrigin::signed(alice()),
+			ProposalAction::Call(bounded_call)
+		));
+		let mut proposal = EncointerDemocracy::proposals(1).unwrap();
+		proposal.state = ProposalState::Approved;
+		Proposals::<TestRuntime>::insert(1, proposal);
+
+		assert_ok!(EncointerDemocracy::enact_proposal(1));
+		assert_eq!(EncointerDemocracy::proposals(1).unwrap().state, ProposalState::EnactmentFailed);
+		assert!(event_deposited::<TestRuntime>(
+			Event::EnactmentFailed { proposal_id: 1, reason: DispatchError::BadOrigin }.into()
+		));
+	});
+}
+
+#[test]
+fn ensure_encointer_democracy_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(EnsureEncointerDemocracy::try_origin(RuntimeOrigin::from(
+			RawOrigin::EncointerDemocracy
+		)));
+		assert!(EnsureEncointerDemocracy::try_origin(RuntimeOrigin::signed(alice())).is_err());
+		assert!(EnsureEncointerDemocracy::try_origin(RuntimeOrigin::root()).is_err());
 	});
 }
@@ -16,15 +16,18 @@ limitations under the License.
 */
 
 //! Autogenerated weights for pallet_encointer_democracy with reference hardware:
-//! * 2.3 GHz 8-Core Intel Core i9
-//! * 16 GB 2400 MHz DDR4
-//! * SSD
+//! * Intel(R) Xeon(R) Processor, 1 core
+//! * 5 GB RAM
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-09-13, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: , WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//! DATE: 2026-10-18, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Native, ANALYSIS: min squares (IQR), RUNTIME: `mock::TestRuntime`
+//!
+//! Generated by running the benchmarks of `benchmarking.rs` natively against the pallet's mock
+//! runtime on the in-memory benchmarking state of `sc-client-db`, with a `ProposalDeposit` of 100
+//! and a `CommunityProposalDeposit` of 10. Native execution is faster than the Wasm execution of
+//! a node, so a runtime should regenerate its weights with the command below.
 
-// Executed Command:
+// Regenerate with:
 // target/release/encointer-node-notee
 // benchmark
 // pallet
@@ -36,7 +39,6 @@ limitations under the License.
 // --wasm-execution=compiled
 // --heap-pages=4096
 // --output=runtime/src/weights/pallet_encointer_democracy.rs
-// --template=/Users/pigu/Documents/code/encointer/encointer-node/scripts/frame-weight-template-full-info.hbs
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -50,45 +52,158 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for pallet_encointer_democracy.
 pub trait WeightInfo {
 	fn submit_proposal() -> Weight;
-	fn vote() -> Weight;
+	fn vote(r: u32, d: u32) -> Weight;
 	fn update_proposal_state() -> Weight;
+	fn cancel_proposal() -> Weight;
+	fn change_vote(r: u32, d: u32) -> Weight;
+	fn remove_vote(r: u32, d: u32) -> Weight;
+	fn delegate() -> Weight;
+	fn undelegate() -> Weight;
+	fn lookup_call(l: u32) -> Weight;
+	fn enact_proposal() -> Weight;
 }
 
-/// Weights for pallet_encointer_democracy using the Encointer solo chain node and recommended hardware.
+/// Weights for pallet_encointer_democracy measured on the mock runtime, see module docs.
 pub struct EncointerWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for EncointerWeight<T> {
 	fn submit_proposal() -> Weight {
-		Weight::from_parts(40_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(3))
-			.saturating_add(T::DbWeight::get().writes(3))
+		Weight::from_parts(59_712_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(7))
 	}
-	fn vote() -> Weight {
-		Weight::from_parts(168_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(16))
-			.saturating_add(T::DbWeight::get().writes(5))
+	fn vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(181_693_000, 0)
+			.saturating_add(Weight::from_parts(8_824_786, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(7_523_699, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(9))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(d.into())))
 	}
 	fn update_proposal_state() -> Weight {
-		Weight::from_parts(116_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(9))
+		Weight::from_parts(172_055_000, 0)
+			.saturating_add(T::DbWeight::get().reads(14))
+			.saturating_add(T::DbWeight::get().writes(7))
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_parts(84_315_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(5))
+	}
+	fn change_vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(45_634_467, 0)
+			.saturating_add(Weight::from_parts(7_764_937, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(10_204_070, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	fn remove_vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(113_152_000, 0)
+			.saturating_add(Weight::from_parts(5_242_178, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(8_342_691, 0).saturating_mul(d.into()))
+			.saturating_add(T::DbWeight::get().reads(8))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(T::DbWeight::get().writes(2))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(d.into())))
+	}
+	fn delegate() -> Weight {
+		Weight::from_parts(59_187_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	fn undelegate() -> Weight {
+		Weight::from_parts(38_988_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2))
+			.saturating_add(T::DbWeight::get().writes(2))
+	}
+	fn lookup_call(l: u32) -> Weight {
+		Weight::from_parts(182_000, 0)
+			.saturating_add(Weight::from_parts(2_680, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(1))
+	}
+	fn enact_proposal() -> Weight {
+		Weight::from_parts(106_792_000, 0)
+			.saturating_add(T::DbWeight::get().reads(7))
+			.saturating_add(T::DbWeight::get().writes(6))
+	}
 }
 
 // For tests
 impl WeightInfo for () {
 	fn submit_proposal() -> Weight {
-		Weight::from_parts(40_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(3))
-			.saturating_add(RocksDbWeight::get().writes(3))
+		Weight::from_parts(59_712_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(7))
 	}
-	fn vote() -> Weight {
-		Weight::from_parts(168_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(16))
-			.saturating_add(RocksDbWeight::get().writes(5))
+	fn vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(181_693_000, 0)
+			.saturating_add(Weight::from_parts(8_824_786, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(7_523_699, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(9))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(d.into())))
 	}
 	fn update_proposal_state() -> Weight {
-		Weight::from_parts(116_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(9))
+		Weight::from_parts(172_055_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(14))
+			.saturating_add(RocksDbWeight::get().writes(7))
+	}
+	fn cancel_proposal() -> Weight {
+		Weight::from_parts(84_315_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(5))
+	}
+	fn change_vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(45_634_467, 0)
+			.saturating_add(Weight::from_parts(7_764_937, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(10_204_070, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(d.into())))
+	}
+	fn remove_vote(r: u32, d: u32) -> Weight {
+		Weight::from_parts(113_152_000, 0)
+			.saturating_add(Weight::from_parts(5_242_178, 0).saturating_mul(r.into()))
+			.saturating_add(Weight::from_parts(8_342_691, 0).saturating_mul(d.into()))
+			.saturating_add(RocksDbWeight::get().reads(8))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(d.into())))
+			.saturating_add(RocksDbWeight::get().writes(2))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(d.into())))
+	}
+	fn delegate() -> Weight {
+		Weight::from_parts(59_187_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7))
 			.saturating_add(RocksDbWeight::get().writes(3))
 	}
+	fn undelegate() -> Weight {
+		Weight::from_parts(38_988_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2))
+			.saturating_add(RocksDbWeight::get().writes(2))
+	}
+	fn lookup_call(l: u32) -> Weight {
+		Weight::from_parts(182_000, 0)
+			.saturating_add(Weight::from_parts(2_680, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(1))
+	}
+	fn enact_proposal() -> Weight {
+		Weight::from_parts(106_792_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(7))
+			.saturating_add(RocksDbWeight::get().writes(6))
+	}
 }
@@ -14,15 +14,23 @@
 // You should have received a copy of the GNU General Public License
 // along with Encointer.  If not, see <http://www.gnu.org/licenses/>.
 
+#[cfg(feature = "serde_derive")]
+use ep_core::serde::serialize_fixed;
 #[cfg(feature = "serde_derive")]
 use serde::{Deserialize, Serialize};
 
-use crate::communities::{CommunityIdentifier, Location};
+use crate::{
+	balances::BalanceType,
+	communities::{CommunityIdentifier, GeoHash, Location},
+};
 
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::{RuntimeDebug, H256};
-use sp_runtime::traits::{BlakeTwo256, Hash, IdentifyAccount, Verify};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash, IdentifyAccount, Verify},
+	Perbill,
+};
 
 pub use crate::scheduler::CeremonyIndexType;
 
@@ -38,8 +46,11 @@ pub type MeetupTimeOffsetType = i32;
 pub type MeetupData<AccountId, Moment> =
 	(CeremonyIndexType, MeetupIndexType, Vec<AccountId>, Location, Moment);
 pub type ReputationCountType = u128;
+pub type ReputationScoreType = u32;
+pub type PenaltyCountType = u32;
 
 use crate::scheduler::CeremonyPhaseType;
+use sp_std::cmp::max;
 #[cfg(not(feature = "std"))]
 use sp_std::vec::Vec;
 
@@ -78,6 +89,40 @@ pub enum ParticipantType {
 	Newbie,
 }
 
+/// Misbehaviour at a meetup which is penalized
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+pub enum PenaltyReason {
+	/// voted for a number of participants contradicting the majority
+	WrongVote,
+	/// attested a participant who was not confirmed to be present by the others
+	AttestedAbsentee,
+}
+
+/// Repeated misbehaviour of an account within a community
+#[derive(
+	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct PenaltyRecord {
+	/// number of penalties since the record last lapsed
+	pub count: PenaltyCountType,
+	/// ceremony of the most recent penalty
+	pub last_penalized: CeremonyIndexType,
+}
+
+impl PenaltyRecord {
+	/// A record lapses once its latest penalty is older than the reputation lifetime
+	pub fn has_lapsed(
+		&self,
+		cindex: CeremonyIndexType,
+		reputation_lifetime: ReputationLifetimeType,
+	) -> bool {
+		self.last_penalized.saturating_add(reputation_lifetime) < cindex
+	}
+}
+
 #[derive(
 	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
 )]
@@ -179,6 +224,33 @@ impl<Signature, AccountId: Clone + Encode, Moment: Encode + Copy>
 			Some(Signature::from(pair.sign(&claim_mut.payload_encoded()[..])));
 		claim_mut
 	}
+
+	/// The payload of a claim which is submitted together with the claims of `claimants`. It
+	/// commits to the sorted set of claimants, so the claim can't be bundled with other claims.
+	pub fn bundle_payload_encoded(&self, claimants: &[AccountId]) -> Vec<u8>
+	where
+		AccountId: Ord,
+	{
+		let mut claimants = claimants.to_vec();
+		claimants.sort();
+		claimants.dedup();
+		let mut payload = self.payload_encoded();
+		claimants.encode_to(&mut payload);
+		payload
+	}
+
+	#[cfg(any(feature = "std", feature = "full_crypto"))]
+	pub fn sign_for_bundle<P>(self, pair: &P, claimants: &[AccountId]) -> Self
+	where
+		P: sp_core::Pair,
+		Signature: From<P::Signature>,
+		AccountId: Ord,
+	{
+		let mut claim_mut = self;
+		claim_mut.claimant_signature =
+			Some(Signature::from(pair.sign(&claim_mut.bundle_payload_encoded(claimants)[..])));
+		claim_mut
+	}
 }
 
 impl<Signature, AccountId, Moment> ClaimOfAttendance<Signature, AccountId, Moment> {
@@ -194,6 +266,21 @@ impl<Signature, AccountId, Moment> ClaimOfAttendance<Signature, AccountId, Momen
 			.map(|sig| sig.verify(&self.payload_encoded()[..], &self.claimant_public))
 			.unwrap_or(false)
 	}
+
+	pub fn verify_bundle_signature(&self, claimants: &[AccountId]) -> bool
+	where
+		Signature: Verify,
+		<Signature as Verify>::Signer: IdentifyAccount<AccountId = AccountId>,
+		AccountId: Clone + Encode + Ord,
+		Moment: Copy + Encode,
+	{
+		self.claimant_signature
+			.as_ref()
+			.map(|sig| {
+				sig.verify(&self.bundle_payload_encoded(claimants)[..], &self.claimant_public)
+			})
+			.unwrap_or(false)
+	}
 }
 
 /// Reputation that is linked to a specific community
@@ -213,6 +300,46 @@ impl CommunityReputation {
 	}
 }
 
+/// Reputation of an account within a community, weighted by recency
+#[derive(
+	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct ReputationScore {
+	/// recency weighted sum of verified reputations plus the current streak
+	pub score: ReputationScoreType,
+	/// number of consecutive ceremonies attended up to the most recent one
+	pub streak: ReputationScoreType,
+	/// number of verified reputations within the reputation lifetime
+	pub verified_count: ReputationScoreType,
+}
+
+impl ReputationScore {
+	/// Derive the score from the verification status of past ceremonies, most recent first.
+	///
+	/// A verified reputation weighs as many points as ceremonies remain in the evaluated window,
+	/// so the most recent ceremony counts most and the oldest one counts one point. Every
+	/// ceremony of the current streak adds one bonus point. The current ceremony doesn't break
+	/// the streak if it has not been verified (yet).
+	pub fn from_history(history: &[bool]) -> Self {
+		let window = history.len() as ReputationScoreType;
+		let mut score: ReputationScoreType = 0;
+		let mut verified_count: ReputationScoreType = 0;
+		for (age, _) in history.iter().enumerate().filter(|(_, verified)| **verified) {
+			score = score.saturating_add(window.saturating_sub(age as ReputationScoreType));
+			verified_count = verified_count.saturating_add(1);
+		}
+		let skip = match history.first() {
+			Some(false) => 1,
+			_ => 0,
+		};
+		let streak = history.iter().skip(skip).take_while(|verified| **verified).count()
+			as ReputationScoreType;
+		Self { score: score.saturating_add(streak), streak, verified_count }
+	}
+}
+
 #[derive(
 	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
 )]
@@ -240,6 +367,42 @@ impl<Signature, AccountId: Clone + Encode> ProofOfAttendance<Signature, AccountI
 	}
 }
 
+/// Expected outcome of the meetup assignment given the current registrations.
+#[derive(
+	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct AssignmentForecast {
+	pub registered: AssignmentCount,
+	pub assigned: AssignmentCount,
+	/// additional locations needed to assign all participants the newbie limit permits
+	pub missing_locations: u64,
+	/// participants of the previous ceremony's waitlist among `registered`, who are assigned
+	/// before the others
+	pub waitlisted: AssignmentCount,
+}
+
+/// Consent of a newbie to be endorsed and registered by `endorser` for a ceremony.
+#[derive(
+	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct RegistrationConsent<Signature, AccountId> {
+	pub newbie: AccountId,
+	pub endorser: AccountId,
+	pub ceremony_index: CeremonyIndexType,
+	pub community_identifier: CommunityIdentifier,
+	pub newbie_signature: Signature,
+}
+
+impl<Signature, AccountId: Clone + Encode> RegistrationConsent<Signature, AccountId> {
+	pub fn payload_encoded(&self) -> Vec<u8> {
+		(self.endorser.clone(), self.ceremony_index, self.community_identifier).encode()
+	}
+}
+
 #[derive(
 	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
 )]
@@ -270,6 +433,21 @@ pub struct Assignment {
 	pub locations: AssignmentParams,
 }
 
+/// Meetup assignment of the participants who prefer to meet in the same region
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct RegionalAssignment {
+	/// geohash bucket of the community locations where the region's meetups take place
+	pub region: GeoHash,
+	/// registry index of the region's first participant of each participant type, 0-based
+	pub offsets: AssignmentCount,
+	/// number of the region's participants who are assigned to a meetup
+	pub assigned: AssignmentCount,
+	/// number of meetups of the preceding regions
+	pub meetup_offset: MeetupIndexType,
+	pub meetup_count: MeetupIndexType,
+	pub params: Assignment,
+}
+
 // Todo: abstract AssignmentParams trait and use two different structs: AssignmentParams, LocationAssignmentParams
 #[derive(
 	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
@@ -355,6 +533,116 @@ pub enum MeetupResult {
 	MeetupValidationIndexOutOfBounds,
 }
 
+/// Number of evaluated meetups per `MeetupResult`
+#[derive(
+	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct MeetupResultCount {
+	pub ok: u32,
+	pub votes_not_dependable: u32,
+	pub meetup_validation_index_out_of_bounds: u32,
+}
+
+impl MeetupResultCount {
+	pub fn record(&mut self, result: MeetupResult) {
+		let count = match result {
+			MeetupResult::Ok => &mut self.ok,
+			MeetupResult::VotesNotDependable => &mut self.votes_not_dependable,
+			MeetupResult::MeetupValidationIndexOutOfBounds =>
+				&mut self.meetup_validation_index_out_of_bounds,
+		};
+		*count = count.saturating_add(1);
+	}
+}
+
+/// Number of participants excluded from rewards per exclusion reason
+#[derive(
+	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct ExclusionCount {
+	pub no_vote: u32,
+	pub wrong_vote: u32,
+	pub too_few_incoming_attestations: u32,
+	pub too_few_outgoing_attestations: u32,
+}
+
+/// Aggregated statistics of the ceremony of a community
+#[derive(
+	Encode, Decode, Default, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct CeremonyStats {
+	/// registered participants per participant type
+	pub registered: AssignmentCount,
+	/// participants per participant type who have been assigned to a meetup
+	pub assigned: AssignmentCount,
+	pub meetup_results: MeetupResultCount,
+	pub exclusions: ExclusionCount,
+	/// number of participants who have been rewarded
+	pub rewarded: u32,
+	/// total reward issued to participants, excluding the community treasury's share
+	#[cfg_attr(feature = "serde_derive", serde(with = "serialize_fixed"))]
+	pub issued_rewards: BalanceType,
+}
+
+/// How the number of participants of a meetup is determined from the votes of its participants
+#[derive(
+	Encode, Decode, Copy, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub enum VoteMajority {
+	/// the most frequent vote wins
+	#[default]
+	Plurality,
+	/// the winning vote needs the support of more than half of the voters
+	AbsoluteMajority,
+	/// all voters need to agree
+	Unanimity,
+}
+
+/// Community specific rules for the validation of meetups
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct MeetupValidationPolicy {
+	/// Share of their fellow meetup participants a participant needs to attest and be attested by.
+	/// `None` requires all but one fellow participant (all but two for meetups of more than five
+	/// participants).
+	pub min_attestation_ratio: Option<Perbill>,
+	/// rule to determine the number of participants from their votes
+	pub vote_majority: VoteMajority,
+	/// may rewards be claimed during the attesting phase if the meetup is unambiguous
+	pub early_rewards_allowed: bool,
+}
+
+impl Default for MeetupValidationPolicy {
+	fn default() -> Self {
+		Self {
+			min_attestation_ratio: None,
+			vote_majority: VoteMajority::Plurality,
+			early_rewards_allowed: true,
+		}
+	}
+}
+
+impl MeetupValidationPolicy {
+	/// Number of attestations a participant needs in a meetup with `n` remaining participants
+	pub fn attestation_threshold(&self, n: usize) -> usize {
+		let threshold = match self.min_attestation_ratio {
+			Some(ratio) => ratio.mul_ceil(n.saturating_sub(1) as u32) as usize,
+			None if n > 5 => n.saturating_sub(2),
+			None => n.saturating_sub(1),
+		};
+		max(threshold, 1)
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -377,4 +665,53 @@ mod tests {
 
 		assert!(claim.verify_signature())
 	}
+
+	#[test]
+	fn bundle_claim_verification_works() {
+		let alice = AccountKeyring::Alice.pair();
+		let claimants: Vec<AccountId> =
+			vec![AccountKeyring::Bob.into(), AccountKeyring::Alice.into()];
+		let claim = ClaimOfAttendance::<Signature, AccountId, Moment>::new_unsigned(
+			alice.public().into(),
+			1,
+			Default::default(),
+			1,
+			Default::default(),
+			Default::default(),
+			2,
+		)
+		.sign_for_bundle(&alice, &claimants);
+
+		assert!(claim.verify_bundle_signature(&[claimants[1].clone(), claimants[0].clone()]));
+		assert!(!claim.verify_bundle_signature(&claimants[..1]));
+		assert!(!claim.verify_signature());
+	}
+
+	#[test]
+	fn reputation_score_from_history_works() {
+		assert_eq!(ReputationScore::from_history(&[]), ReputationScore::default());
+		assert_eq!(
+			ReputationScore::from_history(&[false, false, false]),
+			ReputationScore::default()
+		);
+		// 3 + 2 + 1 weighted plus a streak of 3
+		assert_eq!(
+			ReputationScore::from_history(&[true, true, true]),
+			ReputationScore { score: 9, streak: 3, verified_count: 3 }
+		);
+		// unverified current ceremony doesn't break the streak
+		assert_eq!(
+			ReputationScore::from_history(&[false, true, true]),
+			ReputationScore { score: 5, streak: 2, verified_count: 2 }
+		);
+		// recent attendance weighs more than old attendance
+		assert_eq!(
+			ReputationScore::from_history(&[false, true, false, false]),
+			ReputationScore { score: 4, streak: 1, verified_count: 1 }
+		);
+		assert_eq!(
+			ReputationScore::from_history(&[false, false, false, true]),
+			ReputationScore { score: 1, streak: 0, verified_count: 1 }
+		);
+	}
 }
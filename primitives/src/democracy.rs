@@ -1,20 +1,32 @@
 use crate::{
-	ceremonies::{CommunityCeremony, InactivityTimeoutType},
+	balances::{BalanceType, Demurrage, FeeConversionFactorType},
+	ceremonies::{
+		CommunityCeremony, EndorsementTicketsType, InactivityTimeoutType, MeetupTimeOffsetType,
+		MeetupValidationPolicy, ReputationCountType, ReputationLifetimeType,
+	},
+	common::BoundedIpfsCid,
 	communities::{CommunityIdentifier, NominalIncome as NominalIncomeType},
+	fixed::{transcendental::sqrt, types::U64F64},
 };
 use codec::{Decode, Encode, MaxEncodedLen};
+#[cfg(feature = "serde_derive")]
+use ep_core::serde::serialize_fixed;
 use scale_info::TypeInfo;
 
 use crate::scheduler::CeremonyIndexType;
 #[cfg(feature = "serde_derive")]
 use serde::{Deserialize, Serialize};
-use sp_core::RuntimeDebug;
-use sp_runtime::BoundedVec;
+use sp_core::{RuntimeDebug, H256};
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	BoundedVec, Perbill,
+};
 
 pub type ProposalIdType = u128;
 pub type VoteCountType = u128;
 pub type VoteEntry<AccountId> = (AccountId, CommunityCeremony);
 pub type ReputationVec<MaxLength> = BoundedVec<CommunityCeremony, MaxLength>;
+pub type DelegatedReputationVec<AccountId, MaxLength> = BoundedVec<VoteEntry<AccountId>, MaxLength>;
 
 #[derive(
 	Encode, Decode, Default, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen,
@@ -26,6 +38,79 @@ pub struct Tally {
 	pub ayes: VoteCountType,
 }
 
+/// Decides whether a proposal passes, given the size of its electorate and its tally
+pub trait TallyStrategy {
+	/// Returns `None` if the evaluation overflows
+	fn is_passing(&self, electorate: VoteCountType, tally: &Tally) -> Option<bool>;
+}
+
+#[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub enum VoteThreshold {
+	/// more ayes than nays
+	SimpleMajority,
+	/// the ayes make up more than the given share of the turnout
+	Supermajority(Perbill),
+	/// adaptive quorum biasing: a low turnout requires a supermajority of ayes to pass
+	PositiveTurnoutBias,
+	/// adaptive quorum biasing: a low turnout requires a supermajority of nays to fail
+	NegativeTurnoutBias,
+}
+
+impl TallyStrategy for VoteThreshold {
+	fn is_passing(&self, electorate: VoteCountType, tally: &Tally) -> Option<bool> {
+		let ayes = tally.ayes;
+		let nays = tally.turnout.checked_sub(ayes)?;
+		match self {
+			Self::SimpleMajority => Some(ayes > nays),
+			Self::Supermajority(threshold) =>
+				Some(ayes > threshold.mul_floor(tally.turnout) && ayes > nays),
+			Self::PositiveTurnoutBias => positive_turnout_bias(electorate, tally.turnout, ayes),
+			Self::NegativeTurnoutBias => negative_turnout_bias(electorate, tally.turnout, ayes),
+		}
+	}
+}
+
+fn positive_turnout_bias(e: u128, t: u128, a: u128) -> Option<bool> {
+	// electorate e
+	// turnout t
+	// approval a
+
+	// let nays n = t - a
+	// approved if n / sqrt(t) < a / sqrt(e)
+	// <==>
+	// a > sqrt(e) * sqrt(t) / (sqrt(e) / sqrt(t) + 1)
+
+	let sqrt_e = sqrt::<U64F64, U64F64>(U64F64::checked_from_num(e)?).ok()?;
+	let sqrt_t = sqrt::<U64F64, U64F64>(U64F64::checked_from_num(t)?).ok()?;
+	let one = U64F64::from_num(1);
+
+	Some(
+		U64F64::checked_from_num(a)? >
+			sqrt_e
+				.checked_mul(sqrt_t)?
+				.checked_div(sqrt_e.checked_div(sqrt_t)?.checked_add(one)?)?,
+	)
+}
+
+fn negative_turnout_bias(e: u128, t: u128, a: u128) -> Option<bool> {
+	// electorate e
+	// turnout t
+	// approval a
+
+	// let nays n = t - a
+	// approved if n / sqrt(e) < a / sqrt(t)
+	// <==>
+	// n * sqrt(t) < a * sqrt(e)
+
+	let sqrt_e = sqrt::<U64F64, U64F64>(U64F64::checked_from_num(e)?).ok()?;
+	let sqrt_t = sqrt::<U64F64, U64F64>(U64F64::checked_from_num(t)?).ok()?;
+	let n = U64F64::checked_from_num(t.checked_sub(a)?)?;
+
+	Some(n.checked_mul(sqrt_t)? < U64F64::checked_from_num(a)?.checked_mul(sqrt_e)?)
+}
+
 #[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
@@ -42,12 +127,31 @@ pub enum ProposalAccessPolicy {
 	Community(CommunityIdentifier),
 }
 
-#[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
-pub enum ProposalAction {
+pub enum ProposalAction<AccountId, Moment, BoundedCall> {
 	UpdateNominalIncome(CommunityIdentifier, NominalIncomeType),
 	SetInactivityTimeout(InactivityTimeoutType),
+	UpdateDemurrage(CommunityIdentifier, Demurrage),
+	SetReputationLifetime(ReputationLifetimeType),
+	SetEndorsementTicketsPerBootstrapper(EndorsementTicketsType),
+	SetEndorsementTicketsPerReputable(EndorsementTicketsType),
+	SetMeetupTimeOffset(MeetupTimeOffsetType),
+	SetTimeTolerance(Moment),
+	SetLocationTolerance(u32),
+	SetFeeConversionFactor(FeeConversionFactorType),
+	/// dispatch an arbitrary (preimage-bounded) call with the `EncointerDemocracy` origin
+	Call(BoundedCall),
+	/// non-binding petition, globally or within a community, whose text is stored on IPFS
+	Petition(Option<CommunityIdentifier>, BoundedIpfsCid),
+	/// transfer community currency from the community treasury to a beneficiary
+	SpendCommunityTreasury(
+		CommunityIdentifier,
+		AccountId,
+		#[cfg_attr(feature = "serde_derive", serde(with = "serialize_fixed"))] BalanceType,
+	),
+	SetMeetupValidationPolicy(CommunityIdentifier, MeetupValidationPolicy),
 }
 
 #[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
@@ -56,35 +160,199 @@ pub enum ProposalAction {
 pub enum ProposalActionIdentifier {
 	UpdateNominalIncome(CommunityIdentifier),
 	SetInactivityTimeout,
+	UpdateDemurrage(CommunityIdentifier),
+	SetReputationLifetime,
+	SetEndorsementTicketsPerBootstrapper,
+	SetEndorsementTicketsPerReputable,
+	SetMeetupTimeOffset,
+	SetTimeTolerance,
+	SetLocationTolerance,
+	SetFeeConversionFactor,
+	/// identified by the hash of the bounded call
+	Call(H256),
+	Petition(Option<CommunityIdentifier>),
+	/// identified by the community and the hash of beneficiary and amount, such that different
+	/// spends do not supersede each other
+	SpendCommunityTreasury(CommunityIdentifier, H256),
+	SetMeetupValidationPolicy(CommunityIdentifier),
+}
+
+impl<AccountId, Moment, BoundedCall> ProposalAction<AccountId, Moment, BoundedCall> {
+	/// Maps the proposed call, e.g. to its hash for display purposes
+	pub fn map_call<Call>(
+		self,
+		f: impl FnOnce(BoundedCall) -> Call,
+	) -> ProposalAction<AccountId, Moment, Call> {
+		match self {
+			ProposalAction::UpdateNominalIncome(cid, nominal_income) =>
+				ProposalAction::UpdateNominalIncome(cid, nominal_income),
+			ProposalAction::SetInactivityTimeout(inactivity_timeout) =>
+				ProposalAction::SetInactivityTimeout(inactivity_timeout),
+			ProposalAction::UpdateDemurrage(cid, demurrage) =>
+				ProposalAction::UpdateDemurrage(cid, demurrage),
+			ProposalAction::SetReputationLifetime(reputation_lifetime) =>
+				ProposalAction::SetReputationLifetime(reputation_lifetime),
+			ProposalAction::SetEndorsementTicketsPerBootstrapper(endorsement_tickets) =>
+				ProposalAction::SetEndorsementTicketsPerBootstrapper(endorsement_tickets),
+			ProposalAction::SetEndorsementTicketsPerReputable(endorsement_tickets) =>
+				ProposalAction::SetEndorsementTicketsPerReputable(endorsement_tickets),
+			ProposalAction::SetMeetupTimeOffset(meetup_time_offset) =>
+				ProposalAction::SetMeetupTimeOffset(meetup_time_offset),
+			ProposalAction::SetTimeTolerance(time_tolerance) =>
+				ProposalAction::SetTimeTolerance(time_tolerance),
+			ProposalAction::SetLocationTolerance(location_tolerance) =>
+				ProposalAction::SetLocationTolerance(location_tolerance),
+			ProposalAction::SetFeeConversionFactor(fee_conversion_factor) =>
+				ProposalAction::SetFeeConversionFactor(fee_conversion_factor),
+			ProposalAction::Call(call) => ProposalAction::Call(f(call)),
+			ProposalAction::Petition(maybe_cid, text) => ProposalAction::Petition(maybe_cid, text),
+			ProposalAction::SpendCommunityTreasury(cid, beneficiary, amount) =>
+				ProposalAction::SpendCommunityTreasury(cid, beneficiary, amount),
+			ProposalAction::SetMeetupValidationPolicy(cid, policy) =>
+				ProposalAction::SetMeetupValidationPolicy(cid, policy),
+		}
+	}
 }
 
-impl ProposalAction {
-	pub fn get_access_policy(self) -> ProposalAccessPolicy {
+impl<AccountId: Encode, Moment, BoundedCall: Encode>
+	ProposalAction<AccountId, Moment, BoundedCall>
+{
+	pub fn get_access_policy(&self) -> ProposalAccessPolicy {
 		match self {
-			ProposalAction::UpdateNominalIncome(cid, _) => ProposalAccessPolicy::Community(cid),
-			ProposalAction::SetInactivityTimeout(_) => ProposalAccessPolicy::Global,
+			ProposalAction::UpdateNominalIncome(cid, _) => ProposalAccessPolicy::Community(*cid),
+			ProposalAction::UpdateDemurrage(cid, _) => ProposalAccessPolicy::Community(*cid),
+			ProposalAction::SpendCommunityTreasury(cid, _, _) =>
+				ProposalAccessPolicy::Community(*cid),
+			ProposalAction::SetMeetupValidationPolicy(cid, _) =>
+				ProposalAccessPolicy::Community(*cid),
+			ProposalAction::SetInactivityTimeout(_) |
+			ProposalAction::SetReputationLifetime(_) |
+			ProposalAction::SetEndorsementTicketsPerBootstrapper(_) |
+			ProposalAction::SetEndorsementTicketsPerReputable(_) |
+			ProposalAction::SetMeetupTimeOffset(_) |
+			ProposalAction::SetTimeTolerance(_) |
+			ProposalAction::SetLocationTolerance(_) |
+			ProposalAction::SetFeeConversionFactor(_) |
+			ProposalAction::Call(_) => ProposalAccessPolicy::Global,
+			ProposalAction::Petition(maybe_cid, _) => match maybe_cid {
+				Some(cid) => ProposalAccessPolicy::Community(*cid),
+				None => ProposalAccessPolicy::Global,
+			},
 		}
 	}
 
-	pub fn get_identifier(self) -> ProposalActionIdentifier {
+	/// Petitions have no on-chain effect. They are neither enacted nor do they supersede
+	/// each other.
+	pub fn is_petition(&self) -> bool {
+		matches!(self, ProposalAction::Petition(_, _))
+	}
+
+	pub fn get_identifier(&self) -> ProposalActionIdentifier {
 		match self {
 			ProposalAction::UpdateNominalIncome(cid, _) =>
-				ProposalActionIdentifier::UpdateNominalIncome(cid),
+				ProposalActionIdentifier::UpdateNominalIncome(*cid),
 			ProposalAction::SetInactivityTimeout(_) =>
 				ProposalActionIdentifier::SetInactivityTimeout,
+			ProposalAction::UpdateDemurrage(cid, _) =>
+				ProposalActionIdentifier::UpdateDemurrage(*cid),
+			ProposalAction::SetReputationLifetime(_) =>
+				ProposalActionIdentifier::SetReputationLifetime,
+			ProposalAction::SetEndorsementTicketsPerBootstrapper(_) =>
+				ProposalActionIdentifier::SetEndorsementTicketsPerBootstrapper,
+			ProposalAction::SetEndorsementTicketsPerReputable(_) =>
+				ProposalActionIdentifier::SetEndorsementTicketsPerReputable,
+			ProposalAction::SetMeetupTimeOffset(_) => ProposalActionIdentifier::SetMeetupTimeOffset,
+			ProposalAction::SetTimeTolerance(_) => ProposalActionIdentifier::SetTimeTolerance,
+			ProposalAction::SetLocationTolerance(_) =>
+				ProposalActionIdentifier::SetLocationTolerance,
+			ProposalAction::SetFeeConversionFactor(_) =>
+				ProposalActionIdentifier::SetFeeConversionFactor,
+			ProposalAction::Call(call) =>
+				ProposalActionIdentifier::Call(call.using_encoded(BlakeTwo256::hash)),
+			ProposalAction::Petition(maybe_cid, _) =>
+				ProposalActionIdentifier::Petition(*maybe_cid),
+			ProposalAction::SpendCommunityTreasury(cid, beneficiary, amount) =>
+				ProposalActionIdentifier::SpendCommunityTreasury(
+					*cid,
+					(beneficiary, amount).using_encoded(BlakeTwo256::hash),
+				),
+			ProposalAction::SetMeetupValidationPolicy(cid, _) =>
+				ProposalActionIdentifier::SetMeetupValidationPolicy(*cid),
 		}
 	}
 }
 
+impl<AccountId, BlockNumber, Moment, BoundedCall>
+	Proposal<AccountId, BlockNumber, Moment, BoundedCall>
+{
+	/// Maps the proposed call, e.g. to its hash for display purposes
+	pub fn map_call<Call>(
+		self,
+		f: impl FnOnce(BoundedCall) -> Call,
+	) -> Proposal<AccountId, BlockNumber, Moment, Call> {
+		Proposal {
+			proposer: self.proposer,
+			start: self.start,
+			start_cindex: self.start_cindex,
+			action: self.action.map_call(f),
+			state: self.state,
+		}
+	}
+}
+
+/// A proposal together with its current tally and evaluation
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct ProposalInfo<AccountId, BlockNumber, Moment, CallHash> {
+	pub proposal_id: ProposalIdType,
+	/// the proposal with a `Call` action represented by the hash of the call
+	pub proposal: Proposal<AccountId, BlockNumber, Moment, CallHash>,
+	pub tally: Tally,
+	pub electorate: ReputationCountType,
+	pub is_passing: bool,
+	/// blocks left until a `Confirming` proposal can be approved
+	pub remaining_confirmation_blocks: Option<BlockNumber>,
+}
+
+/// Voting power delegated to another account for a `ProposalAccessPolicy` scope
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub struct Delegation<AccountId> {
+	pub delegate: AccountId,
+	/// ceremony index at which the delegation was made. The delegation expires together with
+	/// reputation gained in that cycle.
+	pub cindex: CeremonyIndexType,
+}
+
+/// Deposit held from the proposer until the proposal is enacted or cancelled
+#[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
+pub enum ProposalDeposit<Balance> {
+	/// reserved in the native currency
+	Native(Balance),
+	/// held in an escrow account in the community currency
+	Community(
+		CommunityIdentifier,
+		#[cfg_attr(feature = "serde_derive", serde(with = "serialize_fixed"))] BalanceType,
+	),
+}
+
 #[derive(Encode, Decode, RuntimeDebug, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
 pub enum ProposalState<BlockNumber> {
 	Ongoing,
-	Confirming { since: BlockNumber },
+	Confirming {
+		since: BlockNumber,
+	},
 	Approved,
 	Cancelled,
 	Enacted,
+	/// approved, but the proposal action failed upon enactment
+	EnactmentFailed,
 }
 
 impl<BlockNumber: PartialEq> ProposalState<BlockNumber> {
@@ -95,9 +363,72 @@ impl<BlockNumber: PartialEq> ProposalState<BlockNumber> {
 #[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 #[cfg_attr(feature = "serde_derive", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde_derive", serde(rename_all = "camelCase"))]
-pub struct Proposal<BlockNumber> {
+pub struct Proposal<AccountId, BlockNumber, Moment, BoundedCall> {
+	pub proposer: AccountId,
 	pub start: BlockNumber,
 	pub start_cindex: CeremonyIndexType,
-	pub action: ProposalAction,
+	pub action: ProposalAction<AccountId, Moment, BoundedCall>,
 	pub state: ProposalState<BlockNumber>,
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn tally(turnout: VoteCountType, ayes: VoteCountType) -> Tally {
+		Tally { turnout, ayes }
+	}
+
+	#[test]
+	fn simple_majority_works() {
+		let strategy = VoteThreshold::SimpleMajority;
+		assert_eq!(strategy.is_passing(100, &tally(10, 6)), Some(true));
+		assert_eq!(strategy.is_passing(100, &tally(10, 5)), Some(false));
+		assert_eq!(strategy.is_passing(100, &tally(0, 0)), Some(false));
+	}
+
+	#[test]
+	fn supermajority_works() {
+		let strategy = VoteThreshold::Supermajority(Perbill::from_percent(66));
+		assert_eq!(strategy.is_passing(100, &tally(100, 67)), Some(true));
+		assert_eq!(strategy.is_passing(100, &tally(100, 66)), Some(false));
+		assert_eq!(strategy.is_passing(100, &tally(10, 6)), Some(false));
+		assert_eq!(strategy.is_passing(100, &tally(0, 0)), Some(false));
+	}
+
+	#[test]
+	fn positive_turnout_bias_works() {
+		let strategy = VoteThreshold::PositiveTurnoutBias;
+		// low turnout requires a supermajority of ayes
+		assert_eq!(strategy.is_passing(100, &tally(10, 6)), Some(false));
+		assert_eq!(strategy.is_passing(100, &tally(10, 9)), Some(true));
+		// full turnout is a simple majority
+		assert_eq!(strategy.is_passing(100, &tally(100, 51)), Some(true));
+		assert_eq!(strategy.is_passing(100, &tally(100, 50)), Some(false));
+		// no turnout
+		assert_eq!(strategy.is_passing(100, &tally(0, 0)), None);
+	}
+
+	#[test]
+	fn negative_turnout_bias_works() {
+		let strategy = VoteThreshold::NegativeTurnoutBias;
+		// low turnout requires a supermajority of nays to reject
+		assert_eq!(strategy.is_passing(100, &tally(10, 4)), Some(true));
+		assert_eq!(strategy.is_passing(100, &tally(10, 1)), Some(false));
+		// full turnout is a simple majority
+		assert_eq!(strategy.is_passing(100, &tally(100, 51)), Some(true));
+		assert_eq!(strategy.is_passing(100, &tally(100, 50)), Some(false));
+	}
+
+	#[test]
+	fn invalid_tally_is_rejected() {
+		assert_eq!(VoteThreshold::SimpleMajority.is_passing(100, &tally(1, 2)), None);
+	}
+	#[test]
+	fn vote_threshold_serializes_correctly() {
+		let threshold = VoteThreshold::Supermajority(Perbill::from_percent(60));
+		let json = serde_json::to_string(&threshold).unwrap();
+		assert_eq!(json, "{\"supermajority\":600000000}");
+		assert_eq!(serde_json::from_str::<VoteThreshold>(&json).unwrap(), threshold);
+	}
+}
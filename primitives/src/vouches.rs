@@ -20,6 +20,7 @@ use scale_info::TypeInfo;
 #[cfg(feature = "serde_derive")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
 
 /// Did the attester meet the attestee physically, virtually or through asynchronous messages?
 #[derive(Default, Encode, Decode, Copy, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
@@ -60,6 +61,17 @@ pub enum VouchKind {
 	AttendedEvent(PresenceType),
 }
 
+impl VouchKind {
+	/// true if the vouch testifies a live encounter with a human being
+	pub fn is_live_human_encounter(&self) -> bool {
+		matches!(
+			self,
+			Self::KnownHuman(PresenceType::LivePhysical | PresenceType::LiveVirtual) |
+				Self::EncounteredHuman(PresenceType::LivePhysical | PresenceType::LiveVirtual)
+		)
+	}
+}
+
 /// a scalar expression of quality. Interpretation left to client side per use case
 /// could be a 0-5 star rating or a high-resolution 0..255
 pub type Rating = u8;
@@ -89,3 +101,14 @@ pub struct Vouch<Moment> {
 	/// additional information about the attestee's qualities
 	pub quality: VouchQuality,
 }
+
+/// Read access to the vouches an attester has registered for an attestee
+pub trait VouchesProvider<AccountId, Moment> {
+	fn vouches(attestee: &AccountId, attester: &AccountId) -> Vec<Vouch<Moment>>;
+}
+
+impl<AccountId, Moment> VouchesProvider<AccountId, Moment> for () {
+	fn vouches(_attestee: &AccountId, _attester: &AccountId) -> Vec<Vouch<Moment>> {
+		Vec::new()
+	}
+}
@@ -19,10 +19,10 @@
 //extern crate node_primitives;
 
 use encointer_primitives::balances::{BalanceType, Demurrage};
-use frame_support::{ord_parameter_types, parameter_types, traits::EitherOfDiverse};
+use frame_support::{ord_parameter_types, parameter_types, traits::EitherOfDiverse, PalletId};
 use frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot, EnsureSignedBy};
 use sp_core::crypto::AccountId32;
-use sp_runtime::{generic, traits::IdentifyAccount, MultiSignature, Perbill};
+use sp_runtime::{generic, traits::IdentifyAccount, MultiSignature, Perbill, Permill};
 
 // convenience reexport such that the tests do not need to put sp-keyring in the Cargo.toml.
 pub use sp_keyring::AccountKeyring;
@@ -159,6 +159,7 @@ parameter_types! {
 	pub const DefaultDemurrage: Demurrage = Demurrage::from_bits(0x0000000000000000000001E3F0A8A973_i128);
 	/// 0.000005
 	pub const EncointerBalancesExistentialDeposit: BalanceType = BalanceType::from_bits(0x0000000000000000000053e2d6238da4_u128);
+	pub const CommunityTreasuryPalletId: PalletId = PalletId(*b"ecr/trsy");
 }
 
 #[macro_export]
@@ -170,6 +171,7 @@ macro_rules! impl_encointer_balances {
 			type ExistentialDeposit = EncointerBalancesExistentialDeposit;
 			type WeightInfo = ();
 			type CeremonyMaster = EnsureAlice;
+			type CommunityTreasuryPalletId = CommunityTreasuryPalletId;
 		}
 	};
 }
@@ -215,14 +217,19 @@ parameter_types! {
 	pub const MeetupSizeTarget: u64 = 10;
 	pub const MeetupMinSize: u64 = 3;
 	pub const MeetupNewbieLimitDivider: u64 = 3;
+	// rewards are fully issued to the participants unless a test enables the treasury
+	pub static CommunityTreasuryShare: Permill = Permill::zero();
 }
 
 #[macro_export]
 macro_rules! impl_encointer_ceremonies {
 	($t:ident) => {
+		impl_encointer_ceremonies!($t, EnsureAlice);
+	};
+	($t:ident, $ceremony_master:ty) => {
 		impl encointer_ceremonies::Config for $t {
 			type RuntimeEvent = RuntimeEvent;
-			type CeremonyMaster = EnsureAlice;
+			type CeremonyMaster = $ceremony_master;
 			type Public = <Signature as Verify>::Signer;
 			type Signature = Signature;
 			type RandomnessSource = test_utils::TestRandomness<$t>;
@@ -231,6 +238,13 @@ macro_rules! impl_encointer_ceremonies {
 			type MeetupNewbieLimitDivider = MeetupNewbieLimitDivider;
 			type WeightInfo = ();
 			type MaxAttestations = ConstU32<10>;
+			type CommunityTreasuryShare = CommunityTreasuryShare;
+			type Vouches = ();
+			type PenaltyThreshold = ConstU32<2>;
+			type MaxEndorsementBatchSize = ConstU32<10>;
+			type MaxClaimBatch = ConstU32<10>;
+			type MaxWaitlistSize = ConstU32<10>;
+			type MaxPartnerCommunities = ConstU32<10>;
 		}
 	};
 }
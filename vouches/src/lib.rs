@@ -17,11 +17,11 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use core::marker::PhantomData;
-use encointer_primitives::vouches::{Vouch, VouchKind, VouchQuality};
+use encointer_primitives::vouches::{Vouch, VouchKind, VouchQuality, VouchesProvider};
 use frame_system::{self as frame_system, ensure_signed, pallet_prelude::OriginFor};
 use log::info;
 pub use pallet::*;
-use sp_std::convert::TryInto;
+use sp_std::{convert::TryInto, vec::Vec};
 pub use weights::WeightInfo;
 // Logger target
 const LOG: &str = "encointer::vouches";
@@ -103,3 +103,9 @@ pub mod pallet {
 		ValueQuery,
 	>;
 }
+
+impl<T: Config> VouchesProvider<T::AccountId, T::Moment> for Pallet<T> {
+	fn vouches(attestee: &T::AccountId, attester: &T::AccountId) -> Vec<Vouch<T::Moment>> {
+		<Vouches<T>>::get(attestee, attester).into_inner()
+	}
+}